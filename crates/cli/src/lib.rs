@@ -1,44 +1,193 @@
 use std::env;
 use std::fs;
 use std::io::{self, Read};
+use std::path::Path;
+use std::time::{Duration, Instant};
 
-use edge_rules::runtime::edge_rules::EdgeRulesModel;
+use edge_rules::runtime::edge_rules::{EdgeRulesModel, EvaluationObserver};
+use edge_rules::runtime::execution_context::ExecutionContext;
+use edge_rules::typesystem::errors::RuntimeError;
+use edge_rules::typesystem::values::ValueEnum;
 
 pub fn run() -> Result<(), String> {
     let args: Vec<String> = env::args().skip(1).collect();
 
+    if args.first().is_some_and(|arg| arg == "--watch") {
+        let path = args.get(1).and_then(|arg| arg.strip_prefix('@')).ok_or("Usage: edgerules --watch @model.er")?;
+        return watch(Path::new(path));
+    }
+
+    if args.first().is_some_and(|arg| arg == "--time") {
+        let code = read_code(&args[1..])?;
+        for (path, duration) in profile(&code)? {
+            println!("{:>10.3} ms  {}", duration.as_secs_f64() * 1000.0, path);
+        }
+        return Ok(());
+    }
+
+    if args.first().is_some_and(|arg| arg == "--json") {
+        let code = read_code(&args[1..])?;
+        println!("{}", evaluate_json(&code)?);
+        return Ok(());
+    }
+
     let code = read_code(&args)?;
+    println!("{}", evaluate(&code)?);
+
+    Ok(())
+}
+
+/// Evaluates the model and returns the printable result: `value` field if present, otherwise the whole context
+pub fn evaluate(code: &str) -> Result<String, String> {
+    if let Some(output) = eval_value(code)? {
+        return Ok(output);
+    }
+
+    let service = load_model(code)?;
+    let runtime = service.to_runtime().map_err(|error| error.to_string())?;
+    let mut failed = FailedField { error: None };
+    runtime.eval_all_observed(&mut failed).map_err(|error| error.to_string())?;
+    if let Some(error) = failed.error {
+        return Err(error);
+    }
+    let output = runtime.context.borrow().to_code();
+
+    Ok(output)
+}
+
+/// Evaluates the model and returns the whole context as JSON
+pub fn evaluate_json(code: &str) -> Result<String, String> {
+    let service = load_model(code)?;
+    let runtime = service.to_runtime().map_err(|error| error.to_string())?;
+
+    ExecutionContext::to_json(&runtime.context).map_err(|error| error.to_string())
+}
+
+/// Evaluates all fields and returns evaluation time of each field path, slowest first
+pub fn profile(code: &str) -> Result<Vec<(String, Duration)>, String> {
+    let service = load_model(code)?;
+    let runtime = service.to_runtime().map_err(|error| error.to_string())?;
+
+    let mut timer = FieldTimer { started: Vec::new(), timings: Vec::new() };
+    runtime.eval_all_observed(&mut timer).map_err(|error| error.to_string())?;
+
+    timer.timings.sort_by(|(_, left), (_, right)| right.cmp(left));
+    Ok(timer.timings)
+}
+
+struct FieldTimer {
+    started: Vec<Instant>,
+    timings: Vec<(String, Duration)>,
+}
+
+impl EvaluationObserver for FieldTimer {
+    fn before_field(&mut self, _path: &[String]) {
+        self.started.push(Instant::now());
+    }
 
-    match eval_value(&code) {
-        Ok(Some(output)) => {
-            println!("{}", output);
+    fn after_field(&mut self, path: &[String], _result: &Result<ValueEnum, RuntimeError>) {
+        if let Some(started) = self.started.pop() {
+            self.timings.push((path.join("."), started.elapsed()));
         }
-        _ => {
-            let mut service = EdgeRulesModel::new();
-            match service.append_source(&code) {
-                Ok(()) => match service.to_runtime() {
-                    Ok(runtime) => match runtime.eval_all() {
-                        Ok(()) => println!("{}", runtime.context.borrow().to_code()),
-                        Err(error) => println!("{}", error),
-                    },
-                    Err(error) => println!("{}", error),
-                },
-                Err(error) => println!("{}", error),
-            }
+    }
+}
+
+/// Remembers the error of the first field that failed to evaluate
+struct FailedField {
+    error: Option<String>,
+}
+
+impl EvaluationObserver for FailedField {
+    fn before_field(&mut self, _path: &[String]) {}
+
+    fn after_field(&mut self, _path: &[String], result: &Result<ValueEnum, RuntimeError>) {
+        if let (None, Err(error)) = (&self.error, result) {
+            self.error = Some(error.to_string());
+        }
+    }
+}
+
+/// Re-reads the model file and evaluates it
+pub fn evaluate_file(path: &Path) -> Result<String, String> {
+    let code =
+        fs::read_to_string(path).map_err(|error| format!("Failed to read file '{}': {}", path.display(), error))?;
+    evaluate(&code)
+}
+
+#[cfg(feature = "watch")]
+fn watch(path: &Path) -> Result<(), String> {
+    watch_file(path, |result| {
+        match result {
+            Ok(output) => println!("{}", output),
+            Err(error) => eprintln!("{}", error),
+        }
+        true
+    })
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch(_path: &Path) -> Result<(), String> {
+    Err("Watch mode is not available, build the CLI with the `watch` feature".to_string())
+}
+
+/// Evaluates the file once and then after every debounced change, until `on_result` returns false.
+/// The parent directory is watched, because editors often save by replacing the file.
+#[cfg(feature = "watch")]
+pub fn watch_file<F>(path: &Path, mut on_result: F) -> Result<(), String>
+where
+    F: FnMut(Result<String, String>) -> bool,
+{
+    use notify_debouncer_mini::new_debouncer;
+    use notify_debouncer_mini::notify::RecursiveMode;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(200);
+
+    let path = fs::canonicalize(path).map_err(|error| format!("Failed to watch '{}': {}", path.display(), error))?;
+    let directory =
+        path.parent().ok_or_else(|| format!("Failed to watch '{}': no parent directory", path.display()))?;
+
+    if !on_result(evaluate_file(&path)) {
+        return Ok(());
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, sender).map_err(|error| error.to_string())?;
+    debouncer
+        .watcher()
+        .watch(directory, RecursiveMode::NonRecursive)
+        .map_err(|error| error.to_string())?;
+
+    for events in receiver {
+        let changed = events.map_err(|error| error.to_string())?.iter().any(|event| event.path == path);
+        if changed && !on_result(evaluate_file(&path)) {
+            break;
         }
     }
 
     Ok(())
 }
 
+/// Parses the model, `import "path"` sources are read from the file system relative to the working directory
+fn load_model(code: &str) -> Result<EdgeRulesModel, String> {
+    let mut model = EdgeRulesModel::new();
+    model.set_import_resolver(|path| fs::read_to_string(path).map_err(|error| error.to_string()));
+    model.append_source(code).map_err(|error| error.to_string())?;
+    Ok(model)
+}
+
 fn eval_value(code: &str) -> Result<Option<String>, String> {
-    let mut service = EdgeRulesModel::new();
-    service.append_source(code).map_err(|e| e.to_string())?;
+    let service = load_model(code)?;
     let runtime = service.to_runtime().map_err(|e| e.to_string())?;
 
+    if !runtime.static_tree.borrow().field_name_set.contains("value") {
+        return Ok(None);
+    }
+
     match runtime.evaluate_field("value") {
         Ok(val) => Ok(Some(format!("{}", val))),
-        Err(_) => Ok(None),
+        Err(err) => Err(err.to_string()),
     }
 }
 
@@ -0,0 +1,73 @@
+use std::process::{Command, Output};
+
+fn run_er(code: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_er")).arg(code).output().expect("er binary should start")
+}
+
+#[test]
+fn successful_evaluation_exits_with_zero() {
+    let output = run_er("{ value: 1 + 2 }");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "3");
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn malformed_model_exits_with_error() {
+    let output = run_er("{ value: 1 + }");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(!output.stderr.is_empty());
+}
+
+#[test]
+fn linking_error_exits_with_error() {
+    let output = run_er("{ value: missingField + 1 }");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missingField"));
+}
+
+#[test]
+fn runtime_error_exits_with_error() {
+    let output = run_er("{ value: 10 / 0 }");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Division by zero"));
+
+    let output = run_er("{ rate: 10 / 0; other: 1 }");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Division by zero"));
+}
+
+#[test]
+fn json_flag_prints_evaluated_context() {
+    let output = Command::new(env!("CARGO_BIN_EXE_er"))
+        .args(["--json", "{ value: 1 + 2; offer: { rate: 0.5 } }"])
+        .output()
+        .expect("er binary should start");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), r#"{"value":3,"offer":{"rate":0.5}}"#);
+}
+
+#[test]
+fn imports_are_read_relative_to_working_directory() {
+    let directory = std::env::temp_dir().join(format!("er-import-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("common.er"), "{ type Money: {amount: <number>} }").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_er"))
+        .arg("{ import \"common.er\"\n value: ({amount: 5} as Money).amount * 2 }")
+        .current_dir(&directory)
+        .output()
+        .expect("er binary should start");
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "10");
+}
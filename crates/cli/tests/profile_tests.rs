@@ -0,0 +1,33 @@
+#[test]
+fn profile_reports_every_evaluated_field() {
+    let timings = edge_rules_cli::profile(
+        r#"
+        {
+            a: 1 + 2
+            b: { c: sum(for x in 1..100 return x * 2); d: 'text' }
+            func inc(x): x + 1
+            e: inc(a)
+        }
+        "#,
+    )
+    .expect("profile model");
+
+    let mut paths: Vec<&str> = timings.iter().map(|(path, _)| path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["a", "b.c", "b.d", "e"]);
+
+    assert!(timings.windows(2).all(|pair| pair[0].1 >= pair[1].1), "timings must be sorted, slowest first");
+}
+
+#[test]
+fn time_flag_prints_field_table() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_er"))
+        .args(["--time", "{ a: 1; b: a + 1 }"])
+        .output()
+        .expect("er binary should start");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.lines().all(|line| line.contains(" ms  ")));
+}
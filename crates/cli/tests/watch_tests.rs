@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::PathBuf;
+
+fn model_file(name: &str, code: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("edgerules-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&directory).expect("temp directory");
+    let path = directory.join("model.er");
+    fs::write(&path, code).expect("model file");
+    path
+}
+
+#[test]
+fn evaluate_file_reads_latest_content() {
+    let path = model_file("evaluate", "{ value: 1 + 1 }");
+    assert_eq!(edge_rules_cli::evaluate_file(&path), Ok("2".to_string()));
+
+    fs::write(&path, "{ value: 2 * 5 }").expect("update model file");
+    assert_eq!(edge_rules_cli::evaluate_file(&path), Ok("10".to_string()));
+
+    fs::write(&path, "{ value: 2 * }").expect("break model file");
+    assert!(edge_rules_cli::evaluate_file(&path).is_err());
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn watch_file_reevaluates_on_change() {
+    use std::thread;
+    use std::time::Duration;
+
+    let path = model_file("watch", "{ value: 1 }");
+    let writer_path = path.clone();
+    let writer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(500));
+        fs::write(&writer_path, "{ value: 2 }").expect("update model file");
+    });
+
+    let mut results = Vec::new();
+    edge_rules_cli::watch_file(&path, |result| {
+        results.push(result);
+        results.len() < 2
+    })
+    .expect("watch model file");
+    writer.join().expect("writer thread");
+
+    assert_eq!(results, vec![Ok("1".to_string()), Ok("2".to_string())]);
+}
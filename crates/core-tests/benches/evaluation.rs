@@ -0,0 +1,66 @@
+//! Baseline timings for the loan offer model, run with `just bench` or
+//! `cargo bench -p edge-rules-core-tests --bench evaluation`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use edge_rules::runtime::edge_rules::{EdgeRulesModel, FrozenModel};
+use edge_rules::runtime::execution_context::ExecutionContext;
+use std::hint::black_box;
+
+const LOAN_OFFER: &str = include_str!("loan_offer.txt");
+
+fn frozen_model() -> FrozenModel {
+    let mut model = EdgeRulesModel::new();
+    model.append_source(LOAN_OFFER).expect("loan offer model parses");
+    model.freeze().expect("loan offer model links")
+}
+
+/// Parsing and linking from source, the cost paid once per model change
+fn linking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("linking");
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            let mut model = EdgeRulesModel::new();
+            model.append_source(black_box(LOAN_OFFER)).unwrap();
+            model
+        })
+    });
+    group.bench_function("parse_and_link", |b| {
+        b.iter(|| {
+            let mut model = EdgeRulesModel::new();
+            model.append_source(black_box(LOAN_OFFER)).unwrap();
+            model.to_runtime().unwrap()
+        })
+    });
+    group.finish();
+}
+
+/// Evaluation of every field on a fresh runtime of an already linked model
+fn evaluation(c: &mut Criterion) {
+    let frozen = frozen_model();
+    let mut group = c.benchmark_group("evaluation");
+    group.bench_function("eval_all", |b| b.iter(|| frozen.to_runtime().eval_all().unwrap()));
+    group.bench_function("to_json", |b| {
+        b.iter(|| {
+            let runtime = frozen.to_runtime();
+            runtime.eval_all().unwrap();
+            ExecutionContext::to_json(&runtime.context).unwrap()
+        })
+    });
+    group.finish();
+}
+
+/// Single field evaluation, on a fresh runtime and repeated on a runtime that already evaluated it
+fn evaluate_field(c: &mut Criterion) {
+    let frozen = frozen_model();
+    let mut group = c.benchmark_group("evaluate_field");
+    group.bench_function("fresh_runtime", |b| {
+        b.iter(|| frozen.to_runtime().evaluate_field(black_box("loanOffer1")).unwrap())
+    });
+
+    let runtime = frozen.to_runtime();
+    group.bench_function("repeated", |b| b.iter(|| runtime.evaluate_field(black_box("totalMonthly")).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, linking, evaluation, evaluate_field);
+criterion_main!(benches);
@@ -9,8 +9,30 @@ pub use utilities::*;
 #[test]
 fn test_unary_numeric_validation() {
     let numeric_funcs = [
-        "abs", "floor", "ceiling", "trunc", "sqrt", "ln", "log10", "exp", "degrees", "radians", "sin", "cos", "tan",
-        "asin", "acos", "atan",
+        "abs",
+        "sign",
+        "factorial",
+        "floor",
+        "ceiling",
+        "trunc",
+        "sqrt",
+        "ln",
+        "log10",
+        "exp",
+        "degrees",
+        "radians",
+        "sin",
+        "cos",
+        "tan",
+        "asin",
+        "acos",
+        "atan",
+        "sinh",
+        "cosh",
+        "tanh",
+        "asinh",
+        "acosh",
+        "atanh",
     ];
 
     for func in numeric_funcs {
@@ -35,7 +57,17 @@ fn test_unary_numeric_validation() {
 
 #[test]
 fn test_unary_string_validation() {
-    let string_funcs = ["length", "toUpperCase", "toLowerCase", "trim", "toBase64", "fromBase64", "sanitizeFilename"];
+    let string_funcs = [
+        "length",
+        "toUpperCase",
+        "toLowerCase",
+        "trim",
+        "trimStart",
+        "trimEnd",
+        "toBase64",
+        "fromBase64",
+        "sanitizeFilename",
+    ];
 
     for func in string_funcs {
         // 0 args -> Parse Error
@@ -60,7 +92,18 @@ fn test_unary_string_validation() {
 #[test]
 fn test_unary_list_validation() {
     // List functions that expect list of numbers
-    let numeric_list_funcs = ["product", "mean", "median", "stddev"];
+    let numeric_list_funcs = [
+        "product",
+        "cumulativeSum",
+        "indexOfMax",
+        "indexOfMin",
+        "mean",
+        "median",
+        "stddev",
+        "stddevSample",
+        "variance",
+        "varianceSample",
+    ];
     for func in numeric_list_funcs {
         let code = format!("{{ value: {}() }}", func);
         parse_error_contains(&code, &[&format!("Function '{}' got no arguments", func)]);
@@ -113,27 +156,28 @@ fn test_unary_list_validation() {
         let code = format!("{{ value: {}() }}", func);
         parse_error_contains(&code, &[&format!("Function '{}' got no arguments", func)]);
 
-        // count is special, accepts range or number too
+        // count is special, accepts range, number or string too
         if func == "count" {
-            let code = format!("{{ value: {}('abc') }}", func);
+            let code = format!("{{ value: {}(true) }}", func);
             link_error_location(
                 &code,
                 &["value"],
-                &format!("{}('abc')", func),
+                &format!("{}(true)", func),
                 LinkingErrorEnum::TypesNotCompatible(
                     None,
-                    ValueType::StringType,
+                    ValueType::BooleanType,
                     Some(vec![
                         ValueType::NumberType,
                         ValueType::RangeType,
+                        ValueType::StringType,
                         ValueType::ListType(Some(Box::new(ValueType::NumberType))),
                     ]),
                 ),
             );
         } else {
             let code = format!("{{ value: {}('abc') }}", func);
-            // reverse accepts strings too
-            if func != "reverse" {
+            // reverse and isEmpty accept strings too
+            if func != "reverse" && func != "isEmpty" {
                 link_error_location(
                     &code,
                     &["value"],
@@ -155,7 +199,22 @@ fn test_unary_list_validation() {
 
 #[test]
 fn test_unary_date_validation() {
-    let date_funcs = ["date", "time", "datetime", "duration", "period", "dayOfWeek", "monthOfYear", "lastDayOfMonth"];
+    let date_funcs = [
+        "date",
+        "time",
+        "datetime",
+        "duration",
+        "period",
+        "dayOfWeek",
+        "monthOfYear",
+        "lastDayOfMonth",
+        "absDuration",
+        "durationSign",
+        "isWeekend",
+        "toUtc",
+        "parseDuration",
+        "parsePeriod",
+    ];
 
     for func in date_funcs {
         // 0 args -> Parse Error
@@ -164,7 +223,8 @@ fn test_unary_date_validation() {
 
         // Wrong type -> Link Error
         // Parsers expect string
-        let parse_funcs = ["date", "time", "datetime", "duration", "period"];
+        let parse_funcs = ["date", "time", "datetime", "duration", "period", "parseDuration", "parsePeriod"];
+        let duration_funcs = ["absDuration", "durationSign"];
         if parse_funcs.contains(&func) {
             let code = format!("{{ value: {}(123) }}", func);
             link_error_location(
@@ -173,6 +233,34 @@ fn test_unary_date_validation() {
                 &format!("{}(123)", func),
                 LinkingErrorEnum::TypesNotCompatible(None, ValueType::NumberType, Some(vec![ValueType::StringType])),
             );
+        } else if func == "isWeekend" {
+            let code = format!("{{ value: {}(123) }}", func);
+            link_error_location(
+                &code,
+                &["value"],
+                &format!("{}(123)", func),
+                LinkingErrorEnum::TypesNotCompatible(
+                    None,
+                    ValueType::NumberType,
+                    Some(vec![ValueType::DateType, ValueType::DateTimeType]),
+                ),
+            );
+        } else if func == "toUtc" {
+            let code = format!("{{ value: {}(123) }}", func);
+            link_error_location(
+                &code,
+                &["value"],
+                &format!("{}(123)", func),
+                LinkingErrorEnum::TypesNotCompatible(None, ValueType::NumberType, Some(vec![ValueType::DateTimeType])),
+            );
+        } else if duration_funcs.contains(&func) {
+            let code = format!("{{ value: {}(123) }}", func);
+            link_error_location(
+                &code,
+                &["value"],
+                &format!("{}(123)", func),
+                LinkingErrorEnum::TypesNotCompatible(None, ValueType::NumberType, Some(vec![ValueType::DurationType])),
+            );
         } else {
             // Helpers expect date
             let code = format!("{{ value: {}(123) }}", func);
@@ -193,7 +281,7 @@ fn test_unary_date_validation() {
 #[test]
 fn test_binary_validation() {
     // Math
-    let math_funcs = ["modulo", "idiv", "atan2"];
+    let math_funcs = ["modulo", "idiv", "pow", "gcd", "lcm", "binomial", "logBase", "atan2"];
     for func in math_funcs {
         let code = format!("{{ value: {}(1) }}", func);
         parse_error_contains(&code, &[&format!("Binary function '{}' expected 2 arguments, but got 1", func)]);
@@ -208,7 +296,17 @@ fn test_binary_validation() {
     }
 
     // String
-    let string_funcs = ["startsWith", "endsWith", "split", "regexSplit", "substringBefore", "substringAfter"];
+    let string_funcs = [
+        "startsWith",
+        "endsWith",
+        "containsIgnoreCase",
+        "equalsIgnoreCase",
+        "split",
+        "regexSplit",
+        "matches",
+        "substringBefore",
+        "substringAfter",
+    ];
     for func in string_funcs {
         let code = format!("{{ value: {}(1) }}", func);
         parse_error_contains(&code, &[&format!("Binary function '{}' expected 2 arguments, but got 1", func)]);
@@ -289,23 +387,35 @@ fn test_extrema_validation() {
                 ),
             );
         } else {
+            // strings are comparable, so min and max reject booleans instead
             link_error_location(
-                &code,
+                &format!("{{ value: {}(1, true) }}", func),
                 &["value"],
-                &format!("{}(1, 'a')", func),
+                &format!("{}(1, true)", func),
                 LinkingErrorEnum::TypesNotCompatible(
                     None,
-                    ValueType::StringType,
+                    ValueType::BooleanType,
                     Some(vec![
                         ValueType::NumberType,
                         ValueType::DateType,
                         ValueType::TimeType,
                         ValueType::DateTimeType,
                         ValueType::DurationType,
+                        ValueType::StringType,
                     ]),
                 ),
             );
+            link_error_contains(&code, &["function arguments"]);
         }
+
+        // No arguments
+        let code = format!("{{ value: {}() }}", func);
+        link_error_location(
+            &code,
+            &["value"],
+            &format!("{}()", func),
+            LinkingErrorEnum::OtherLinkingError(format!("{} accepts at least 1 argument, got 0", func)),
+        );
     }
 }
 
@@ -316,10 +426,23 @@ fn test_pi_validation() {
         code,
         &["value"],
         "pi(1)",
-        LinkingErrorEnum::OtherLinkingError("Expects 0 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("pi accepts no arguments, got 1".to_string()),
     );
 }
 
+#[test]
+fn test_clock_functions_validation() {
+    for func in ["today", "now"] {
+        let code = format!("{{ value: {}(1) }}", func);
+        link_error_location(
+            &code,
+            &["value"],
+            &format!("{}(1)", func),
+            LinkingErrorEnum::OtherLinkingError(format!("{} accepts no arguments, got 1", func)),
+        );
+    }
+}
+
 #[test]
 fn test_other_string_multi_validation() {
     // regexReplace (3 or 4 args)
@@ -328,7 +451,7 @@ fn test_other_string_multi_validation() {
         code,
         &["value"],
         "regexReplace('a', 'b')",
-        LinkingErrorEnum::OtherLinkingError("replace expects 3 or 4 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("regexReplace accepts 3 or 4 arguments, got 2".to_string()),
     );
 
     // replaceFirst (3 args)
@@ -337,7 +460,7 @@ fn test_other_string_multi_validation() {
         code,
         &["value"],
         "replaceFirst('a', 'b')",
-        LinkingErrorEnum::OtherLinkingError("replace expects 3 or 4 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("replaceFirst accepts 3 or 4 arguments, got 2".to_string()),
     );
 
     // replaceLast (3 args)
@@ -346,7 +469,27 @@ fn test_other_string_multi_validation() {
         code,
         &["value"],
         "replaceLast('a', 'b')",
-        LinkingErrorEnum::OtherLinkingError("replace expects 3 or 4 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("replaceLast accepts 3 or 4 arguments, got 2".to_string()),
+    );
+
+    // extract (3 args, numeric group)
+    let code = "{ value: extract('a', 'b') }";
+    link_error_location(
+        code,
+        &["value"],
+        "extract('a', 'b')",
+        LinkingErrorEnum::OtherLinkingError("extract accepts 3 arguments, got 2".to_string()),
+    );
+    let code = "{ value: extract('a', 'b', 'c') }";
+    link_error_location(
+        code,
+        &["value"],
+        "extract('a', 'b', 'c')",
+        LinkingErrorEnum::TypesNotCompatible(
+            Some("group".to_string()),
+            ValueType::StringType,
+            Some(vec![ValueType::NumberType]),
+        ),
     );
 
     // fromCharCode (args must be numbers)
@@ -371,7 +514,7 @@ fn test_round_validation() {
         code,
         &["value"],
         "round()",
-        LinkingErrorEnum::OtherLinkingError("round functions expect 1 or 2 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("round accepts 1 or 2 arguments, got 0".to_string()),
     );
 
     // 3 args
@@ -380,7 +523,7 @@ fn test_round_validation() {
         code,
         &["value"],
         "round(1, 2, 3)",
-        LinkingErrorEnum::OtherLinkingError("round functions expect 1 or 2 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("round accepts 1 or 2 arguments, got 3".to_string()),
     );
 
     // Wrong type (arg 1)
@@ -400,7 +543,7 @@ fn test_round_up_validation() {
         code,
         &["value"],
         "roundUp()",
-        LinkingErrorEnum::OtherLinkingError("round functions expect 1 or 2 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("roundUp accepts 1 or 2 arguments, got 0".to_string()),
     );
 }
 
@@ -411,7 +554,7 @@ fn test_round_down_validation() {
         code,
         &["value"],
         "roundDown(1, 2, 3)",
-        LinkingErrorEnum::OtherLinkingError("round functions expect 1 or 2 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("roundDown accepts 1 or 2 arguments, got 3".to_string()),
     );
 }
 
@@ -423,7 +566,7 @@ fn test_clamp_validation() {
         code,
         &["value"],
         "clamp(1, 2)",
-        LinkingErrorEnum::OtherLinkingError("clamp expects 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("clamp accepts 3 arguments, got 2".to_string()),
     );
 
     // 4 args
@@ -432,7 +575,7 @@ fn test_clamp_validation() {
         code,
         &["value"],
         "clamp(1, 2, 3, 4)",
-        LinkingErrorEnum::OtherLinkingError("clamp expects 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("clamp accepts 3 arguments, got 4".to_string()),
     );
 
     // Wrong type
@@ -445,6 +588,27 @@ fn test_clamp_validation() {
     );
 }
 
+#[test]
+fn test_lerp_validation() {
+    for func in ["lerp", "lerpUnclamped"] {
+        let code = format!("{{ value: {}(1, 2) }}", func);
+        link_error_location(
+            &code,
+            &["value"],
+            &format!("{}(1, 2)", func),
+            LinkingErrorEnum::OtherLinkingError(format!("{} accepts 3 arguments, got 2", func)),
+        );
+
+        let code = format!("{{ value: {}(1, 'b', 0.5) }}", func);
+        link_error_location(
+            &code,
+            &["value"],
+            &format!("{}(1, 'b', 0.5)", func),
+            LinkingErrorEnum::TypesNotCompatible(None, ValueType::StringType, Some(vec![ValueType::NumberType])),
+        );
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // String Functions Validation
 // -------------------------------------------------------------------------------------------------
@@ -457,7 +621,7 @@ fn test_substring_validation() {
         code,
         &["value"],
         "substring('abc')",
-        LinkingErrorEnum::OtherLinkingError("substring expects 2 or 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("substring accepts 2 or 3 arguments, got 1".to_string()),
     );
 
     // 4 args
@@ -466,7 +630,7 @@ fn test_substring_validation() {
         code,
         &["value"],
         "substring('abc', 0, 1, 2)",
-        LinkingErrorEnum::OtherLinkingError("substring expects 2 or 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("substring accepts 2 or 3 arguments, got 4".to_string()),
     );
 
     // Wrong type (arg 2 must be number)
@@ -491,7 +655,7 @@ fn test_replace_validation() {
         code,
         &["value"],
         "replace('abc', 'a')",
-        LinkingErrorEnum::OtherLinkingError("replace expects 3 or 4 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("replace accepts 3 or 4 arguments, got 2".to_string()),
     );
 
     // 5 args
@@ -500,7 +664,7 @@ fn test_replace_validation() {
         code,
         &["value"],
         "replace('abc', 'a', 'b', 'i', 'x')",
-        LinkingErrorEnum::OtherLinkingError("replace expects 3 or 4 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("replace accepts 3 or 4 arguments, got 5".to_string()),
     );
 }
 
@@ -512,7 +676,7 @@ fn test_pad_validation() {
         code,
         &["value"],
         "padStart('abc', 5)",
-        LinkingErrorEnum::OtherLinkingError("padStart/padEnd expects 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("padStart accepts 3 arguments, got 2".to_string()),
     );
 
     // padEnd 4 args
@@ -521,7 +685,7 @@ fn test_pad_validation() {
         code,
         &["value"],
         "padEnd('abc', 5, ' ', 'x')",
-        LinkingErrorEnum::OtherLinkingError("padStart/padEnd expects 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("padEnd accepts 3 arguments, got 4".to_string()),
     );
 }
 
@@ -537,7 +701,7 @@ fn test_sublist_validation() {
         code,
         &["value"],
         "sublist([1, 2])",
-        LinkingErrorEnum::OtherLinkingError("sublist expects 2 or 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("sublist accepts 2 or 3 arguments, got 1".to_string()),
     );
 
     // 4 args
@@ -546,7 +710,7 @@ fn test_sublist_validation() {
         code,
         &["value"],
         "sublist([1, 2], 1, 1, 1)",
-        LinkingErrorEnum::OtherLinkingError("sublist expects 2 or 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("sublist accepts 2 or 3 arguments, got 4".to_string()),
     );
 }
 
@@ -605,7 +769,7 @@ fn test_insert_before_validation() {
         code,
         &["value"],
         "insertBefore([1], 1)",
-        LinkingErrorEnum::OtherLinkingError("insertBefore expects 3 arguments".to_string()),
+        LinkingErrorEnum::OtherLinkingError("insertBefore accepts 3 arguments, got 2".to_string()),
     );
 }
 
@@ -4,7 +4,9 @@ use std::rc::Rc;
 
 use edge_rules::runtime::edge_rules::DefinitionEnum::UserFunction as UserFunctionDef;
 use edge_rules::runtime::{
-    edge_rules::{link_parts, ContextObjectBuilder, EvalError, ExpressionEnum, FunctionDefinition},
+    edge_rules::{
+        link_parts, ContextObjectBuilder, EdgeRulesModel, EvalError, ExpressionEnum, FunctionDefinition, MergePolicy,
+    },
     TypedValue,
 };
 use edge_rules::test_support::expr;
@@ -88,3 +90,43 @@ fn test_nesting() -> Result<(), EvalError> {
 
     Ok(())
 }
+
+fn model_from(code: &str) -> EdgeRulesModel {
+    let mut model = EdgeRulesModel::new();
+    model.append_source(code).expect("model should parse");
+    model
+}
+
+#[test]
+fn test_merge_base_types_with_overlay() -> Result<(), EvalError> {
+    let mut base = model_from("{ type X: {amount: <number>}; rate: 2 }");
+    let overlay = model_from("{ value: {amount: 5} as X; doubled: value.amount * rate }");
+
+    base.ast_root.merge(overlay.ast_root, MergePolicy::ErrorOnDuplicate)?;
+
+    let runtime = base.to_runtime()?;
+    assert_eq!(runtime.evaluate_field("doubled")?.to_string(), "10");
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_conflict_policies() -> Result<(), EvalError> {
+    let mut base = model_from("{ type X: {a: <number>}; rate: 2; func f(x): x + rate }");
+
+    let err = base.ast_root.merge(model_from("{ rate: 3 }").ast_root, MergePolicy::ErrorOnDuplicate).err();
+    assert_eq!(err.map(|err| err.to_string()), Some("Duplicate field 'rate'".to_string()));
+    let err = base
+        .ast_root
+        .merge(model_from("{ type X: {b: <number>} }").ast_root, MergePolicy::ErrorOnDuplicate)
+        .err();
+    assert_eq!(err.map(|err| err.to_string()), Some("Duplicate user type 'X'".to_string()));
+
+    base.ast_root.merge(model_from("{ rate: 3; result: f(1) }").ast_root, MergePolicy::Override)?;
+
+    let runtime = base.to_runtime()?;
+    assert_eq!(runtime.evaluate_field("result")?.to_string(), "4");
+    assert_eq!(runtime.static_tree.borrow().get_field_names(), vec!["rate", "f", "result"]);
+
+    Ok(())
+}
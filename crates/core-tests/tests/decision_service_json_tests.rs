@@ -0,0 +1,98 @@
+use edge_rules::runtime::decision_service::DecisionService;
+use edge_rules::runtime::TypedValue;
+use edge_rules::test_support::ValueEnum;
+use serde_json::{json, Value};
+
+mod utilities;
+pub use utilities::*;
+
+fn from_json(json: Value) -> ValueEnum {
+    ValueEnum::try_from(json).expect("JSON should convert to a value")
+}
+
+fn round_trip(json: Value) -> Value {
+    let value = from_json(json);
+    serde_json::from_str(&value.to_json().expect("value should serialize")).expect("output should be valid JSON")
+}
+
+#[test]
+fn json_primitives_round_trip() {
+    assert_eq!(from_json(json!(7)), ValueEnum::from(7));
+    assert_eq!(from_json(json!(0.1)).to_string(), "0.1");
+    assert_eq!(from_json(json!(true)).to_string(), "true");
+    assert_eq!(from_json(json!("x")).to_string(), "'x'");
+
+    for json in [json!(7), json!(-2.5), json!(true), json!("text"), json!([1, 2, 3]), json!([])] {
+        assert_eq!(round_trip(json.clone()), json);
+    }
+}
+
+#[test]
+fn json_objects_and_lists_round_trip() {
+    let json = json!({
+        "name": "Alice",
+        "age": 30,
+        "address": { "city": "Vilnius", "zip": "01100" },
+        "tags": ["a", "b"],
+        "orders": [{ "id": 1, "total": 9.99 }, { "id": 2, "total": 20 }]
+    });
+
+    assert_eq!(round_trip(json.clone()), json);
+}
+
+#[test]
+fn json_date_strings_stay_strings() {
+    let value = from_json(json!({ "birthday": "2000-01-31" }));
+    assert_string_contains("birthday: '2000-01-31'", value.to_string());
+}
+
+#[test]
+fn json_null_and_mixed_lists_are_rejected() {
+    let err = ValueEnum::try_from(json!({ "a": null })).unwrap_err();
+    assert_string_contains("null values are not supported", err.to_string());
+
+    assert!(ValueEnum::try_from(json!([1, "a"])).is_err());
+
+    let err = ValueEnum::try_from(json!([null, null])).unwrap_err();
+    assert_string_contains("only nulls has no item type", err.to_string());
+}
+
+#[test]
+fn json_null_list_items_are_missing() {
+    assert_eq!(from_json(json!([1, null, 3])).to_string(), "[1, Missing('N/A'), 3]");
+    assert_eq!(from_json(json!([null, "a"])).to_string(), "[Missing('N/A'), 'a']");
+}
+
+#[test]
+fn json_objects_of_different_shapes_share_one_list_type() {
+    let value = from_json(json!([{ "id": 1 }, { "id": 2, "note": "late" }]));
+    assert_string_contains("note", value.get_type().to_string());
+}
+
+#[test]
+fn json_request_drives_decision_service() {
+    let model = r#"
+    {
+        type Customer: { name: <string>; income: <number> }
+        type Order: { id: <number>; total: <number> }
+        type Request: { customer: <Customer>; orders: <Order[]> }
+        func decide(request: Request): {
+            greeting: "Hello " + request.customer.name
+            spent: sum(for order in request.orders return order.total)
+            approved: request.customer.income > spent * 10
+        }
+    }
+    "#;
+
+    let body = r#"{
+        "customer": { "name": "Alice", "income": 5000 },
+        "orders": [{ "id": 1, "total": 120.5 }, { "id": 2, "total": 79.5 }]
+    }"#;
+
+    let request = from_json(serde_json::from_str(body).unwrap());
+    let mut service = DecisionService::from_source(model).expect("service from source");
+    let response = service.execute("decide", Some(vec![request])).expect("decision service should execute");
+
+    let response: Value = serde_json::from_str(&response.to_json().unwrap()).unwrap();
+    assert_eq!(response, json!({ "greeting": "Hello Alice", "spent": 200, "approved": true }));
+}
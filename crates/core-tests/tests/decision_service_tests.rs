@@ -1,8 +1,14 @@
-use edge_rules::runtime::decision_service::DecisionService;
-use edge_rules::runtime::edge_rules::{EdgeRulesModel, EvalError};
+use edge_rules::runtime::decision_service::{Coercion, DecisionService};
+use edge_rules::runtime::edge_rules::{EdgeRulesModel, EvalError, EvaluationObserver};
 use edge_rules::runtime::edge_rules::{ExpressionEnum, InvocationSpec};
-use edge_rules::test_support::ValueEnum;
+use edge_rules::runtime::execution_context::Clock;
+use edge_rules::test_support::{NumberEnum, SpecialValueEnum, ValueEnum, ValueType};
+use edge_rules::typesystem::errors::RuntimeError;
+use edge_rules::typesystem::types::string::StringEnum;
+use std::cell::Cell;
 use std::rc::Rc;
+use time::macros::datetime;
+use time::{Duration, OffsetDateTime};
 
 mod utilities;
 pub use utilities::*;
@@ -134,6 +140,73 @@ fn execute_multi_argument_method() {
     assert_eq!(res, ValueEnum::from(30));
 }
 
+#[test]
+fn sample_request_is_built_from_argument_type() {
+    let model = r#"
+    {
+        type LoanOffer: {eligible: <boolean>; amount: <number>; termInMonths: <number, 12>; startDate: <date>}
+        func decide(offer: LoanOffer): { result: offer.amount }
+        func sum(a, b): { result: a + b }
+    }
+    "#;
+
+    let mut service = DecisionService::from_source(model).expect("service from source");
+
+    let sample = service.sample_request("decide").expect("sample request");
+    assert_eq!(
+        value_to_string(&sample),
+        "{eligible:Missing('eligible')amount:Missing('amount')termInMonths:12startDate:Missing('startDate')}"
+    );
+
+    let response = service.execute("decide", Some(vec![sample])).expect("execute with sample request");
+    assert_eq!(value_to_string(&response), "{result:Missing('amount')}");
+
+    let err = service.sample_request("sum").unwrap_err();
+    assert!(err.to_string().contains("`sum` has 2 arguments"), "expected arity error, got: {}", err);
+}
+
+#[test]
+fn coerce_request_converts_loosely_typed_values() {
+    let model = r#"
+    {
+        type Item: {name: <string>; quantity: <number>}
+        type Application: {age: <number>; vip: <boolean>; code: <string>; since: <date>; items: <Item[]>}
+        func decide(application: Application): { nextAge: application.age + 1 }
+    }
+    "#;
+
+    let mut service = DecisionService::from_source(model).expect("service from source");
+    let request = build_request_value(
+        "{ age: '30'; vip: 'TRUE'; code: 42; since: '2020-01-15'; items: [{name: 'a'; quantity: '2'}] }",
+    );
+
+    let coerced = service.coerce_request("decide", request).expect("coerce request");
+    assert_eq!(
+        value_to_string(&coerced.request),
+        "{age:30vip:truecode:'42'since:2020-01-15items:[{name:'a'quantity:2}]}"
+    );
+
+    let coercion = |path: &str, from: ValueType, to: ValueType| Coercion { path: path.to_string(), from, to };
+    assert_eq!(
+        coerced.coercions,
+        vec![
+            coercion("age", ValueType::StringType, ValueType::NumberType),
+            coercion("vip", ValueType::StringType, ValueType::BooleanType),
+            coercion("code", ValueType::NumberType, ValueType::StringType),
+            coercion("since", ValueType::StringType, ValueType::DateType),
+            coercion("items[0].quantity", ValueType::StringType, ValueType::NumberType),
+        ]
+    );
+
+    let response = service.execute("decide", Some(vec![coerced.request])).expect("execute coerced request");
+    assert_eq!(value_to_string(&response), "{nextAge:31}");
+
+    // well-typed and unconvertible values are kept as they are
+    let coerced = service.coerce_request("decide", build_request_value("{ age: 30; vip: 'maybe' }")).expect("coerce");
+    assert!(coerced.coercions.is_empty(), "expected no coercions, got {:?}", coerced.coercions);
+    assert_eq!(value_to_string(&coerced.request), "{age:30vip:'maybe'}");
+}
+
 #[test]
 fn execute_field_evaluation() {
     let model = r#"
@@ -233,6 +306,130 @@ fn execute_relinks_after_model_updates() {
     assert_string_contains("value:13", inline_text(second.to_string()));
 }
 
+fn shares_state(left: &ValueEnum, right: &ValueEnum) -> bool {
+    match (left, right) {
+        (ValueEnum::Reference(left), ValueEnum::Reference(right)) => Rc::ptr_eq(left, right),
+        _ => false,
+    }
+}
+
+#[test]
+fn execute_reuses_cached_responses_for_identical_requests() {
+    let model = r#"
+    {
+        type Request: { amount: <number> }
+        func decide(request: Request): {
+            value: request.amount + 1
+        }
+    }
+    "#;
+
+    let mut service = DecisionService::from_source(model).expect("service from model");
+    let small = build_request_value("{ amount: 3 }");
+    let large = build_request_value("{ amount: 30 }");
+
+    // without a cache every request is evaluated again
+    service.execute("decide", Some(vec![small.clone()])).expect("uncached execution");
+    service.execute("decide", Some(vec![small.clone()])).expect("uncached execution");
+    assert_eq!(service.cache_hits(), 0);
+
+    service.set_cache_size(1);
+    let first = service.execute("decide", Some(vec![small.clone()])).expect("first execution");
+    let hit = service.execute("decide", Some(vec![build_request_value("{ amount: 3 }")])).expect("cache hit");
+    assert_eq!(service.cache_hits(), 1, "identical request should be answered from the cache");
+    assert!(!shares_state(&first, &hit), "cached responses are handed out as copies");
+    assert_eq!(inline_text(hit.to_string()), inline_text(first.to_string()));
+
+    // a request of another type is a different request
+    service.execute("decide", Some(vec![build_request_value("{ amount: '3' }")])).ok();
+    assert_eq!(service.cache_hits(), 1);
+
+    let miss = service.execute("decide", Some(vec![large])).expect("cache miss");
+    assert_eq!(service.cache_hits(), 1, "different request should be evaluated");
+    assert_string_contains("value:31", inline_text(miss.to_string()));
+
+    // the only slot now holds the larger request, so the smaller one was evicted
+    service.execute("decide", Some(vec![small.clone()])).expect("evicted request");
+    assert_eq!(service.cache_hits(), 1);
+
+    service.clear_cache();
+    service.execute("decide", Some(vec![small.clone()])).expect("after clear");
+    assert_eq!(service.cache_hits(), 1);
+    service.execute("decide", Some(vec![small.clone()])).expect("cached again");
+    assert_eq!(service.cache_hits(), 2);
+
+    // model changes drop cached responses
+    service.get_model();
+    service.execute("decide", Some(vec![small])).expect("after model change");
+    assert_eq!(service.cache_hits(), 2);
+}
+
+/// Moves one hour forward on every reading
+struct TickingClock(Cell<OffsetDateTime>);
+
+impl Clock for TickingClock {
+    fn now(&self) -> OffsetDateTime {
+        let now = self.0.get();
+        self.0.set(now + Duration::hours(1));
+        now
+    }
+}
+
+#[test]
+fn execute_does_not_cache_responses_that_read_the_clock() {
+    let model = "{ func stamp(x): { at: now(); next: x + 1 } }";
+    let mut service = DecisionService::from_source(model).expect("service from model");
+    service.set_clock(Rc::new(TickingClock(Cell::new(datetime!(2024-12-31 22:00 UTC)))));
+    service.set_cache_size(10);
+
+    let first = service.execute("stamp", Some(vec![ValueEnum::from(1)])).expect("first execution");
+    let second = service.execute("stamp", Some(vec![ValueEnum::from(1)])).expect("second execution");
+
+    assert_eq!(inline_text(first.to_string()), "{at:2024-12-31T22:00:00next:2}");
+    assert_eq!(inline_text(second.to_string()), "{at:2024-12-31T23:00:00next:2}");
+}
+
+#[test]
+fn warm_up_then_execute_matches_cold_execute() {
+    let model = r#"
+    {
+        type Applicant: { name: <string>; income: <number>; expense: <number> }
+        rates: { func margin(income, expense): income - expense }
+        func applyTwice(f, x): f(f(x))
+        func decide(applicant: Applicant): {
+            func score(income): if income > 1000 then 30 else 10
+            margin: rates.margin(applicant.income, applicant.expense)
+            total: score(applicant.income) + margin
+            status: if total > 500 then "APPROVE" else "DECLINE"
+        }
+    }
+    "#;
+    let request = build_request_value("{ name: 'Alice'; income: 1500; expense: 700 }");
+
+    let mut cold = DecisionService::from_source(model).expect("cold service");
+    let cold_result = cold.execute("decide", Some(vec![request.clone()])).expect("cold execution");
+
+    let mut warm = DecisionService::from_source(model).expect("warm service");
+    warm.warm_up().expect("warm up");
+    let warm_result = warm.execute("decide", Some(vec![request.clone()])).expect("warm execution");
+
+    assert_eq!(value_to_string(&warm_result), value_to_string(&cold_result));
+    assert_string_contains("status:'APPROVE'", value_to_string(&warm_result));
+
+    // warming up again after a model change relinks it
+    {
+        let model_ref = warm.get_model();
+        let mut borrowed = model_ref.borrow_mut();
+        borrowed.remove_user_function("decide").expect("remove previous decide function");
+        borrowed
+            .append_source("{ func decide(applicant: Applicant): { status: rates.margin(applicant.income, 1200) } }")
+            .expect("override decide implementation");
+    }
+    warm.warm_up().expect("warm up after update");
+    let updated = warm.execute("decide", Some(vec![request])).expect("execution after update");
+    assert_string_contains("status:300", value_to_string(&updated));
+}
+
 #[test]
 fn from_context_reuses_provided_tree() {
     let model = r#"
@@ -346,3 +543,111 @@ fn execute_zero_arg_method_as_field_returns_context() {
     let res2 = service.execute("getTen", Some(vec![])).expect("execute as method with 0 args");
     assert_eq!(res2, ValueEnum::from(10));
 }
+
+#[derive(Default)]
+struct EvaluatedFields(Vec<String>);
+
+impl EvaluationObserver for EvaluatedFields {
+    fn before_field(&mut self, _path: &[String]) {}
+
+    fn after_field(&mut self, path: &[String], _result: &Result<ValueEnum, RuntimeError>) {
+        self.0.push(path.join("."));
+    }
+}
+
+#[test]
+fn update_input_evaluates_only_dependent_fields() {
+    let mut service = DecisionService::from_source(
+        r#"
+        {
+            price: 10
+            quantity: 2
+            discount: 0.1
+            subtotal: price * quantity
+            total: subtotal * (1 - discount)
+            shipping: if quantity > 5 then 0 else 5
+            label: 'Order'
+            summary: {
+                net: total
+                gross: total + shipping
+            }
+        }
+        "#,
+    )
+    .expect("service");
+
+    let mut evaluated = EvaluatedFields::default();
+    let changed = service.update_input_observed("price", ValueEnum::from(20), &mut evaluated).expect("update price");
+    assert_eq!(evaluated.0, vec!["subtotal", "total", "summary.net", "summary.gross"]);
+    let changed: Vec<String> = changed.iter().map(|(path, value)| format!("{} = {}", path, value)).collect();
+    assert_eq!(changed, vec!["subtotal = 40", "summary.gross = 41", "summary.net = 36", "total = 36"]);
+
+    let mut evaluated = EvaluatedFields::default();
+    let changed = service
+        .update_input_observed("quantity", ValueEnum::from(6), &mut evaluated)
+        .expect("update quantity");
+    assert_eq!(evaluated.0, vec!["subtotal", "total", "shipping", "summary.net", "summary.gross"]);
+    assert_eq!(changed.get("shipping").map(ToString::to_string), Some("0".to_string()));
+    assert_eq!(changed.get("summary.gross").map(ToString::to_string), Some("108".to_string()));
+
+    // setting the same value again evaluates nothing
+    let mut evaluated = EvaluatedFields::default();
+    let changed = service
+        .update_input_observed("quantity", ValueEnum::from(6), &mut evaluated)
+        .expect("same quantity");
+    assert!(changed.is_empty());
+    assert!(evaluated.0.is_empty());
+
+    // a change that keeps the outcome of a condition only reports the fields that really changed
+    let changed = service.update_input("quantity", ValueEnum::from(7)).expect("update quantity");
+    assert!(!changed.contains_key("shipping"));
+    assert!(changed.contains_key("total"));
+
+    assert!(service.update_input("missing", ValueEnum::from(1)).is_err());
+
+    // a field may read a field declared after it
+    let mut service =
+        DecisionService::from_source("{ price: 10; total: subtotal * 2; subtotal: price + 1 }").expect("service");
+    let changed = service.update_input("price", ValueEnum::from(20)).expect("update price");
+    let changed: Vec<String> = changed.iter().map(|(path, value)| format!("{} = {}", path, value)).collect();
+    assert_eq!(changed, vec!["subtotal = 21", "total = 42"]);
+}
+
+#[test]
+fn update_input_checks_the_input_type() {
+    let mut service = DecisionService::from_source("{ price: 10; out: price * 2 }").expect("service");
+
+    let err = service.update_input("price", ValueEnum::BooleanValue(true)).expect_err("boolean price");
+    assert!(err.to_string().contains("Failed to parse 'number' from 'boolean'"), "{}", err);
+
+    // loosely typed values are converted like in coerce_request
+    let changed = service
+        .update_input("price", ValueEnum::StringValue(StringEnum::String("25".to_string())))
+        .expect("string price");
+    assert_eq!(changed.get("out").map(ToString::to_string), Some("50".to_string()));
+}
+
+#[test]
+fn update_input_evaluates_all_dependents_before_reporting_an_error() {
+    let mut service =
+        DecisionService::from_source("{ divisor: 2; ratio: 10 / divisor; next: divisor + 1 }").expect("service");
+
+    assert!(service.update_input("divisor", ValueEnum::from(0)).is_err());
+
+    let changed = service.update_input("divisor", ValueEnum::from(5)).expect("update divisor");
+    let changed: Vec<String> = changed.iter().map(|(path, value)| format!("{} = {}", path, value)).collect();
+    assert_eq!(changed, vec!["next = 6", "ratio = 2"]);
+}
+
+#[test]
+fn reduce_of_missing_list_argument_is_missing() {
+    let model = r#"
+    {
+        func total(amounts: number[]): { result: reduce(amounts, 0, acc + it) }
+    }
+    "#;
+    let mut service = DecisionService::from_source(model).expect("service from source");
+    let missing = ValueEnum::NumberValue(NumberEnum::SV(SpecialValueEnum::missing("amounts")));
+    let response = service.execute("total", Some(vec![missing])).expect("execute with missing list");
+    assert_eq!(value_to_string(&response), "{result:Missing('amounts')}");
+}
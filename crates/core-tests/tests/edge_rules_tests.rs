@@ -1,14 +1,17 @@
 use edge_rules::runtime::edge_rules::{
     ContextObjectBuilder, ContextQueryErrorEnum, EdgeRulesModel, EvalError, ExpressionEnum, UserFunctionDefinition,
 };
+use edge_rules::runtime::execution_context::Clock;
 use edge_rules::test_support::NumberEnum::Int;
 use edge_rules::test_support::ParseErrorEnum::{UnexpectedToken, WrongFormat};
-use edge_rules::test_support::SpecialValueEnum::Missing;
 use edge_rules::test_support::{
-    expr, ComplexTypeRef, EToken, EUnparsedToken, FunctionDefinition, LinkingErrorEnum, NumberEnum, UserTypeBody,
-    ValueEnum, ValueType,
+    expr, ComplexTypeRef, EToken, EUnparsedToken, FunctionDefinition, LinkingErrorEnum, UserTypeBody, ValueEnum,
+    ValueType,
 };
+use std::cell::Cell;
 use std::rc::Rc;
+use time::macros::datetime;
+use time::{Duration, OffsetDateTime};
 
 mod utilities;
 pub use utilities::*;
@@ -84,10 +87,8 @@ fn test_service_evaluate_field_with_path_depth() -> Result<(), EvalError> {
     let start = runtime.evaluate_field("calendar.config.start")?;
     assert_eq!(start.to_string(), "7");
 
-    // @Todo: this test is incorrect, `calendar.config.end` cannot be linked and link error should occur
-    // @Todo: find and fix if self.path.len() > 1 && is_unattached_root {...
-    let end = runtime.evaluate_field("calendar.config.end")?;
-    assert_eq!(end.to_string(), ValueEnum::NumberValue(NumberEnum::SV(Missing("end".to_string()))).to_string());
+    let error = runtime.evaluate_field("calendar.config.end").expect_err("`end` is not defined in `config`");
+    assert_string_contains("Field 'end' not found in config", error.to_string());
 
     Ok(())
 }
@@ -149,6 +150,69 @@ fn append_source_accepts_user_function_definition() -> Result<(), EvalError> {
     Ok(())
 }
 
+#[test]
+fn append_sources_reports_failed_fragments_and_keeps_others() -> Result<(), EvalError> {
+    init_logger();
+
+    let mut service = EdgeRulesModel::new();
+    let failures = service
+        .append_sources(&["{ a: 1 }", "{ b: 2 + }", "{ c: a + 2 }"])
+        .expect_err("second fragment must fail");
+
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, 1);
+    assert!(!failures[0].1.errors().is_empty());
+
+    let runtime = service.to_runtime_snapshot()?;
+    assert_eq!(runtime.evaluate_expression_str("c")?, ValueEnum::NumberValue(Int(3)));
+    assert!(runtime.evaluate_expression_str("b").is_err());
+
+    assert!(service.append_sources(&["{ d: 4 }", "e: d + 1"]).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn to_code_round_trip_keeps_functions_and_types() -> Result<(), EvalError> {
+    init_logger();
+
+    let code = r#"
+    {
+        type Point: { x: <number>; y: <number> }
+        type Points: <Point[]>
+        func shift(p: Point, delta: number): { x: p.x + delta; y: p.y + delta }
+        func double(value) : value * 2
+        moved: shift({x: 1; y: 2}, 3).x
+        doubled: double(moved)
+        nested: {
+            func inc(value): { result: value + 1 }
+            total: inc(doubled).result
+        }
+    }
+    "#;
+
+    let mut original = EdgeRulesModel::new();
+    original.append_source(code)?;
+    let original_runtime = original.to_runtime()?;
+    let printed = original_runtime.context.borrow().to_code();
+
+    assert_string_contains("type Point: {x: <number>; y: <number>}", &printed);
+    assert_string_contains("type Points: <Point[]>", &printed);
+    assert_string_contains("func shift(p: Point, delta: number) : {x: p.x + delta; y: p.y + delta}", &printed);
+    assert_string_contains("func double(value) : value * 2", &printed);
+    assert_string_contains("func inc(value) : {result: value + 1}", &printed);
+
+    let mut reparsed = EdgeRulesModel::new();
+    reparsed.append_source(&printed)?;
+    let reparsed_runtime = reparsed.to_runtime()?;
+
+    assert_eq!(reparsed_runtime.context.borrow().to_code(), printed);
+    assert_eq!(reparsed_runtime.get_type("*")?.to_string(), original_runtime.get_type("*")?.to_string());
+    assert_eq!(reparsed_runtime.evaluate_field("nested.total")?, ValueEnum::NumberValue(Int(9)));
+
+    Ok(())
+}
+
 #[test]
 fn merge_context_object_appends_new_fields() -> Result<(), EvalError> {
     init_logger();
@@ -178,7 +242,7 @@ fn merge_context_object_rejects_duplicate_fields() -> Result<(), EvalError> {
     let context = builder.build();
 
     match service.merge_context_object(context) {
-        Err(err) => {
+        Err(ContextQueryErrorEnum::DuplicateNameError(err)) => {
             assert_eq!(err.name, "value");
         }
         other => panic!("expected duplicate error, got {:?}", other),
@@ -680,3 +744,216 @@ fn pass_sub_context_to_function() -> Result<(), EvalError> {
 
     Ok(())
 }
+
+fn in_memory_model() -> EdgeRulesModel {
+    let mut model = EdgeRulesModel::new();
+    model.set_import_resolver(|path| match path {
+        "common.er" => Ok("{ type Money: {amount: <number>; currency: <string>} }".to_string()),
+        "rates.er" => Ok("{ import \"common.er\"\n rate: 0.2 }".to_string()),
+        "money.er" => Ok("{ import \"rates.er\"\n scale: 100; func round2(x): round(x * scale) / scale }".to_string()),
+        "loop.er" => Ok("{ import \"loop.er\" as again\n value: 1 }".to_string()),
+        other => Err(format!("'{}' does not exist", other)),
+    });
+    model
+}
+
+#[test]
+fn import_inlines_type_definitions_from_resolver() -> Result<(), EvalError> {
+    let mut model = in_memory_model();
+    model.append_source(
+        r#"
+        {
+            import "rates.er"
+            import "common.er"
+            price: {amount: 100; currency: 'EUR'} as Money
+            tax: price.amount * rate
+        }
+        "#,
+    )?;
+
+    let runtime = model.to_runtime()?;
+    assert_eq!(runtime.evaluate_field("tax")?.to_string(), "20");
+    assert_eq!(runtime.evaluate_field("price.currency")?.to_string(), "'EUR'");
+
+    Ok(())
+}
+
+#[test]
+fn import_errors_are_reported() {
+    let err = in_memory_model().append_source("{ import \"missing.er\"\n value: 1 }").unwrap_err();
+    assert_string_contains("Cannot import 'missing.er': 'missing.er' does not exist", err.to_string());
+
+    let err = EdgeRulesModel::new().append_source("import \"common.er\"").unwrap_err();
+    assert_string_contains("no import resolver is set", err.to_string());
+
+    let mut model = in_memory_model();
+    model.append_source("{ nested: { import \"common.er\"\n value: 1 } }").unwrap();
+    let err = model.to_runtime().err().map(|err| err.to_string()).unwrap_or_default();
+    assert_string_contains("imports are resolved only at the top level", err);
+}
+
+#[test]
+fn import_with_alias_resolves_namespaced_references() -> Result<(), EvalError> {
+    let mut model = in_memory_model();
+    model.append_source(
+        r#"
+        {
+            import "money.er" as money
+            func round2(x): x
+            rate: 1
+            price: money.round2(10.456)
+            own: round2(10.456)
+            tax: price * money.rate
+        }
+        "#,
+    )?;
+
+    let runtime = model.to_runtime()?;
+    assert_eq!(runtime.evaluate_field("price")?.to_string(), "10.46");
+    assert_eq!(runtime.evaluate_field("own")?.to_string(), "10.456");
+    assert_eq!(runtime.evaluate_field("tax")?.to_string(), "2.092");
+    assert_eq!(runtime.evaluate_field("money.scale")?.to_string(), "100");
+
+    let err = in_memory_model().append_source("{ import \"loop.er\" as again }").unwrap_err();
+    assert_string_contains("Cannot import 'loop.er': cyclic import", err.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn eval_all_with_diagnostics_reports_missing_fields() -> Result<(), EvalError> {
+    init_logger();
+
+    let mut model = EdgeRulesModel::new();
+    model.append_source(
+        r#"
+        {
+            type Request: { amount: <number>; name: <string> }
+            request: { name: 'Alice' } as Request
+            total: request.amount
+            nested: { copy: request.amount; name: request.name }
+        }
+        "#,
+    )?;
+    let runtime = model.to_runtime_snapshot()?;
+
+    let result = runtime.eval_all_with_diagnostics()?;
+    assert_eq!(
+        result.missing_fields,
+        vec!["request.amount".to_string(), "total".to_string(), "nested.copy".to_string()]
+    );
+    assert!(result.warnings.is_empty());
+    assert_string_contains("name: 'Alice'", result.value.to_string());
+
+    // fields evaluated before the call are still reported
+    let runtime = model.to_runtime_snapshot()?;
+    assert_eq!(runtime.evaluate_field("total")?.to_string(), "Missing('amount')");
+    let result = runtime.eval_all_with_diagnostics()?;
+    assert_eq!(
+        result.missing_fields,
+        vec!["request.amount".to_string(), "total".to_string(), "nested.copy".to_string()]
+    );
+
+    // the tolerance is reported only when reals were compared with it
+    let mut runtime = model.to_runtime_snapshot()?;
+    runtime.set_real_tolerance(Some(1.into()));
+    assert!(runtime.eval_all_with_diagnostics()?.warnings.is_empty());
+
+    let mut compared = EdgeRulesModel::new();
+    compared.append_source("{ third: 1 / 3; equal: third * 3 = 1 }")?;
+    let mut runtime = compared.to_runtime()?;
+    runtime.set_real_tolerance(Some(1.into()));
+    let result = runtime.eval_all_with_diagnostics()?;
+    assert_eq!(result.warnings, vec!["Reals were compared with a tolerance of 1".to_string()]);
+
+    Ok(())
+}
+
+#[test]
+fn frozen_model_spawns_isolated_runtimes() -> Result<(), EvalError> {
+    init_logger();
+
+    let mut model = EdgeRulesModel::new();
+    model.append_source("{ third: 1 / 3; equal: third * 3 = 1; func double(x): { result: x * 2 } }")?;
+    let frozen = model.freeze()?;
+    assert!(model.is_frozen());
+
+    let mut tolerant = frozen.to_runtime();
+    let exact = frozen.clone().to_runtime();
+    assert!(Rc::ptr_eq(&tolerant.static_tree, &exact.static_tree), "runtimes must share the linked model");
+
+    // the first runtime caches `equal` evaluated with a tolerance, the second one must not see it
+    tolerant.set_real_tolerance(Some(1.into()));
+    assert_eq!(tolerant.evaluate_field("equal")?.to_string(), "true");
+    assert_eq!(exact.evaluate_field("equal")?.to_string(), "false");
+    assert_eq!(exact.evaluate_expression_str("double(4).result")?.to_string(), "8");
+
+    let err = model.append_source("{ more: 1 }").unwrap_err();
+    assert_string_contains("Model is frozen", err.to_string());
+    assert!(matches!(model.remove_expression("third"), Err(ContextQueryErrorEnum::ModelFrozenError)));
+    assert!(matches!(model.rename_entry("third", "fourth"), Err(ContextQueryErrorEnum::ModelFrozenError)));
+
+    let mut builder = ContextObjectBuilder::new();
+    builder.set_expression("merged", expr("1")?).expect("set expression");
+    assert!(matches!(model.merge_context_object(builder.build()), Err(ContextQueryErrorEnum::ModelFrozenError)));
+
+    // snapshots of a frozen model reuse the frozen tree
+    assert!(Rc::ptr_eq(&model.to_runtime_snapshot()?.static_tree, &exact.static_tree));
+    assert!(Rc::ptr_eq(&model.freeze()?.to_runtime().static_tree, &exact.static_tree));
+    assert!(Rc::ptr_eq(&model.to_runtime()?.static_tree, &exact.static_tree));
+
+    Ok(())
+}
+
+/// Moves one hour forward on every reading, so repeated readings would differ
+struct TickingClock(Cell<OffsetDateTime>);
+
+impl Clock for TickingClock {
+    fn now(&self) -> OffsetDateTime {
+        let now = self.0.get();
+        self.0.set(now + Duration::hours(1));
+        now
+    }
+}
+
+#[test]
+fn today_and_now_read_the_runtime_clock() -> Result<(), EvalError> {
+    init_logger();
+
+    let mut model = EdgeRulesModel::new();
+    model.append_source(
+        "{ day: today(); instant: now(); same: now() = instant; func age(born): { years: today() - born } }",
+    )?;
+    model.append_source("{ years: age(date('2000-12-31')).years }")?;
+    let mut runtime = model.to_runtime()?;
+    runtime.set_clock(Rc::new(TickingClock(Cell::new(datetime!(2024-12-31 23:30 UTC)))));
+    runtime.eval_all()?;
+
+    assert_eq!(runtime.evaluate_field("day")?.to_string(), "2024-12-31");
+    assert_eq!(runtime.evaluate_field("instant")?.to_string(), "2024-12-31T23:30:00");
+    assert_eq!(runtime.evaluate_field("same")?.to_string(), "true");
+    assert_eq!(runtime.evaluate_field("years")?.to_string(), "P8766D");
+
+    Ok(())
+}
+
+/// Fails the test when read
+struct UnreadClock;
+
+impl Clock for UnreadClock {
+    fn now(&self) -> OffsetDateTime {
+        panic!("only today() and now() read the clock")
+    }
+}
+
+#[test]
+fn other_functions_without_arguments_do_not_read_the_clock() -> Result<(), EvalError> {
+    let mut model = EdgeRulesModel::new();
+    model.append_source("{ value: round(pi(), 2) }")?;
+    let mut runtime = model.to_runtime()?;
+    runtime.set_clock(Rc::new(UnreadClock));
+
+    assert_eq!(runtime.evaluate_field("value")?.to_string(), "3.14");
+
+    Ok(())
+}
@@ -159,7 +159,14 @@ fn test_common() {
 fn test_functions_count() {
     assert_expression_value("count([1,2,3]) + 1", "4");
     assert_expression_value("count(['a','b','c'])", "3");
+    assert_expression_value("count('')", "0");
+    assert_expression_value("count('abc')", "3");
+    assert_expression_value("count('a')", "1");
     assert_expression_value("count(['a',toString(5),toString(date('2012-01-01')),'1'])", "4");
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: count(t.s)", missing).as_str(), "Missing('s')");
+    assert_eval_value(format!("{}; value: isEmpty(t.s)", missing).as_str(), "Missing('s')");
 }
 
 #[test]
@@ -201,6 +208,21 @@ fn test_functions_find() {
     assert_expression_value("find([1,2,888],999)", "Missing('N/A')");
 }
 
+#[test]
+fn test_functions_type_of() {
+    assert_expression_value("typeOf(date('2020-01-01'))", "'date'");
+    assert_expression_value("typeOf(1.5)", "'number'");
+    assert_expression_value("typeOf('abc')", "'string'");
+    assert_expression_value("typeOf(true)", "'boolean'");
+    assert_expression_value("typeOf(datetime('2020-01-01T10:00:00'))", "'datetime'");
+    assert_expression_value("typeOf(duration('P1D'))", "'duration'");
+    assert_expression_value("typeOf([1, 2])", "'list'");
+    assert_expression_value("typeOf({ a: 1 })", "'object'");
+
+    let missing = "type T: { n: <number> }; t: {} as T";
+    assert_eval_value(format!("{}; value: typeOf(t.n)", missing).as_str(), "'missing'");
+}
+
 #[test]
 fn client_functions_test() {
     // variant 1
@@ -314,7 +336,7 @@ fn variable_linkin_test() {
 
 #[test]
 fn accessing_constant_inner_field_is_link_error() {
-    runtime_error_contains(
+    link_error_contains(
         r#"
         {
             dateVal: date('2024-01-01')
@@ -327,14 +349,14 @@ fn accessing_constant_inner_field_is_link_error() {
 
 #[test]
 fn accessing_function_inner_field_is_link_error() {
-    runtime_error_contains(
+    link_error_contains(
         r#"
         {
             func helper(): { result: 1 }
             value: helper.nonexistent
         }
         "#,
-        &["Missing('nonexistent')"],
+        &["Field 'nonexistent' not found in function helper"],
     );
 }
 
@@ -1,6 +1,8 @@
 mod utilities;
 pub use utilities::*;
 
+use edge_rules::runtime::edge_rules::EdgeRulesModel;
+
 #[test]
 fn example_context_deep_evaluation() {
     let code = r#"
@@ -327,3 +329,101 @@ fn incredibly_nested_vl_record_example() {
 
     assert_eval_field(&rt, "applicationResponse.applicantDecisions", "0");
 }
+
+#[test]
+fn object_spread_with_overrides() {
+    let code = r#"
+    base: {
+        amount: 100
+        currency: 'EUR'
+        limits: {daily: 10}
+    }
+    extra: {currency: 'USD'; rate: 2}
+    offer: {
+        ...base
+        amount: 200
+    }
+    merged: {
+        ...base
+        ...extra
+        total: amount * rate
+    }
+    "#;
+
+    let rt = get_runtime(code);
+    assert_eval_field(&rt, "offer.amount", "200");
+    assert_eval_field(&rt, "offer.currency", "'EUR'");
+    assert_eval_field(&rt, "offer.limits.daily", "10");
+    assert_eval_field(&rt, "merged.currency", "'USD'");
+    assert_eval_field(&rt, "merged.total", "200");
+    assert_eq!(
+        rt.get_type("offer").unwrap().to_string(),
+        "{currency: string; limits: {daily: number}; amount: number}"
+    );
+
+    link_error_contains("{ base: 1; value: { ...base } }", &["Cannot spread `base` of type 'number'"]);
+    parse_error_contains("{ value: { ...1 } }", &["Only object references can be spread"]);
+}
+
+#[test]
+fn optional_field_is_omitted_when_if_has_no_else() {
+    let code = r#"
+    hasDiscount: false
+    hasFee: true
+    order: {
+        amount: 100
+        discount?: if hasDiscount then 10
+        fee?: if hasFee then 5
+        note?: if hasDiscount then 'discounted'
+        else 'regular'
+    }
+    "#;
+
+    let rt = get_runtime(code);
+    rt.eval_all().unwrap();
+    let output = rt.context.borrow().to_code();
+    assert_string_contains("amount: 100", &output);
+    assert_string_contains("fee: 5", &output);
+    assert_string_contains("note: 'regular'", &output);
+    assert!(!output.contains("discount"), "discount must be omitted:\n{}", output);
+
+    assert_eq!(
+        EdgeRulesModel::parse_expression("{discount?: if a then 10}").unwrap().to_string(),
+        "{discount?: if a then 10}"
+    );
+    assert_expression_value("if false then 2", "Omitted");
+    link_error_contains("{ value: if true then [1] }", &["has no `else`"]);
+    parse_error_contains("{ value?: {a: 1} }", &["Field 'value' cannot be optional"]);
+}
+
+#[test]
+fn object_field_introspection() {
+    let code = r#"
+    applicant: {
+        name: 'Ann'
+        income: 1200
+        address: {
+            city: 'Vilnius'
+            lines: ['Main st. 1']
+        }
+    }
+    "#;
+
+    assert_eval_value(format!("{}\nvalue: fieldsOf(applicant)", code).as_str(), "['name', 'income', 'address']");
+    assert_eval_value(format!("{}\nvalue: fieldsOf(applicant.address)", code).as_str(), "['city', 'lines']");
+    assert_eval_value(format!("{}\nvalue: typeOfField(applicant, 'income')", code).as_str(), "'number'");
+    assert_eval_value(format!("{}\nvalue: typeOfField(applicant.address, 'lines')", code).as_str(), "'list'");
+    assert_eval_value(format!("{}\nvalue: typeOfField(applicant, 'address')", code).as_str(), "'object'");
+    assert_eval_value(
+        format!("{}\nvalue: typeOfField(applicant, 'income') = typeOf(applicant.income)", code).as_str(),
+        "true",
+    );
+    assert_eval_value(format!("{}\nvalue: typeOfField(applicant, 'age')", code).as_str(), "NotFound('age')");
+    assert_eval_value(
+        format!("{}\nvalue: fieldsOf(applicant)[typeOfField(applicant, ...) = 'string']", code).as_str(),
+        "['name']",
+    );
+
+    link_error_contains("value: fieldsOf(12)", &["unexpected"]);
+    link_error_contains("value: typeOfField({a: 1}, 1)", &["unexpected", "string"]);
+}
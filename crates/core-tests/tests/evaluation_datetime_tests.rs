@@ -72,6 +72,150 @@ fn datetime_additional_functions() {
     assert_expression_value("lastDayOfMonth(date('2025-02-10'))", "28");
 }
 
+#[test]
+fn weekend_and_business_day_predicates() {
+    // 2024-06-15 is a Saturday
+    assert_expression_value("isWeekend(date('2024-06-15'))", "true");
+    assert_expression_value("isWeekend(date('2024-06-17'))", "false");
+    assert_expression_value("isWeekend(datetime('2024-06-16T10:00:00'))", "true");
+    assert_expression_value("isBusinessDay(date('2024-06-15'))", "false");
+    assert_expression_value("isBusinessDay(date('2024-06-17'))", "true");
+
+    // listed holidays are not business days
+    assert_expression_value("isBusinessDay(date('2024-12-25'), ['2024-12-25', '2024-12-26'])", "false");
+    assert_expression_value("isBusinessDay(datetime('2024-12-24T09:00:00'), ['2024-12-25'])", "true");
+    assert_expression_value("isBusinessDay(date('2024-12-24'), [])", "true");
+
+    let missing = "type T: { d: <date> }; t: {} as T";
+    assert_eval_value(format!("{}; value: isWeekend(t.d)", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: isBusinessDay(t.d)", missing).as_str(), "Missing('d')");
+
+    link_error_contains("value: isBusinessDay(date('2024-12-24'), [1])", &["holidays", "string[]"]);
+    runtime_error_contains("value: isBusinessDay(date('2024-12-24'), ['24/12'])", &["Failed to parse 'date'"]);
+}
+
+#[test]
+fn add_business_days_skips_weekends_and_holidays() {
+    // Thursday + 3 business days skips the weekend and lands on Tuesday
+    assert_expression_value("addBusinessDays(date('2024-06-13'), 3)", "2024-06-18");
+    assert_expression_value("addBusinessDays(date('2024-06-17'), -1)", "2024-06-14");
+    assert_expression_value("addBusinessDays(date('2024-06-15'), 0)", "2024-06-15");
+    assert_expression_value("addBusinessDays(date('2024-12-23'), 2, ['2024-12-25', '2024-12-26'])", "2024-12-27");
+    assert_expression_value("addBusinessDays(date('2024-12-27'), -2.0, ['2024-12-25', '2024-12-26'])", "2024-12-23");
+
+    let missing = "type T: { d: <date>; n: <number> }; t: {} as T";
+    assert_eval_value(format!("{}; value: addBusinessDays(t.d, 1)", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: addBusinessDays(date('2024-06-13'), t.n)", missing).as_str(), "Missing('n')");
+
+    runtime_error_contains("value: addBusinessDays(date('2024-06-13'), 1.5)", &["expects a whole number of days"]);
+    link_error_contains("value: addBusinessDays(date('2024-06-13'), '1')", &["unexpected", "number"]);
+}
+
+#[test]
+fn add_months_and_years_clamp_to_month_end() {
+    assert_expression_value("addMonths(date('2024-01-31'), 1)", "2024-02-29");
+    assert_expression_value("addMonths(date('2024-03-31'), -1)", "2024-02-29");
+    assert_expression_value("addMonths(date('2024-11-15'), 14)", "2026-01-15");
+    assert_expression_value("addYears(date('2024-02-29'), 1)", "2025-02-28");
+    assert_expression_value("addYears(date('2024-05-01'), -2)", "2022-05-01");
+    assert_expression_value("addMonths(datetime('2024-01-31T10:30:00'), 13)", "2025-02-28T10:30:00");
+    // same result as adding a period
+    assert_expression_value("addMonths(date('2024-01-31'), 1) = date('2024-01-31') + period('P1M')", "true");
+
+    let missing = "type T: { d: <date>; n: <number> }; t: {} as T";
+    assert_eval_value(format!("{}; value: addMonths(t.d, 1)", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: addYears(date('2024-06-13'), t.n)", missing).as_str(), "Missing('n')");
+
+    runtime_error_contains("value: addMonths(date('2024-06-13'), 1.5)", &["addMonths expects a whole number"]);
+    link_error_contains("value: addYears('2024-06-13', 1)", &["unexpected", "date or datetime"]);
+}
+
+#[test]
+fn days_and_months_between_dates() {
+    assert_expression_value("daysBetween(date('2024-01-01'), date('2024-03-01'))", "60");
+    assert_expression_value("daysBetween(date('2024-03-01'), date('2024-01-01'))", "-60");
+    assert_expression_value("daysBetween(date('2024-01-01'), date('2024-01-01'))", "0");
+    // same count as subtracting the dates
+    assert_expression_value("daysBetween(date('2020-02-10'), date('2024-06-13'))", "1585");
+    assert_expression_value("date('2024-06-13') - date('2020-02-10')", "P1585D");
+
+    assert_expression_value("monthsBetween(date('2024-01-15'), date('2024-03-14'))", "1");
+    assert_expression_value("monthsBetween(date('2024-01-15'), date('2024-03-15'))", "2");
+    assert_expression_value("monthsBetween(date('2024-01-31'), date('2024-02-29'))", "1");
+    assert_expression_value("monthsBetween(date('2025-03-15'), date('2024-01-15'))", "-14");
+
+    let missing = "type T: { d: <date> }; t: {} as T";
+    assert_eval_value(format!("{}; value: daysBetween(t.d, date('2024-01-01'))", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: monthsBetween(date('2024-01-01'), t.d)", missing).as_str(), "Missing('d')");
+
+    link_error_contains("value: daysBetween(date('2024-01-01'), 5)", &["unexpected", "date"]);
+}
+
+#[test]
+fn snap_dates_to_month_and_year_boundaries() {
+    assert_expression_value("startOfMonth(date('2024-02-17'))", "2024-02-01");
+    assert_expression_value("endOfMonth(date('2024-02-17'))", "2024-02-29");
+    assert_expression_value("endOfMonth(date('2023-02-17'))", "2023-02-28");
+    assert_expression_value("endOfMonth(date('2024-04-30'))", "2024-04-30");
+    assert_expression_value("startOfYear(date('2024-06-13'))", "2024-01-01");
+    assert_expression_value("endOfYear(date('2024-06-13'))", "2024-12-31");
+
+    // datetimes keep their time of day
+    assert_expression_value("startOfMonth(datetime('2024-06-13T10:30:00'))", "2024-06-01T10:30:00");
+    assert_expression_value("endOfMonth(datetime('2024-06-13T10:30:00+02:00'))", "2024-06-30T10:30:00+02:00");
+    assert_expression_value("endOfYear(datetime('2024-06-13T23:59:59'))", "2024-12-31T23:59:59");
+
+    let missing = "type T: { d: <date> }; t: {} as T";
+    assert_eval_value(format!("{}; value: endOfMonth(t.d)", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: startOfYear(t.d)", missing).as_str(), "Missing('d')");
+
+    link_error_contains("value: startOfMonth('2024-06-13')", &["unexpected", "date or datetime"]);
+}
+
+#[test]
+fn leap_years_follow_the_gregorian_rule() {
+    assert_expression_value("isLeapYear(date('2024-06-13'))", "true");
+    assert_expression_value("isLeapYear(date('2023-06-13'))", "false");
+    assert_expression_value("isLeapYear(datetime('2000-01-01T10:00:00'))", "true");
+    assert_expression_value("isLeapYear(1900)", "false");
+    assert_expression_value("isLeapYear(2000)", "true");
+    assert_expression_value("isLeapYear(2024)", "true");
+    assert_expression_value("isLeapYear(2025)", "false");
+
+    let missing = "type T: { d: <date>; n: <number> }; t: {} as T";
+    assert_eval_value(format!("{}; value: isLeapYear(t.d)", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: isLeapYear(t.n)", missing).as_str(), "Missing('n')");
+
+    runtime_error_contains("value: isLeapYear(2024.5)", &["isLeapYear expects a whole year"]);
+    link_error_contains("value: isLeapYear('2024')", &["unexpected"]);
+}
+
+#[test]
+fn datetime_offsets_and_conversion() {
+    assert_expression_value("datetimeTz('2024-06-01T10:00:00', '+02:00')", "2024-06-01T10:00:00+02:00");
+    assert_expression_value("toUtc(datetimeTz('2024-06-01T10:00:00', '+02:00'))", "2024-06-01T08:00:00");
+    assert_expression_value("toUtc(datetime('2024-06-01T01:00:00+02:00')).day", "31");
+    assert_expression_value("toOffset(datetime('2024-06-01T08:00:00'), '-05:30')", "2024-06-01T02:30:00-05:30");
+    assert_expression_value("toOffset(datetime('2024-06-01T08:00:00'), '-00:30')", "2024-06-01T07:30:00-00:30");
+    assert_expression_value("datetimeTz('2024-06-01T10:00:00Z', '+02:00')", "2024-06-01T12:00:00+02:00");
+
+    // conversion keeps the instant, so converted datetimes stay equal
+    assert_expression_value(
+        "toUtc(datetimeTz('2024-06-01T10:00:00', '+02:00')) = datetimeTz('2024-06-01T10:00:00', '+02:00')",
+        "true",
+    );
+
+    let missing = "type T: { dt: <datetime>; offset: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: toUtc(t.dt)", missing).as_str(), "Missing('dt')");
+    assert_eval_value(
+        format!("{}; value: datetimeTz('2024-06-01T10:00:00', t.offset)", missing).as_str(),
+        "Missing('offset')",
+    );
+
+    runtime_error_contains("value: datetimeTz('2024-06-01T10:00:00', '2 hours')", &["invalid offset '2 hours'"]);
+    link_error_contains("value: toOffset(date('2024-06-01'), '+02:00')", &["unexpected", "date"]);
+}
+
 #[test]
 fn date_comparator_operators() {
     assert_expression_value("date('2020-01-01') = date('2020-01-01')", "true");
@@ -111,6 +255,44 @@ fn duration_parsing_and_operations() {
     assert_expression_value("duration('PT45S') + duration('PT15S')", "PT1M");
 }
 
+#[test]
+fn lenient_duration_and_period_parsing() {
+    assert_expression_value("parseDuration('PT90M')", "PT1H30M");
+    assert_expression_value("parseDuration('-P1DT2H')", "-P1DT2H");
+    assert_expression_value("parsePeriod('P18M')", "P1Y6M");
+    assert_expression_value("parsePeriod('-P1Y2M5D')", "-P1Y2M5D");
+
+    // malformed input is not applicable instead of an error
+    assert_expression_value("parseDuration('2 hours')", "NotApplicable('invalid duration 2 hours')");
+    assert_expression_value("parseDuration('')", "NotApplicable('invalid duration ')");
+    assert_expression_value("parsePeriod('P1H')", "NotApplicable('invalid period P1H')");
+    assert_expression_value(
+        "for s in ['PT1H', 'oops'] return parseDuration(s)",
+        "[PT1H, NotApplicable('invalid duration oops')]",
+    );
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: parseDuration(t.s)", missing).as_str(), "Missing('s')");
+    assert_eval_value(format!("{}; value: parsePeriod(t.s)", missing).as_str(), "Missing('s')");
+}
+
+#[test]
+fn duration_magnitude_and_sign() {
+    assert_expression_value("absDuration(date('2024-01-01') - date('2024-01-05'))", "P4D");
+    assert_expression_value("durationSign(date('2024-01-01') - date('2024-01-05'))", "-1");
+    assert_expression_value("absDuration(duration('PT90M'))", "PT1H30M");
+    assert_expression_value("durationSign(duration('PT90M'))", "1");
+    assert_expression_value("durationSign(duration('PT30M') - duration('PT30M'))", "0");
+    assert_expression_value("absDuration(duration('PT30M') - duration('PT30M'))", "PT0S");
+
+    let missing = "type T: { d: <duration> }; t: {} as T";
+    assert_eval_value(format!("{}; value: absDuration(t.d)", missing).as_str(), "Missing('d')");
+    assert_eval_value(format!("{}; value: durationSign(t.d)", missing).as_str(), "Missing('d')");
+
+    link_error_contains("value: absDuration(period('P1M'))", &["unexpected", "duration"]);
+    link_error_contains("value: durationSign(5)", &["unexpected", "duration"]);
+}
+
 #[test]
 fn period_parsing_and_operations() {
     assert_expression_value("period('P18M')", "P1Y6M");
@@ -5,8 +5,12 @@ pub use utilities::*;
 fn test_indexing() {
     assert_expression_value("[1,2,3][1]", "2");
     assert_expression_value("[1,2,3][0]", "1");
-    assert_expression_value("[1,2,3][-1]", "Missing('N/A')");
     assert_expression_value("[1,2,3][3]", "Missing('N/A')");
+
+    // negative indices count from the end
+    assert_expression_value("[1,2,3][-1]", "3");
+    assert_expression_value("[1,2,3][-3]", "1");
+    assert_expression_value("[1,2,3][-4]", "Missing('N/A')");
 }
 
 #[test]
@@ -108,3 +112,11 @@ fn test_string_filters() {
         ],
     );
 }
+
+#[test]
+fn test_filter_function() {
+    assert_expression_value("filter([1,2,3], it > 1)", "[2, 3]");
+    assert_expression_value("filter([{a: 1},{a: 2}], a > 1)", "[{a: 2}]");
+    assert_expression_value("filter([1,2,3], 0)", "1");
+    assert_eq!(eval_value("value: filter([1,2,3], it > 1)"), eval_value("value: [1,2,3][it > 1]"));
+}
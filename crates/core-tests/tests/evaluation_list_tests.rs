@@ -42,6 +42,11 @@ fn list_slicing_and_mutation() {
     // sublist
     assert_expression_value("sublist([1,2,3], 2)", "[2, 3]");
     assert_expression_value("sublist([1,2,3], 1, 2)", "[1, 2]");
+    assert_expression_value("sublist([1,2,3,4], -2)", "[3, 4]");
+    assert_expression_value("sublist([1,2,3,4], -3, 2)", "[2, 3]");
+    assert_expression_value("sublist([1,2,3,4], 3, -5)", "[]");
+    assert_expression_value("sublist([1,2,3], -10)", "[1, 2, 3]");
+    assert_expression_value("sublist([1,2,3], -10, 2)", "[1, 2]");
 
     // append
     assert_expression_value("append(['a'], 'b', 'c')", "['a', 'b', 'c']");
@@ -65,19 +70,121 @@ fn list_order_and_indexing() {
     // indexOf returns 1-based positions (list)
     assert_expression_value("indexOf([1,2,3,2], 2)", "[2, 4]");
 
+    // indexOfMax / indexOfMin return the 1-based position of the first extremum
+    assert_expression_value("indexOfMax([3, 9, 9, 1])", "2");
+    assert_expression_value("indexOfMin([3, 1, 1.0, 2])", "2");
+    assert_expression_value("indexOfMin([2.5, 1, 0.5])", "3");
+    assert_expression_value("indexOfMax([7])", "1");
+    assert_expression_value("indexOfMax([1, 2][... > 5])", "Missing('N/A')");
+    runtime_error_contains("value: indexOfMin([[1, 5], [2]])", &["is not supported"]);
+
     // sort default ascending
     assert_expression_value("sort([3,1,4,2])", "[1, 2, 3, 4]");
     assert_expression_value("sort(['b','a','c'])", "['a', 'b', 'c']");
 
     assert_expression_value("sortDescending([3,1,4,2])", "[4, 3, 2, 1]");
+    // integers and reals are compared by value, not by their text
+    assert_expression_value("sort([10, 2.5, 1, 1.5])", "[1, 1.5, 2.5, 10]");
+    assert_expression_value("sortDescending([10, 2.5, 1, 1.5])", "[10, 2.5, 1.5, 1]");
     assert_expression_value("sortDescending(['b','a','c'])", "['c', 'b', 'a']");
 }
 
+#[test]
+fn list_map_projection() {
+    // `it`-scoped projection
+    assert_expression_value("map([1,2,3], it * 2)", "[2, 4, 6]");
+    assert_expression_value("map(1..3, it + 1)", "[2, 3, 4]");
+
+    // lambda projection may reference outer fields
+    assert_eval_value(
+        r#"
+        factor: 10
+        value: map([1, 2, 3], (x) => x * factor)
+    "#,
+        "[10, 20, 30]",
+    );
+    assert_expression_value("map([{a: 1}, {a: 2}], (item) => item.a)", "[1, 2]");
+
+    // the lambda body stays intact when its map is evaluated more than once
+    assert_eval_value(
+        r#"
+        func double(xs): { result: map(xs, (x) => x * 2) }
+        value: [double([1]).result, double([2, 3]).result]
+    "#,
+        "[[2], [4, 6]]",
+    );
+
+    // composes with filters and aggregates
+    assert_expression_value("sum(map([1,2,3], it * 2)[it > 2])", "10");
+
+    parse_error_contains("{ value: map([1,2,3], (x, y) => x) }", &["map lambda expects exactly 1 parameter, got 2"]);
+}
+
+#[test]
+fn list_reduce_fold() {
+    // the accumulator is `acc` and the element is `it`
+    assert_expression_value("reduce([1,2,3], 0, acc + it)", "6");
+    assert_expression_value("reduce(['a','b','c'], '', it + acc)", "'cba'");
+    assert_expression_value("reduce(1..4, 0, acc + it)", "10");
+    assert_expression_value("reduce([{v: 2}, {v: 3}], 1, acc * it.v)", "6");
+    assert_expression_value("reduce([3, 9, 4], 0, if it > acc then it else acc)", "9");
+    assert_expression_value("reduce([1,2], [0], append(acc, it * 2))", "[0, 2, 4]");
+
+    // lambda parameters name the accumulator and the element
+    assert_expression_value("reduce([1,2,3], 1, (product, x) => product * x)", "6");
+    assert_eval_value(
+        r#"
+        factor: 10
+        value: reduce([1, 2], 0, (total, x) => total + x * factor)
+    "#,
+        "30",
+    );
+    assert_eval_value(
+        r#"
+        func total(xs): { result: reduce(xs, 0, (sum, x) => sum + x) }
+        value: [total([1]).result, total([2, 3]).result]
+    "#,
+        "[1, 5]",
+    );
+
+    // an empty list returns the initial value
+    assert_expression_value("reduce([1,2,3][... > 5], 10, acc + it)", "10");
+
+    // a missing list input is an empty list
+    assert_eval_value(
+        r#"
+        type Loans: {amounts: <number[]>}
+        func total(loans: Loans): { result: reduce(loans.amounts, 0, acc + it) }
+        value: total({}).result
+    "#,
+        "0",
+    );
+
+    link_error_contains("value: reduce([1,2], 0, toString(it))", &["reduce types", "number", "string"]);
+    link_error_contains("value: reduce(5, 0, acc + it)", &["cannot reduce non list type"]);
+    parse_error_contains(
+        "{ value: reduce([1,2], 0, (x) => x) }",
+        &["reduce lambda expects exactly 2 parameters, got 1"],
+    );
+    parse_error_contains("{ value: reduce([1,2], 0) }", &["Function 'reduce' expected 3 arguments, but got 2"]);
+}
+
 #[test]
 fn list_set_ops_and_flatten() {
     // union (dedup across lists)
     assert_expression_value("union([1,2], [2,3])", "[1, 2, 3]");
 
+    // intersection keeps the order of the first list, difference removes anything found in later lists
+    assert_expression_value("intersection([3,1,2,1], [1,2,5])", "[1, 2]");
+    assert_expression_value("intersection([1,2,3], [2,3,4], [3,2])", "[2, 3]");
+    assert_expression_value("intersection([1,2], [3])", "[]");
+    assert_expression_value("intersection(['a','b','a'])", "['a', 'b']");
+    assert_expression_value("difference([4,1,2,3,1], [2], [3,5])", "[4, 1]");
+    assert_expression_value("difference([1,2], [5])", "[1, 2]");
+    assert_expression_value("difference([1,2], [1,2])", "[]");
+    runtime_error_contains("value: intersection([{a: 1}], [{a: 1}])", &["not supported"]);
+    link_error_contains("value: difference([1], ['a'])", &["difference"]);
+
     // distinct / duplicates
     assert_expression_value("distinctValues([1,2,3,2,1])", "[1, 2, 3]");
     assert_expression_value("duplicateValues([1,2,3,2,1])", "[2, 1]");
@@ -96,11 +203,26 @@ fn list_join_empty_partition() {
     // isEmpty
     assert_expression_value("isEmpty(sublist([1], 1, 0))", "true");
     assert_expression_value("isEmpty([1])", "false");
+    assert_expression_value("isEmpty('')", "true");
+    assert_expression_value("isEmpty('a')", "false");
+    assert_expression_value("isEmpty('abc')", "false");
 
     // partition
     assert_expression_value("partition([1,2,3,4,5], 2)", "[[1, 2], [3, 4], [5]]");
 }
 
+#[test]
+fn list_zip() {
+    assert_expression_value("zip([1, 2, 3], ['a', 'b'])", "[[1, 'a'], [2, 'b']]");
+    assert_expression_value("zip([1, 2], [10, 20, 30])", "[[1, 10], [2, 20]]");
+    assert_expression_value("for pair in zip([1, 2], [3, 4]) return pair[0] * pair[1]", "[3, 8]");
+    assert_expression_value("zip([1, 2, 3][... > 5], [1])", "[]");
+    assert_expression_value("zip([1], [])", "[]");
+
+    link_error_contains("value : zip(1, [1])", &["unexpected", "number"]);
+    runtime_error_contains("value : zip([{a: 1}], [1])", &["is not supported"]);
+}
+
 #[test]
 fn list_numeric_aggregates() {
     assert_expression_value("min([1,2,3])", "1");
@@ -115,6 +237,131 @@ fn list_numeric_aggregates() {
     assert_expression_value("mode([1,2,2,3])", "[2]");
 }
 
+#[test]
+fn list_cumulative_sum() {
+    assert_expression_value("cumulativeSum([1, 2, 3])", "[1, 3, 6]");
+    assert_expression_value("cumulativeSum([1.5, 2, -0.5])", "[1.5, 3.5, 3]");
+    assert_expression_value("cumulativeSum([1, 2, 3])[2] + 1", "7");
+    assert_expression_value("cumulativeSum([1, 2, 3][... > 5])", "[]");
+    assert_expression_value("cumulativeSum([9223372036854775807, 1])", "[9223372036854775807, 9223372036854775808]");
+    link_error_contains("value : cumulativeSum(['a', 'b'])", &["unexpected", "number"]);
+}
+
+#[test]
+fn list_histogram() {
+    assert_expression_value("for b in histogram([1, 2, 3, 4, 5], 2) return b.bucket", "[0, 2, 4]");
+    assert_expression_value("for b in histogram([1, 2, 3, 4, 5], 2) return b.count", "[1, 2, 2]");
+    assert_expression_value("for b in histogram([0.5, -1.5, 2.25], 0.5) return b.bucket", "[-1.5, 0.5, 2]");
+    assert_expression_value("count(histogram([10, 11, 30], 10))", "2");
+    assert_expression_value("histogram([1, 2, 3][... > 5], 2)", "[]");
+    runtime_error_contains("value : histogram([1, 2], 0)", &["bucket size must be positive"]);
+    runtime_error_contains("value : histogram([1, 2], -1)", &["bucket size must be positive"]);
+    assert_expression_value("histogram([1, 2], varianceSample([5]))", "Missing('N/A')");
+    link_error_contains("value : histogram(['a', 'b'], 2)", &["unexpected", "number"]);
+}
+
+#[test]
+fn list_mean_and_stddev_with_large_offset() {
+    // references: mean = 10^9 + 500.5 and stddev = sqrt((1000^2 - 1) / 12) = 288.674990257209500438266704...
+    let values = "for x in 1..1000 return 1000000000 + x";
+    assert_expression_value(&format!("mean({})", values), "1000000500.5");
+    assert_expression_value(
+        &format!("abs(stddev({}) - 288.674990257209500438266704) < 0.000000000001", values),
+        "true",
+    );
+    assert_expression_value(
+        "abs(stddev([1000000004, 1000000007, 1000000013, 1000000016]) - 4.743416490252568997998340) < 0.000000000001",
+        "true",
+    );
+
+    // values whose sum does not fit a real still have a mean
+    assert_expression_value(
+        "mean([50000000000000000000000000000, 50000000000000000000000000000])",
+        "50000000000000000000000000000",
+    );
+}
+
+#[test]
+fn list_variance_and_sample_stddev() {
+    let values = "[2, 4, 4, 4, 5, 5, 7, 9]";
+    assert_expression_value(&format!("stddev({})", values), "2");
+    assert_expression_value(&format!("variance({})", values), "4");
+    assert_expression_value(&format!("varianceSample({})", values), "4.5714285714285714285714285714");
+    assert_expression_value(&format!("abs(stddevSample({}) - 2.138089935299395) < 0.000000000001", values), "true");
+
+    // sample variants need at least two values
+    assert_expression_value("variance([5])", "0");
+    assert_expression_value("varianceSample([5])", "Missing('N/A')");
+    assert_expression_value("stddevSample([5])", "Missing('N/A')");
+    assert_expression_value("stddevSample([1, 2, 3][... > 5])", "Missing('N/A')");
+
+    assert_expression_value("variance([duration('PT2S'), duration('PT4S')])", "1");
+    assert_expression_value("varianceSample([duration('PT2S'), duration('PT4S')])", "2");
+}
+
+#[test]
+fn list_percentile() {
+    assert_expression_value("percentile([1, 2, 3, 4], 50)", "2.5");
+    assert_expression_value("percentile([50, 15, 40, 20, 35], 40)", "29");
+    assert_expression_value("percentile([3, 1, 2], 0)", "1");
+    assert_expression_value("percentile([3, 1, 2], 100)", "3");
+    assert_expression_value("percentile([1.5, 2.5], 25)", "1.75");
+    assert_expression_value("percentile([5], 90)", "5");
+
+    assert_expression_value("percentile([1, 2, 3][... > 5], 50)", "Missing('N/A')");
+    assert_expression_value("percentile([1, 2], 101)", "NotApplicable('percentile 101 is out of range [0, 100]')");
+    assert_expression_value("percentile([1, 2], -1)", "NotApplicable('percentile -1 is out of range [0, 100]')");
+}
+
+#[test]
+fn list_mode_for_non_numeric_primitives() {
+    assert_expression_value("mode(['a', 'b', 'a', 'c'])", "['a']");
+    // ties return all modes in first-seen order
+    assert_expression_value("mode(['b', 'a', 'a', 'b', 'c'])", "['b', 'a']");
+    assert_expression_value("mode([true, false, false])", "[false]");
+    assert_expression_value("mode([true, false])", "[true, false]");
+    assert_expression_value("mode([date('2024-01-02'), date('2024-01-01'), date('2024-01-02')])", "[2024-01-02]");
+    assert_expression_value("mode([])", "[]");
+
+    // result keeps the list item type, so string functions can be applied to it
+    assert_eval_value(
+        r#"
+        colors: ['red', 'blue', 'red']
+        value: toUpperCase(mode(colors)[0])
+        "#,
+        "'RED'",
+    );
+}
+
+#[test]
+fn list_duration_aggregates() {
+    assert_expression_value("mean([duration('PT1H'), duration('PT3H')])", "PT2H");
+    assert_expression_value("mean([duration('PT1S'), duration('PT2S')])", "PT2S");
+    assert_expression_value("mean([duration('PT2S'), duration('PT3S')])", "PT2S");
+    assert_expression_value("mean([duration('-PT1H'), duration('-PT3H')])", "-PT2H");
+    assert_expression_value("median([duration('PT5M'), duration('PT1H'), duration('PT10M')])", "PT10M");
+    assert_expression_value("median([duration('PT1H'), duration('PT2H')])", "PT1H30M");
+    assert_expression_value("stddev([duration('PT2S'), duration('PT4S')])", "1");
+    assert_expression_value("stddev([duration('PT1H'), duration('PT1H')])", "0");
+
+    // the result type follows the list item type
+    assert_eval_value(
+        r#"
+        latencies: [duration('PT1M'), duration('PT3M')]
+        value: mean(latencies) + duration('PT1M')
+        "#,
+        "PT3M",
+    );
+}
+
+#[test]
+fn list_string_extrema() {
+    assert_expression_value("max(['apple', 'banana'])", "'banana'");
+    assert_expression_value("min(['pear', 'apple', 'fig'])", "'apple'");
+    assert_expression_value("max('a', 'c', 'b')", "'c'");
+    assert_eval_value("names: ['b', 'a']; value: min(names) + '!'", "'a!'");
+}
+
 #[test]
 fn complex_objects_in_lists() {
     assert_eval_value(
@@ -135,6 +382,192 @@ fn complex_objects_in_lists() {
     );
 }
 
+#[test]
+fn object_lists_append_and_concatenate() {
+    assert_eval_value(
+        r#"
+        first: [{a:1}, {a:2}]
+        second: [{a:3}]
+        value: for item in concatenate(first, second) return item.a
+    "#,
+        "[1, 2, 3]",
+    );
+
+    assert_eval_value(
+        r#"
+        first: [{a:1}, {a:2}]
+        second: [{a:3}]
+        value: for item in append(first, second[0], first[0]) return item.a
+    "#,
+        "[1, 2, 3, 1]",
+    );
+}
+
+#[test]
+fn object_list_sublist() {
+    let applicants = "applicants: [{name: 'a'; score: 3}, {name: 'b'; score: 9}, {name: 'c'; score: 5}]";
+    assert_eval_value(
+        format!("{}; value: for item in sublist(sortByDesc(applicants, score), 1, 2) return item.name", applicants)
+            .as_str(),
+        "['b', 'c']",
+    );
+    assert_eval_value(format!("{}; value: sublist(applicants, -1)[0].score", applicants).as_str(), "5");
+    assert_eval_value(format!("{}; value: count(sublist(applicants, 2, 10))", applicants).as_str(), "2");
+    assert_eval_value(format!("{}; value: count(sublist(applicants, 5))", applicants).as_str(), "0");
+    assert_eval_value(format!("{}; value: count(sublist(applicants, 1, -5))", applicants).as_str(), "0");
+}
+
+#[test]
+fn object_lists_join_by() {
+    let people = "people: [{name: 'Ann'; age: 30}, {name: 'Bob'; age: 25}]";
+    assert_eval_value(format!("{}; value: joinBy(people, 'name', ', ')", people).as_str(), "'Ann, Bob'");
+    assert_eval_value("people: []; value: joinBy(people, 'name', ', ')", "''");
+    runtime_error_contains(
+        format!("{}; value: joinBy(people, 'age', ', ')", people).as_str(),
+        &["joinBy field 'age' must be a string"],
+    );
+}
+
+#[test]
+fn object_list_lookup() {
+    let rates = "rates: [{band: 'A'; rate: 0.1}, {band: 'B'; rate: 0.2}, {band: 'C'; rate: 0.35}]";
+    assert_eval_value(format!("{}\nvalue: lookup(rates, 'band', 'B', 'rate')", rates).as_str(), "0.2");
+    assert_eval_value(format!("{}\nvalue: lookup(rates, 'band', 'C', 'rate') * 100", rates).as_str(), "35");
+    assert_eval_value(format!("{}\nvalue: lookup(rates, 'band', 'Z', 'rate')", rates).as_str(), "Missing('rate')");
+
+    // the first matching object wins and nested objects can be selected further
+    assert_eval_value(
+        r#"
+        tiers: [{band: 'A'; limits: {max: 10}}, {band: 'A'; limits: {max: 20}}]
+        value: lookup(tiers, 'band', 'A', 'limits').max
+    "#,
+        "10",
+    );
+
+    link_error_contains(&format!("{{ {}; value: lookup(rates, 'band', 1, 'rate') }}", rates), &["band", "number"]);
+    link_error_contains(&format!("{{ {}; value: lookup(rates, 'band', 'A', 'fee') }}", rates), &["fee", "not found"]);
+    link_error_contains("value: lookup([1, 2], 'band', 'A', 'rate')", &["lookup expects a list of objects"]);
+    parse_error_contains(
+        &format!("{{ {}; field: 'band'; value: lookup(rates, field, 'A', 'rate') }}", rates),
+        &["lookup expects a field name as a string literal"],
+    );
+    parse_error_contains("{ value: lookup([], 'band', 'A') }", &["Function 'lookup' expected 4 arguments, but got 3"]);
+}
+
+#[test]
+fn list_sort_by_key() {
+    let people =
+        "people: [{name: 'Ann'; age: 30}, {name: 'Bob'; age: 25}, {name: 'Cid'; age: 30}, {name: 'Dan'; age: 25}]";
+    // equal keys keep their original order in both directions
+    assert_eval_value(
+        format!("{}\nvalue: for p in sortBy(people, age) return p.name", people).as_str(),
+        "['Bob', 'Dan', 'Ann', 'Cid']",
+    );
+    assert_eval_value(
+        format!("{}\nvalue: for p in sortByDesc(people, age) return p.name", people).as_str(),
+        "['Ann', 'Cid', 'Bob', 'Dan']",
+    );
+    assert_eval_value(
+        format!("{}\nvalue: for p in sortByDesc(people, name) return p.name", people).as_str(),
+        "['Dan', 'Cid', 'Bob', 'Ann']",
+    );
+
+    assert_expression_value("sortBy([3, -1, 2], abs(it))", "[-1, 2, 3]");
+    assert_expression_value("sortByDesc([3, -1, 2.5], ...)", "[3, 2.5, -1]");
+    // keys without a natural order are compared as text
+    assert_expression_value("sortBy([1, 2, 3, 4], modulo(it, 2) = 0)", "[1, 3, 2, 4]");
+    assert_expression_value("sortBy([1, 2, 3][... > 5], it)", "[]");
+
+    link_error_contains("value : sortBy(5, it)", &["sortby subject", "number"]);
+    link_error_contains("value : sortByDesc([1, 2], unknownField)", &["unknownField", "not found"]);
+}
+
+#[test]
+fn list_all_and_any_with_predicate() {
+    assert_expression_value("all([1, 2, 3], ... > 0)", "true");
+    assert_expression_value("any([1, 2, 3], it > 2)", "true");
+    assert_expression_value("all([1, 2, 3][... > 5], it > 0)", "true");
+    assert_expression_value("any([1, 2, 3][... > 5], it > 0)", "false");
+
+    let loans = "loans: [{amount: 100; overdue: false}, {amount: 900; overdue: true}]";
+    assert_eval_value(format!("{}\nvalue: any(loans, overdue)", loans).as_str(), "true");
+    assert_eval_value(format!("{}\nvalue: all(loans, amount > 50)", loans).as_str(), "true");
+    assert_eval_value(format!("{}\nvalue: all(loans, amount > 500)", loans).as_str(), "false");
+
+    link_error_contains("value : any(5, it)", &["any subject", "number"]);
+    link_error_contains("value : all([1, 2], it + 1)", &["all predicate", "boolean"]);
+    parse_error_contains(
+        "{ value: all([true], it, 1) }",
+        &["all accepts 1 or 2 arguments, got 3 in 'all([true], it, 1)'"],
+    );
+}
+
+#[test]
+fn list_take_and_drop_while() {
+    assert_expression_value("takeWhile([1, 2, 5, 1], ... < 3)", "[1, 2]");
+    assert_expression_value("dropWhile([1, 2, 5, 1], ... < 3)", "[5, 1]");
+
+    // a predicate that holds for every element keeps or drops the whole list
+    assert_expression_value("takeWhile([1, 2], it < 10)", "[1, 2]");
+    assert_expression_value("dropWhile([1, 2], it < 10)", "[]");
+    assert_expression_value("takeWhile([1, 2], it > 10)", "[]");
+    assert_expression_value("dropWhile([1, 2], it > 10)", "[1, 2]");
+    assert_expression_value("takeWhile([1, 2, 3][... > 5], it > 0)", "[]");
+
+    let steps = "steps: [{name: 'a'; done: true}, {name: 'b'; done: false}, {name: 'c'; done: true}]";
+    assert_eval_value(format!("{}\nvalue: for s in takeWhile(steps, done) return s.name", steps).as_str(), "['a']");
+    assert_eval_value(
+        format!("{}\nvalue: for s in dropWhile(steps, done) return s.name", steps).as_str(),
+        "['b', 'c']",
+    );
+
+    link_error_contains("value : takeWhile([1, 2], it + 1)", &["takewhile predicate", "boolean"]);
+    link_error_contains("value : dropWhile(5, it > 1)", &["dropwhile subject", "number"]);
+}
+
+#[test]
+fn list_flat_map() {
+    assert_expression_value("flatMap([1, 2, 3], [it, it * 10])", "[1, 10, 2, 20, 3, 30]");
+    assert_expression_value("flatMap([[1, 2], [3]], ...)", "[1, 2, 3]");
+
+    // only one level is flattened and scalar results are single-element lists
+    assert_expression_value("flatMap([1, 2], [[it]])", "[[1], [2]]");
+    assert_expression_value("flatMap([1, 2, 3], it * 2)", "[2, 4, 6]");
+
+    assert_expression_value("flatMap([1, 2, 3][... > 5], [it])", "[]");
+    assert_expression_value("flatMap([1, 2], [1, 2][... > it + 5])", "[]");
+
+    let orders = "orders: [{id: 1; tags: ['a', 'b']}, {id: 2; tags: []}, {id: 3; tags: ['c']}]";
+    assert_eval_value(format!("{}\nvalue: flatMap(orders, tags)", orders).as_str(), "['a', 'b', 'c']");
+    assert_eval_value(format!("{}\nvalue: count(flatMap(orders, [id, id]))", orders).as_str(), "6");
+
+    link_error_contains("value : flatMap(5, [it])", &["flatmap subject", "number"]);
+}
+
+#[test]
+fn list_group_by() {
+    let orders = "orders: [{region: 'EU'; amount: 10}, {region: 'US'; amount: 5}, {region: 'EU'; amount: 7}]";
+    assert_eval_value(
+        format!("{}\nvalue: for g in groupBy(orders, region) return g.key", orders).as_str(),
+        "['EU', 'US']",
+    );
+    assert_eval_value(
+        format!("{}\nvalue: for g in groupBy(orders, region) return sum(for o in g.items return o.amount)", orders)
+            .as_str(),
+        "[17, 5]",
+    );
+    assert_eval_value(format!("{}\nvalue: count(groupBy(orders, region)[0].items)", orders).as_str(), "2");
+
+    // keys are computed per element and keep the order of their first appearance
+    assert_expression_value("for g in groupBy([1, 2, 3, 4, 5], modulo(it, 2) = 0) return g.key", "[false, true]");
+    assert_expression_value("for g in groupBy([1, 2, 3, 4, 5], modulo(it, 2) = 0) return count(g.items)", "[3, 2]");
+    assert_expression_value("groupBy([3, 1, 3], ...)[0].items", "[3, 3]");
+    assert_expression_value("groupBy([1, 2, 3][... > 5], it)", "[]");
+
+    link_error_contains("value : groupBy(5, it)", &["groupby subject", "number"]);
+    link_error_contains("value : groupBy([1, 2], unknownField)", &["unknownField", "not found"]);
+}
+
 #[test]
 fn list_numeric_unhappy_paths() {
     // Using strings where numbers are expected
@@ -142,6 +575,8 @@ fn list_numeric_unhappy_paths() {
     link_error_contains("value : mean(['1','2'])", &["unexpected", "number"]);
     link_error_contains("value : median(['x'])", &["unexpected", "number"]);
     link_error_contains("value : stddev(['x','y'])", &["unexpected", "number"]);
+    link_error_contains("value : percentile(['x','y'], 50)", &["unexpected", "number"]);
+    link_error_contains("value : percentile([1, 2], '50')", &["unexpected", "number"]);
 
     // Using dates where numbers are expected
     link_error_contains("value : product([date('2017-05-03')])", &["unexpected", "number"]);
@@ -36,6 +36,32 @@ fn test_conditionals() {
     assert_expression_value("2 * (if 1 < 2 then if 5 > 2 then 5 * 10 else 0 else 1) + 1", "101");
 }
 
+#[test]
+fn test_decision_table() {
+    let table = r#"
+        func grade(score): {
+            result: decisionTable(
+                [score >= 90, 'A'],
+                [score >= 50, score < 90, 'B'],
+                ['C']
+            )
+        }
+    "#;
+    assert_eval_value(format!("{}\nvalue: grade(95).result", table).as_str(), "'A'");
+    assert_eval_value(format!("{}\nvalue: grade(72).result", table).as_str(), "'B'");
+    assert_eval_value(format!("{}\nvalue: grade(10).result", table).as_str(), "'C'");
+
+    // the first matching row wins, without a default row no match is omitted
+    assert_expression_value("decisionTable([1 < 2, 1], [2 < 3, 2], [3])", "1");
+    assert_expression_value("decisionTable([1 > 2, 1], [2 < 3, 2])", "2");
+    assert_expression_value("decisionTable([1 > 2, 1])", "Omitted");
+
+    parse_error_contains("{ value: decisionTable(['C'], [true, 'A']) }", &["default row must be the last row"]);
+    parse_error_contains("{ value: decisionTable(1) }", &["row must be a list of conditions followed by an output"]);
+    link_error_contains("{ value: decisionTable([1, 'A']) }", &["if condition", "boolean"]);
+    link_error_contains("{ value: decisionTable([true, 'A'], [1]) }", &["string", "number", "must match"]);
+}
+
 #[test]
 fn test_boolean_literals_comparators() {
     init_logger();
@@ -10,6 +10,21 @@ fn test_math_abs() {
     assert_expression_value("abs(-1.5)", "1.5");
 }
 
+#[test]
+fn test_math_sign() {
+    init_logger();
+    assert_expression_value("sign(-10)", "-1");
+    assert_expression_value("sign(0)", "0");
+    assert_expression_value("sign(10)", "1");
+    assert_expression_value("sign(-0.5)", "-1");
+    assert_expression_value("sign(-0.0)", "0");
+    assert_expression_value("sign(2.5)", "1");
+
+    // special values pass through unchanged
+    let code = "{ type Amount: { value: <number> }; amount: {} as Amount; result: sign(amount.value) }";
+    assert_eval_field(code, "result", "Missing('value')");
+}
+
 #[test]
 fn test_math_rounding_basic() {
     init_logger();
@@ -132,6 +147,23 @@ fn test_math_clamp() {
     assert_expression_value("clamp(5, 10, 0)", "0"); // if min > max? Logic: if n < min (5 < 10) -> 10. if 10 > max (10 > 0) -> 0. Returns 0.
 }
 
+#[test]
+fn test_math_lerp() {
+    init_logger();
+    assert_expression_value("lerp(0, 10, 0.5)", "5");
+    assert_expression_value("lerp(1.5, 2.5, 0.25)", "1.75");
+    assert_expression_value("lerp(10, 0, 0.2)", "8");
+
+    // t is clamped to [0, 1] unless the unclamped variant is used
+    assert_expression_value("lerp(0, 10, 2)", "10");
+    assert_expression_value("lerp(0, 10, -1)", "0");
+    assert_expression_value("lerpUnclamped(0, 10, 2)", "20");
+    assert_expression_value("lerpUnclamped(0, 10, -1)", "-10");
+
+    let code = "{ type Tier: { rate: <number> }; tier: {} as Tier; result: lerp(0, tier.rate, 0.5) }";
+    assert_eval_field(code, "result", "Missing('rate')");
+}
+
 #[test]
 fn test_math_arithmetic() {
     init_logger();
@@ -156,6 +188,28 @@ fn test_math_power() {
     assert_expression_value("10 ^ 0", "1");
 }
 
+#[test]
+fn test_math_pow_function() {
+    init_logger();
+    assert_expression_value("pow(2, 3)", "8");
+    assert_expression_value("pow(2, 0.5)", "1.4142135623730951454746218583");
+    assert_expression_value("pow(2, 0.5) = 2 ^ 0.5", "true");
+    // negative integer exponent promotes to a real
+    assert_expression_value("pow(2, -1)", "0.5");
+    assert_expression_value("pow(2.5, 2)", "6.25");
+    assert_expression_value("for x in [1, 2, 3] return pow(x, 2)", "[1, 4, 9]");
+    assert_expression_value("pow(exp(1000), 2)", "NotApplicable('exp overflow')");
+    assert_expression_value("pow(10.5, 1000)", "NotApplicable('power overflow')");
+    assert_expression_value("pow(0, -1)", "NotApplicable('power of zero to a negative exponent')");
+    assert_expression_value("0 ^ -1", "NotApplicable('power of zero to a negative exponent')");
+    assert_expression_value("pow(-8, 0.5)", "NotApplicable('fractional power of negative number')");
+    assert_expression_value("pow(10, 400.5)", "NotApplicable('power overflow')");
+    assert_expression_value("pow(2.5, -2)", "0.16");
+
+    runtime_error_contains("value: pow(2, 64)", &["Power result is out of integer range"]);
+    runtime_error_contains("value: 2 ^ 64", &["Power result is out of integer range"]);
+}
+
 #[test]
 fn test_math_unary_minus() {
     init_logger();
@@ -220,6 +274,94 @@ fn test_math_rounding_edge_cases() {
 
     // RoundDown negative numbers (toward zero)
     assert_expression_value("roundDown(-123.45, 1)", "-123.4");
+
+    // Banker's rounding at midpoints, including negative digits on reals
+    assert_expression_value("round(2.5)", "2");
+    assert_expression_value("round(-2.5)", "-2");
+    assert_expression_value("round(0.125, 2)", "0.12");
+    assert_expression_value("round(1.5, -1)", "0");
+    assert_expression_value("round(15.5, -1)", "20");
+    assert_expression_value("roundUp(150, -19)", "10000000000000000000");
+}
+
+#[test]
+fn test_math_non_finite_reals() {
+    init_logger();
+    // Decimal reals overflow instead of becoming infinite, the overflow is not applicable
+    assert_expression_value("exp(1000)", "NotApplicable('exp overflow')");
+    assert_expression_value("round(exp(1000))", "NotApplicable('exp overflow')");
+    assert_expression_value("round(exp(1000), 2)", "NotApplicable('exp overflow')");
+    assert_expression_value("floor(exp(1000))", "NotApplicable('exp overflow')");
+    assert_expression_value("ceiling(exp(1000))", "NotApplicable('exp overflow')");
+    assert_expression_value("trunc(exp(1000))", "NotApplicable('exp overflow')");
+    assert_expression_value("round(150, -30)", "NotApplicable('rounding overflow')");
+
+    assert_expression_value("idiv(exp(60), 0.0000001)", "NotApplicable('division overflow')");
+    assert_expression_value("idiv(exp(1000), 2)", "NotApplicable('exp overflow')");
+    assert_expression_value("modulo(exp(1000), 2)", "NotApplicable('exp overflow')");
+}
+
+#[test]
+fn test_math_log_base() {
+    init_logger();
+    assert_expression_value("abs(logBase(8, 2) - 3) < 0.000000000001", "true");
+    assert_expression_value("abs(logBase(1000, 10) - 3) < 0.000000000001", "true");
+    assert_expression_value("abs(logBase(9.0, 3) - 2) < 0.000000000001", "true");
+    assert_expression_value("abs(logBase(2, 0.5) + 1) < 0.000000000001", "true");
+    assert_expression_value("logBase(1, 5)", "0");
+
+    assert_expression_value("logBase(5, 1)", "NotApplicable('logBase with base 1 is undefined')");
+    assert_expression_value("logBase(0, 2)", "NotApplicable('logBase of non-positive number')");
+    assert_expression_value("logBase(5, -2)", "NotApplicable('logBase of non-positive number')");
+    assert_expression_value("logBase(exp(1000), 2)", "NotApplicable('exp overflow')");
+}
+
+#[test]
+fn test_math_gcd_lcm() {
+    init_logger();
+    assert_expression_value("gcd(12, 18)", "6");
+    assert_expression_value("gcd(-12, 18)", "6");
+    assert_expression_value("gcd(0, -5)", "5");
+    assert_expression_value("gcd(0, 0)", "0");
+    assert_expression_value("gcd(12.0, 8)", "4");
+
+    assert_expression_value("lcm(4, 6)", "12");
+    assert_expression_value("lcm(-4, 6)", "12");
+    assert_expression_value("lcm(0, 6)", "0");
+
+    // special values propagate
+    assert_expression_value("gcd(exp(1000), 6)", "NotApplicable('exp overflow')");
+    assert_expression_value("lcm(4, exp(1000))", "NotApplicable('exp overflow')");
+
+    runtime_error_contains("value: gcd(1.5, 3)", &["gcd expects integers, but got 1.5"]);
+    runtime_error_contains(
+        "value: lcm(9223372036854775807, 9223372036854775806)",
+        &["lcm result is out of integer range"],
+    );
+}
+
+#[test]
+fn test_math_factorial_binomial() {
+    init_logger();
+    assert_expression_value("factorial(0)", "1");
+    assert_expression_value("factorial(5)", "120");
+    assert_expression_value("factorial(5.0)", "120");
+    assert_expression_value("factorial(20)", "2432902008176640000");
+
+    // beyond the integer range the result becomes a real
+    assert_expression_value("factorial(21)", "51090942171709440000");
+    assert_expression_value("factorial(21) = 51090942171709440000.0", "true");
+    assert_expression_value("factorial(28)", "NotApplicable('factorial overflow')");
+
+    assert_expression_value("binomial(5, 2)", "10");
+    assert_expression_value("binomial(5, 0)", "1");
+    assert_expression_value("binomial(5, 5)", "1");
+    assert_expression_value("binomial(2, 5)", "0");
+    assert_expression_value("binomial(60, 30)", "118264581564861424");
+
+    assert_expression_value("factorial(-1)", "NotApplicable('factorial expects a non-negative integer, but got -1')");
+    assert_expression_value("factorial(2.5)", "NotApplicable('factorial expects a non-negative integer, but got 2.5')");
+    assert_expression_value("binomial(5, -1)", "NotApplicable('binomial expects a non-negative integer, but got -1')");
 }
 
 #[test]
@@ -257,6 +399,18 @@ fn test_math_advanced() {
     assert_expression_value("atan2(1, 0)", "1.5707963267948965579989817335"); // pi/2
     assert_expression_value("atan2(1, 1)", "0.7853981633974482789994908668"); // pi/4
 
+    // Hyperbolic
+    assert_expression_value("sinh(0)", "0");
+    assert_expression_value("cosh(0)", "1");
+    assert_expression_value("tanh(0)", "0");
+    assert_expression_value("abs(sinh(1) - 1.1752011936438014) < 0.000000000001", "true");
+    assert_expression_value("abs(cosh(1.5) - 2.3524096152432472) < 0.000000000001", "true");
+    assert_expression_value("abs(tanh(1) - 0.7615941559557649) < 0.000000000001", "true");
+    assert_expression_value("tanh(1000)", "1");
+    assert_expression_value("abs(asinh(1) - 0.881373587019543) < 0.000000000001", "true");
+    assert_expression_value("acosh(1)", "0");
+    assert_expression_value("abs(atanh(0.5) - 0.5493061443340548) < 0.000000000001", "true");
+
     // Conversions
     assert_expression_value("degrees(pi())", "180");
     assert_expression_value("degrees(pi()/2)", "90");
@@ -269,6 +423,10 @@ fn test_math_advanced() {
     assert_expression_value("log10(0)", "NotApplicable('log10 of non-positive number')");
     assert_expression_value("asin(2)", "NotApplicable('asin input out of range [-1, 1]')");
     assert_expression_value("acos(-2)", "NotApplicable('acos input out of range [-1, 1]')");
+    assert_expression_value("sinh(1000)", "NotApplicable('sinh overflow')");
+    assert_expression_value("acosh(0.5)", "NotApplicable('acosh input below 1')");
+    assert_expression_value("atanh(1)", "NotApplicable('atanh input out of range (-1, 1)')");
+    assert_expression_value("atanh(-2)", "NotApplicable('atanh input out of range (-1, 1)')");
 }
 
 #[test]
@@ -50,5 +50,60 @@ fn date_plus_period_days_overflow() {
     );
 }
 
+#[test]
+fn duration_sum_overflow() {
+    // 200,000,000,000,000 days = 1.728e19 seconds, just below u64::MAX (1.8e19).
+    // A single value fits, but the sum of two does not.
+    runtime_error_contains(
+        "value: sum([duration('P200000000000000D'), duration('P200000000000000D')])",
+        &["duration sum overflow"],
+    );
+    runtime_error_contains(
+        "value: sum(duration('P200000000000000D'), duration('P200000000000000D'))",
+        &["duration sum overflow"],
+    );
+    assert_expression_value(
+        "sum([duration('P200000000000000D'), duration('-P200000000000000D'), duration('PT1H')])",
+        "PT1H",
+    );
+}
+
+#[test]
+fn sublist_out_of_range_index() {
+    runtime_error_contains(
+        "value: sublist([1,2,3], 10000000000000000000000000000)",
+        &["Number 10000000000000000000000000000 is out of integer range"],
+    );
+    runtime_error_contains("value: sublist([1,2,3], 1, 10000000000000000000000000000)", &["out of integer range"]);
+
+    // indexes that fit an integer are clamped to the list bounds
+    assert_expression_value("sublist([1,2,3], 2, 9223372036854775807)", "[2, 3]");
+    assert_expression_value("sublist([1,2,3], -9223372036854775807 - 1, 2)", "[1, 2]");
+    assert_expression_value("insertBefore([1,2], -9223372036854775807 - 1, 3)", "[3, 1, 2]");
+    assert_expression_value("partition([1,2,3], 9223372036854775807)", "[[1, 2, 3]]");
+}
+
+#[test]
+fn string_and_rounding_arguments_out_of_range() {
+    runtime_error_contains("value: round(1.5, 10000000000000000000000000000)", &["out of integer range"]);
+    runtime_error_contains("value: substring('abc', 10000000000000000000000000000)", &["out of integer range"]);
+    runtime_error_contains("value: period('P9999999999Y')", &["Failed to parse 'period'"]);
+
+    assert_expression_value("padStart('ab', -1, 'x')", "'ab'");
+    assert_expression_value("substring('abc', -9223372036854775807, 9223372036854775807)", "'abc'");
+}
+
+#[test]
+fn sum_and_product_overflow_promote_to_real() {
+    assert_expression_value("product([9223372036854775807, 2])", "18446744073709551614");
+    assert_expression_value("sum([9223372036854775807, 1])", "9223372036854775808");
+    assert_expression_value("sum([-9223372036854775807, -2])", "-9223372036854775809");
+    assert_expression_value("product([3, 4])", "12");
+
+    // promoted values stay reals, so the following integers keep accumulating
+    assert_expression_value("product([9223372036854775807, 2, 2]) = 36893488147419103228", "true");
+    assert_expression_value("product([79228162514264337593543950335, 2])", "NotApplicable('product overflow')");
+}
+
 mod utilities;
 pub use utilities::*;
@@ -1,3 +1,6 @@
+use edge_rules::test_support::EdgeRulesRuntime;
+use edge_rules::typesystem::types::Float;
+
 mod utilities;
 use utilities::*;
 
@@ -50,3 +53,39 @@ fn test_simple_addition_precision() {
     // 0.1 + 0.2 = 0.3. In f64 this is 0.30000000000000004
     assert_eval_field(code, "c", "0.3");
 }
+
+const REAL_COMPARISONS: &str = r#"
+{
+    third: 1 / 3
+    equal: third * 3 = 1
+    less: third * 3 < 1
+    greaterOrEqual: third * 3 >= 1
+    close: 2 > 1.9999999
+    integers: 1 = 2
+    func isOne(x): { result: x = 1 }
+    inFunction: isOne(third * 3).result
+    nested: { equal: third * 3 = 1 }
+}
+"#;
+
+fn eval_comparisons(tolerance: Option<Float>) -> Vec<String> {
+    let mut runtime = EdgeRulesRuntime::from_source(REAL_COMPARISONS).unwrap();
+    runtime.set_real_tolerance(tolerance);
+    ["equal", "less", "greaterOrEqual", "close", "integers", "inFunction", "nested.equal"]
+        .iter()
+        .map(|field| runtime.evaluate_field(field).unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_real_comparison_is_exact_by_default() {
+    init_logger();
+    assert_eq!(eval_comparisons(None), ["false", "true", "false", "true", "false", "false", "false"]);
+}
+
+#[test]
+fn test_real_comparison_with_tolerance() {
+    init_logger();
+    // 1 / 3 * 3 = 0.9999999999999999999999999999 is within the tolerance of 0.000001
+    assert_eq!(eval_comparisons(Some(Float::new(1, 6))), ["true", "false", "true", "false", "false", "true", "true"]);
+}
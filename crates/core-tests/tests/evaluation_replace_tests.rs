@@ -10,8 +10,9 @@ fn test_replace_case_insensitive_basic() {
 
 #[test]
 fn test_replace_empty_pattern_behavior() {
-    // Empty pattern inserts between every boundary and ends (Rust replace semantics)
-    assert_expression_value("replace(\"abc\", \"\", \"-\")", "'-a-b-c-'");
+    // Empty pattern finds nothing, so the input is returned unchanged
+    assert_expression_value("replace(\"abc\", \"\", \"-\")", "'abc'");
+    assert_expression_value("replace(\"abc\", \"\", \"-\", \"i\")", "'abc'");
     // For replaceFirst/Last we keep explicit, predictable behavior
     assert_expression_value("replaceFirst(\"abc\", \"\", \"x\")", "'xabc'");
     assert_expression_value("replaceLast(\"abc\", \"\", \"x\")", "'abcx'");
@@ -23,3 +24,16 @@ fn test_replace_case_insensitive_non_ascii() {
     assert_expression_value("replace(\"Ábcd\", \"á\", \"x\", \"i\")", "'xbcd'");
     assert_expression_value("replace(\"Ää\", \"ä\", \"x\", \"i\")", "'xx'");
 }
+
+#[test]
+fn test_replace_all_occurrences() {
+    assert_expression_value("replace(\"order-id-7\", \"-\", \"_\")", "'order_id_7'");
+    // occurrences do not overlap
+    assert_expression_value("replace(\"aaaa\", \"aa\", \"b\")", "'bb'");
+    assert_expression_value("replace(\"abc\", \"x\", \"y\")", "'abc'");
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: replace(t.s, 'a', 'b')", missing).as_str(), "Missing('s')");
+    assert_eval_value(format!("{}; value: replace('abc', 'a', t.s)", missing).as_str(), "Missing('s')");
+    link_error_contains("value: replace('abc', 1, 'b')", &["unexpected", "string"]);
+}
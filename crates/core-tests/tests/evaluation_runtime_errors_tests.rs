@@ -95,3 +95,27 @@ fn runtime_error_captured_in_eval_all_preserves_location() {
     assert_eq!(err.expression().map(|s| s.as_str()), Some("1 / 0"));
     assert!(err.to_string().to_lowercase().contains("division by zero"));
 }
+
+#[test]
+fn runtime_error_names_the_evaluated_field() {
+    let code = r#"
+{
+    loan: {
+        amount: 100
+        months: 0
+        monthlyPayment: amount / months
+    }
+}
+"#;
+
+    let mut model = EdgeRulesModel::new();
+    model.append_source(code).unwrap();
+    let runtime = model.to_runtime().unwrap();
+    runtime.eval_all().expect("eval_all should return Ok even if fields fail");
+
+    let err = runtime.evaluate_field("loan.monthlyPayment").expect_err("expected error");
+    let message = err.to_string();
+
+    assert_string_contains("Division by zero", &message);
+    assert_string_contains("While evaluating field `loan.monthlyPayment`", &message);
+}
@@ -40,6 +40,8 @@ fn cast_nested_object_tracks_field_path() {
         "#,
         &[
             "{",
+            "   type Address: {city: <string>; zip: <number>}",
+            "   type Person: {address: <Address>}",
             "   value: {",
             "      address: {",
             "         city: 'Vilnius'",
@@ -72,6 +74,8 @@ fn missing_is_applied_for_function_argument() {
         model,
         &[
             "{",
+            "   type LoanOffer: {eligible: <boolean>; amount: <number>; termInMonths: <number>; monthlyPayment: <number>}",
+            "   func inc(x: LoanOffer) : {termInMonths: x.termInMonths * 2; result: x}",
             "   value: {",
             "      eligible: Missing('eligible')",
             "      amount: 100",
@@ -1,3 +1,6 @@
+use edge_rules::ast::functions::function_string::{eval_to_lower, eval_to_upper, eval_trim};
+use edge_rules::typesystem::types::string::StringEnum;
+use edge_rules::typesystem::values::ValueEnum;
 mod utilities;
 pub use utilities::*;
 
@@ -41,6 +44,114 @@ fn test_string_functions() {
     assert_expression_value("interpolate('Hi ${name}', { name : 'Ana' })", "'Hi Ana'");
 }
 
+#[test]
+fn split_edge_cases() {
+    assert_expression_value("split('a,b,,c', ',')", "['a', 'b', '', 'c']");
+    assert_expression_value("split('abc', '')", "['a', 'b', 'c']");
+    assert_expression_value("split('', ',')", "['']");
+    assert_expression_value("split('', '')", "['']");
+    assert_expression_value("split('a--b', '--')", "['a', 'b']");
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: split(t.s, ',')", missing).as_str(), "[Missing('s')]");
+    assert_eval_value(format!("{}; value: split('a,b', t.s)", missing).as_str(), "[Missing('s')]");
+}
+
+#[test]
+fn case_insensitive_matching() {
+    assert_expression_value("equalsIgnoreCase('Yes', 'yes')", "true");
+    assert_expression_value("equalsIgnoreCase('Yes', 'yes ')", "false");
+    assert_expression_value("equalsIgnoreCase('ÄRZTE', 'ärzte')", "true");
+    assert_expression_value("containsIgnoreCase('Free Text Input', 'text')", "true");
+    assert_expression_value("containsIgnoreCase('Free Text Input', 'texts')", "false");
+    assert_expression_value("containsIgnoreCase('Straße', 'STRASSE')", "false");
+    assert_expression_value("['Yes', 'no', 'YES'][equalsIgnoreCase(..., 'yes')]", "['Yes', 'YES']");
+
+    // special values are passed through instead of answering false
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: equalsIgnoreCase(t.s, 'yes')", missing).as_str(), "Missing('s')");
+    assert_eval_value(format!("{}; value: containsIgnoreCase('yes', t.s)", missing).as_str(), "Missing('s')");
+}
+
+#[test]
+fn substring_by_characters() {
+    assert_expression_value("substring('LT-0042-X', 4, 4)", "'0042'");
+    assert_expression_value("substring('LT-0042-X', -1)", "'X'");
+    assert_expression_value("substring('LT-0042-X', 0, 2)", "'LT'");
+    assert_expression_value("substring('LT-0042-X', 8, 10)", "'-X'");
+    assert_expression_value("substring('LT-0042-X', 20)", "''");
+    assert_expression_value("substring('LT-0042-X', 4, -2)", "''");
+    // multi-byte characters are never split
+    assert_expression_value("substring('ąčęėį', 2, 3)", "'čęė'");
+    assert_expression_value("substring('a😀b', 2, 1)", "'😀'");
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: substring(t.s, 1, 2)", missing).as_str(), "Missing('s')");
+}
+
+#[test]
+fn prefix_and_suffix_checks() {
+    assert_expression_value("startsWith('route-eu-1', 'route-')", "true");
+    assert_expression_value("startsWith('route-eu-1', 'eu')", "false");
+    assert_expression_value("endsWith('route-eu-1', '-1')", "true");
+    assert_expression_value("startsWith('abc', '')", "true");
+    assert_expression_value("endsWith('', '')", "true");
+    assert_expression_value("['eu-1', 'us-1', 'eu-2'][startsWith(..., 'eu')]", "['eu-1', 'eu-2']");
+
+    // special values are passed through instead of answering false
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: startsWith(t.s, 'a')", missing).as_str(), "Missing('s')");
+    assert_eval_value(format!("{}; value: endsWith('abc', t.s)", missing).as_str(), "Missing('s')");
+}
+
+#[test]
+fn trimming_and_case_conversion() {
+    assert_expression_value("trim('  a b  ')", "'a b'");
+    assert_expression_value("trimStart('  a b  ')", "'a b  '");
+    assert_expression_value("trimEnd('\t a b \n')", "'\t a b'");
+    assert_expression_value("toUpperCase('straße')", "'STRASSE'");
+    assert_expression_value("toLowerCase('ÄRZTE')", "'ärzte'");
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    for func in ["trim", "trimStart", "trimEnd", "toUpperCase", "toLowerCase"] {
+        assert_eval_value(format!("{}; value: {}(t.s)", missing, func).as_str(), "Missing('s')");
+    }
+
+    // a character stays a character unless the conversion expands it
+    let char_of = |c| ValueEnum::StringValue(StringEnum::Char(c));
+    assert_eq!(eval_to_upper(char_of('a')).unwrap(), char_of('A'));
+    assert_eq!(eval_to_lower(char_of('Ä')).unwrap(), char_of('ä'));
+    assert_eq!(eval_to_upper(char_of('ß')).unwrap(), ValueEnum::StringValue(StringEnum::String("SS".to_string())));
+    assert_eq!(eval_trim(char_of(' ')).unwrap(), ValueEnum::StringValue(StringEnum::String(String::new())));
+}
+
+#[test]
+fn regex_matching_and_extraction() {
+    assert_expression_value("matches('AB-123', '^[A-Z]{2}-[0-9]+$')", "true");
+    assert_expression_value("matches('ab-123', '^[A-Z]{2}-[0-9]+$')", "false");
+    assert_expression_value("['a1', 'b', 'c2'][matches(..., '[0-9]')]", "['a1', 'c2']");
+    assert_expression_value("extract('order 42 of 7', '([a-z]+) ([0-9]+)', 2)", "'42'");
+    assert_expression_value("extract('order 42 of 7', '[0-9]+', 0)", "'42'");
+    assert_expression_value("extract('order', '[0-9]+', 0)", "Missing('N/A')");
+    assert_expression_value("extract('ab', '(a)(b)', 3)", "Missing('N/A')");
+    runtime_error_contains("{ value: matches('x', '(') }", &["unclosed group"]);
+    runtime_error_contains("{ value: extract('x', '[', 0) }", &["unclosed character class"]);
+
+    let missing = "type T: { s: <string> }; t: {} as T";
+    assert_eval_value(format!("{}; value: matches(t.s, 'a')", missing).as_str(), "Missing('s')");
+    assert_eval_value(format!("{}; value: extract(t.s, 'a', 0)", missing).as_str(), "Missing('s')");
+}
+
+#[test]
+fn string_formatting() {
+    assert_expression_value("format('Hello {}, you are {}', 'Ann', 30)", "'Hello Ann, you are 30'");
+    assert_expression_value("format('{{{}}} and {}}}', 'a', true)", "'{a} and true}'");
+    assert_expression_value("format('no placeholders')", "'no placeholders'");
+    runtime_error_contains("{ value: format('{} {}', 1) }", &["format expects 2 arguments", "got 1"]);
+    runtime_error_contains("{ value: format('{}', 1, 2) }", &["format expects 1 arguments", "got 2"]);
+    link_error_contains("value: format()", &["format accepts at least 1 argument, got 0"]);
+}
+
 #[test]
 fn test_string_concatenation_with_plus() {
     assert_expression_value("'a' + 'b'", "'ab'");
@@ -24,3 +24,15 @@ fn test_to_string_for_various_types() {
     assert_expression_value("toString(period('P1Y2M'))", "'P1Y2M'");
     assert_expression_value("toString(period('-P3D'))", "'-P3D'");
 }
+
+#[test]
+fn test_range_prints_inclusive_bounds() {
+    assert_expression_value("1..5", "1..5");
+    assert_expression_value("count(1..5)", "5");
+    assert_expression_value("-2..-2", "-2..-2");
+
+    let printed = eval_value("{ value: 1..5 }");
+    assert_eq!(printed, "1..5");
+    assert_eq!(eval_value(&format!("{{ value: sum({}) }}", printed)), "15");
+    assert_eq!(eval_value(&format!("{{ value: toString({}) }}", printed)), "'1..5'");
+}
@@ -372,6 +372,7 @@ fn user_function_body_is_fully_evaluated() {
         code,
         &[
             "{",
+            "   func testFunction(a, b, c) : {sumAll: sum([a, b, c]); lvl1: {result: sumAll * 2}; lvl2: {result: lvl1.result + 1}}",
             "   all: {",
             "      sumAll: 6",
             "      lvl1: {",
@@ -402,7 +403,15 @@ fn user_function_field_with_math_operator() {
     output1: testFunction(1,2,3).lvl2.result + 1
     "#;
 
-    assert_eval_all(code, &["{", "output1: 14", "}"]);
+    assert_eval_all(
+        code,
+        &[
+            "{",
+            "   func testFunction(a, b, c) : {sumAll: sum([a, b, c]); lvl1: {result: sumAll * 2}; lvl2: {result: lvl1.result + 1}}",
+            "   output1: 14",
+            "}",
+        ],
+    );
 }
 
 #[test]
@@ -417,7 +426,20 @@ fn user_function_has_types() {
     output2: testFunction(1,'x', date('2023-05-03')).label
     "#;
 
-    assert_eval_all(code, &["{", "all: {", "sumAll: 6", "label: '1x'", "}", "output1: 6", "output2: '1x'", "}"]);
+    assert_eval_all(
+        code,
+        &[
+            "{",
+            "   func testFunction(a: number, b: string, c: date) : {sumAll: a + c.month; label: toString(a) + b}",
+            "   all: {",
+            "      sumAll: 6",
+            "      label: '1x'",
+            "   }",
+            "   output1: 6",
+            "   output2: '1x'",
+            "}",
+        ],
+    );
 }
 
 #[test]
@@ -455,7 +477,10 @@ fn user_function_accepts_list_parameter_type() {
     sum: total([1,2,3]).sum
     "#;
 
-    assert_eval_all(code, &["{", "count: 3", "sum: 6", "}"]);
+    assert_eval_all(
+        code,
+        &["{", "func total(values: number[]) : {size: count(values); sum: sum(values)}", "count: 3", "sum: 6", "}"],
+    );
 }
 
 #[test]
@@ -534,7 +559,24 @@ fn user_function_deeper_level_call_is_allowed() {
     }
     "#;
 
-    assert_eval_all(model, &["{", "deeper: {", "value: 2", "}", "value: 2", "}"]);
+    assert_eval_all(model, &["{", "deeper: {", "func inc(x) : {result: x + 1}", "value: 2", "}", "value: 2", "}"]);
+}
+
+#[test]
+fn user_function_called_by_path_sees_its_own_object_fields() {
+    let model = r#"
+    {
+        outer: {
+            deeper: {
+                scale: 100
+                func scaled(x): x * scale
+            }
+        }
+        value: outer.deeper.scaled(2)
+    }
+    "#;
+
+    assert_eval_value(model, "200");
 }
 
 #[test]
@@ -555,7 +597,19 @@ fn user_function_nesting_is_allowed_and_function_context_is_forgotten() {
     }
     "#;
 
-    assert_eval_all(model, &["{", "deeper: {", "value1: 11", "value2: 51", "}", "value: 62", "}"]);
+    assert_eval_all(
+        model,
+        &[
+            "{",
+            "   deeper: {",
+            "      func inc(x) : {helper(y) : {result: y * 10}; result: helper(x).result + 1}",
+            "      value1: 11",
+            "      value2: 51",
+            "   }",
+            "   value: 62",
+            "}",
+        ],
+    );
 }
 
 #[test]
@@ -608,10 +662,7 @@ fn accessing_function_in_lower_context() {
 
     assert_eval_field(&rt, "applicationResponse.newAmount", "3001");
 
-    assert_eq!(
-        rt.get_type("*").unwrap().to_string(),
-        "{applicationResponse: {newAmount: number}}"
-    );
+    assert_eq!(rt.get_type("*").unwrap().to_string(), "{applicationResponse: {newAmount: number}}");
 }
 
 #[test]
@@ -843,3 +894,168 @@ fn inline_function_shadowing_it_alias() {
         "[2, 3]",
     );
 }
+
+#[test]
+fn user_function_default_argument_values() {
+    assert_eval_value(
+        r#"
+        {
+            func greet(name, greeting: string = 'Hello'): { message: greeting + ' ' + name }
+            value: greet('Tom').message
+        }
+        "#,
+        "'Hello Tom'",
+    );
+
+    assert_eval_value(
+        r#"
+        {
+            func greet(name, greeting: string = 'Hello'): { message: greeting + ' ' + name }
+            value: greet('Tom', 'Hi').message
+        }
+        "#,
+        "'Hi Tom'",
+    );
+
+    assert_eval_value(
+        r#"
+        {
+            func shift(x: number, by: number = -1.5): { result: x + by }
+            value: shift(4).result
+        }
+        "#,
+        "2.5",
+    );
+
+    parse_error_contains(
+        "{ func greet(greeting = 'Hello', name): { message: greeting + name } }",
+        &["Parameter `name` without a default value cannot follow parameters with defaults"],
+    );
+}
+
+#[test]
+fn user_function_rest_parameter_collects_arguments() {
+    assert_eval_value(
+        r#"
+        {
+            func total(...xs): { r: sum(xs) }
+            value: total(1, 2, 3).r
+        }
+        "#,
+        "6",
+    );
+
+    assert_eval_value(
+        r#"
+        {
+            func total(base, ...xs: number[]): { r: base + sum(xs) }
+            value: total(10, 1, 2).r
+        }
+        "#,
+        "13",
+    );
+
+    parse_error_contains("{ func total(...xs, y): { r: y } }", &["Rest parameter must be the last function parameter"]);
+}
+
+#[test]
+fn user_function_named_arguments_in_any_order() {
+    assert_eval_value(
+        r#"
+        {
+            func calculateLoanOffer(executionDatetime: date, applicant): {
+                result: applicant.name + ' ' + toString(executionDatetime.year)
+            }
+            value: calculateLoanOffer(applicant: { name: 'Tom' }, executionDatetime: date('2024-01-01')).result
+        }
+        "#,
+        "'Tom 2024'",
+    );
+
+    link_error_contains(
+        "{ func f(a, b): { r: a - b }; value: f(c: 1, a: 5) }",
+        &["Function f has no parameter named `c`"],
+    );
+
+    parse_error_contains(
+        "{ func f(a, b): { r: a - b }; value: f(a: 1, a: 5) }",
+        &["Argument `a` of function `f` is passed more than once"],
+    );
+}
+
+#[test]
+fn user_function_passed_as_argument() {
+    assert_eval_value(
+        r#"
+        {
+            func inc(x): { result: x + 1 }
+            func applyTwice(f, x): { r: f(f(x).result).result }
+            value: applyTwice(inc, 1).r
+        }
+        "#,
+        "3",
+    );
+
+    assert_eval_value(
+        r#"
+        {
+            func inc(x): { result: x + 1 }
+            func double(y): { result: y * 2 }
+            func applyTwice(f, x): { r: f(f(x).result).result }
+            value: [applyTwice(inc, 1).r, applyTwice(double, 1).r, applyTwice(inc, 5).r]
+        }
+        "#,
+        "[3, 4, 7]",
+    );
+
+    // every distinct passed function links its own copy of the called function
+    assert_eval_all(
+        r#"
+        {
+            func apply(f, x): { return: f(x) }
+            func inc(a): { return: a + 1 }
+            func up(s): { return: toUpperCase(s) }
+            r1: apply(inc, 1)
+            r2: apply(up, "a") + "b"
+        }
+        "#,
+        &[
+            "{",
+            "   func apply(f, x) : f(x)",
+            "   func inc(a) : a + 1",
+            "   func up(s) : toUpperCase(s)",
+            "   r1: 2",
+            "   r2: 'Ab'",
+            "}",
+        ],
+    );
+}
+
+#[test]
+fn lambda_applied_over_list() {
+    assert_eval_value(
+        r#"
+        {
+            offset: 10
+            func mapAll(f, xs): for x in xs return f(x)
+            value: mapAll((x) => x * 2 + offset, [1, 2, 3])
+        }
+        "#,
+        "[12, 14, 16]",
+    );
+
+    assert_eval_value(
+        r#"
+        {
+            func combine(f, a, b): f(a, b)
+            value: combine((a, b) => a * b, 3, 4)
+        }
+        "#,
+        "12",
+    );
+
+    link_error_contains(
+        "{ value: (x) => x + 1 }",
+        &["Lambda `(x) => x + 1` can only be passed as a function argument"],
+    );
+}
@@ -2,6 +2,8 @@ mod utilities;
 
 use edge_rules::runtime::decision_service::DecisionService;
 use edge_rules::runtime::edge_rules::EdgeRulesModel;
+use edge_rules::runtime::execution_context::ExecutionContext;
+use edge_rules::typesystem::values::JsonSpecialValues;
 pub use utilities::*;
 
 // Additional tests for user-defined types: limitations and potential problems
@@ -144,6 +146,52 @@ fn loan_offer_decision_service_end_to_end_reduced() {
     );
 }
 
+#[test]
+fn cast_to_alias_serializes_to_json_without_internal_names() {
+    let rt = get_runtime(
+        r#"
+    {
+        type LoanOffer: {eligible: <boolean>; amount: <number>}
+        sample: {eligible: false; note: 'say "hi"'}
+        result: sample as LoanOffer
+        offers: [(sample as LoanOffer), (sample as LoanOffer)]
+        func make(x): { offer: x as LoanOffer }
+        made: make(sample).offer
+    }
+    "#,
+    );
+
+    let json = ExecutionContext::to_json(&rt.context).unwrap();
+    assert!(!json.contains("#child"), "internal names must not be serialized:\n{}", json);
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"sample":{"eligible":false,"note":"say \"hi\""},"#,
+            r#""result":{"eligible":false,"amount":"Missing('amount')"},"#,
+            r#""offers":[{"eligible":false,"amount":"Missing('amount')"},{"eligible":false,"amount":"Missing('amount')"}],"#,
+            r#""made":{"eligible":false,"amount":"Missing('amount')"}}"#
+        )
+    );
+}
+
+#[test]
+fn missing_fields_serialize_to_json_null() {
+    let rt = get_runtime(
+        r#"
+    {
+        type Applicant: {name: <string>; field: <number>}
+        result: {name: 'Ann'} as Applicant
+    }
+    "#,
+    );
+
+    let json = ExecutionContext::to_json_with(&rt.context, JsonSpecialValues::AsNull).unwrap();
+    assert_eq!(json, r#"{"result":{"name":"Ann","field":null}}"#);
+
+    let text = ExecutionContext::to_json(&rt.context).unwrap();
+    assert_eq!(text, r#"{"result":{"name":"Ann","field":"Missing('field')"}}"#);
+}
+
 // Potential limitation to explore further: forward references and alias-based placeholders.
 
 #[test]
@@ -327,7 +375,16 @@ fn input_type_validation() {
     }
     "#;
 
-    assert_eval_all(model, &["{", "value: 2", "}"]);
+    assert_eval_all(
+        model,
+        &[
+            "{",
+            "type LoanOffer: {eligible: <boolean>; amount: <number>; termInMonths: <number>; monthlyPayment: <number>}",
+            "func inc(x: LoanOffer) : {result: x.amount + 1}",
+            "value: 2",
+            "}",
+        ],
+    );
 }
 
 #[test]
@@ -389,7 +446,16 @@ fn complex_type_array_function_argument() {
             value: incAll([{amount: 1}, {amount: 2}])
         }
         "#,
-        &["{", "value: {", "simpleResult: 3", "forResult: [2, 3]", "}", "}"],
+        &[
+            "{",
+            "type LoanOffer: {eligible: <boolean>; amount: <number>; termInMonths: <number>; monthlyPayment: <number>}",
+            "func incAll(offers: LoanOffer[]) : {simpleResult: offers[0].amount + offers[1].amount; forResult: for offer in offers return offer.amount + 1}",
+            "value: {",
+            "simpleResult: 3",
+            "forResult: [2, 3]",
+            "}",
+            "}",
+        ],
     );
 }
 
@@ -413,6 +479,9 @@ fn complex_type_array_function_argument_v2() {
         "#,
         &[
             "{",
+            "type PeopleList: <Person[]>",
+            "type Person: {name: <string>; age: <number>; tags: <string[]>}",
+            "func getAdults(people: PeopleList) : {result: people[age >= 18]}",
             "persons: [{",
             "name: 'Alice'",
             "age: 30",
@@ -456,6 +525,8 @@ fn special_values_are_set_in_function_argument() {
         "#,
         &[
             "{",
+            "type Customer: {valid: <boolean>; name: <string>; birthdate: <date>; birthtime: <time>; birthdatetime: <datetime>; income: <number>}",
+            "func incAll(customer: Customer) : {primaryCustomer: customer}",
             "value: {",
             "primaryCustomer: {",
             "valid: Missing('valid')",
@@ -512,6 +583,9 @@ fn complex_nested_types_in_function_argument() {
         "#,
         &[
             "{",
+            "type Customer: {valid: <boolean>; name: <string>; birthdate: <date>; birthtime: <time>; birthdatetime: <datetime>; income: <number>}",
+            "type LoanOffer: {customer: <Customer>; eligible: <boolean>; amount: <number>; termInMonths: <number>; monthlyPayment: <number>}",
+            "func incAll(offers: LoanOffer[]) : {primaryCustomer: offers[0].customer; simpleResult: offers[0].amount + offers[1].amount; forResult: for offer in offers return offer.amount + 1}",
             "value: {",
             "primaryCustomer: {",
             "valid: Missing('customer.valid')",
@@ -667,9 +741,9 @@ fn date_time_and_duration_roundtrip_to_string() {
 #[test]
 fn type_validation_errors_when_mismatched() {
     // List of booleans for all/any
-    // @Todo: all and any are disabled for now
-    //link_error_contains("value: all([1,2])", &["unexpected", "boolean"]);
-    //link_error_contains("value: any(['x'])", &["unexpected", "boolean"]);
+    link_error_contains("value: all([1,2])", &["unexpected", "boolean"]);
+    link_error_contains("value: any(['x'])", &["unexpected", "boolean"]);
+    assert_eval_value("flags: [true, false]; value: all(flags)", "false");
 
     // Numeric lists for numeric aggregates
     link_error_contains("value: product(['a','b'])", &["unexpected", "number"]);
@@ -950,3 +1024,28 @@ fn cast_string_array_to_temporal_array_via_decision_service() {
     assert_string_contains("firstYear:2026", &rendered);
     assert_string_contains("secondYear:2027", &rendered);
 }
+
+#[test]
+fn empty_object_argument_takes_expected_type() {
+    let model = r#"
+    {
+        type Address: {street: <string>; number: <number>}
+        type Customer: {name: <string>; address: <Address>}
+        func register(customer: Customer): customer
+        expected: <Customer>
+        value: register({})
+        street: register(customer: {}).address.street
+    }
+    "#;
+
+    let mut service = EdgeRulesModel::new();
+    service.append_source(model).unwrap();
+    let runtime = service.to_runtime().unwrap();
+
+    assert_eq!(runtime.get_type("value").unwrap(), runtime.get_type("expected").unwrap());
+    assert_eq!(runtime.get_type("value").unwrap().to_string(), "{name: string; address: Address}");
+    assert_eq!(runtime.get_type("street").unwrap().to_string(), "string");
+
+    assert_eq!(runtime.evaluate_field("street").unwrap().to_string(), "Missing('address.street')");
+    assert_string_contains("name: Missing('name')", runtime.evaluate_field("value").unwrap().to_string());
+}
@@ -11,9 +11,9 @@ use edge_rules::runtime::edge_rules::EvalError;
 use edge_rules::runtime::execution_context::ExecutionContext;
 use edge_rules::test_support::expr;
 use edge_rules::typesystem::types::TypedValue;
-use edge_rules::typesystem::values::ValueEnum;
+use edge_rules::typesystem::values::{ArrayValue, ValueEnum};
 mod utilities;
-use utilities::init_logger;
+use utilities::{get_runtime, init_logger};
 
 type E = ExpressionEnum;
 
@@ -76,3 +76,24 @@ fn test_nesting() -> Result<(), EvalError> {
 
     Ok(())
 }
+
+#[test]
+fn copied_object_lists_do_not_share_evaluated_fields() -> Result<(), EvalError> {
+    let rt = get_runtime(
+        "source: [{amount: 10}, {amount: 20}]; copy: deepClone(source); appended: append(source, {amount: 30}); \
+         concatenated: concatenate(source, [{amount: 30}])",
+    );
+
+    for field in ["copy", "appended", "concatenated"] {
+        let ValueEnum::Array(ArrayValue::ObjectsArray { values, .. }) = rt.evaluate_field(field)? else {
+            panic!("{} must be an object list", field);
+        };
+        assert_eq!(values.len(), if field == "copy" { 2 } else { 3 });
+        values[0].borrow().stack_insert("amount", Ok(ValueEnum::from(99.0)));
+        assert_eq!(values[0].borrow().get("amount")?.to_string(), "99");
+    }
+
+    assert_eq!(rt.evaluate_expression_str("source[0].amount")?.to_string(), "10");
+    assert_eq!(rt.evaluate_expression_str("deepClone(source[1]).amount")?.to_string(), "20");
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use edge_rules::runtime::edge_rules::EdgeRulesModel;
 use edge_rules::tokenizer::parser::tokenize;
 use std::fmt::Display;
 
@@ -40,11 +41,11 @@ fn test_common() {
     init_test("test_common");
 
     is_equals("value : sum(1,sum(7,8),3)", "value : sum(1,sum(7,8),3)");
-    is_equals("value : 1 + 7 - 8 / 3 * 10", "value : 1+(7-8/3*10)");
+    is_equals("value : 1 + 7 - 8 / 3 * 10", "value : 1+7-8/3*10");
     is_equals("value : 1 + -2", "value : 1 + -2");
     is_equals("value : -1 + 2", "value : -1 + 2");
     is_equals("value : - (-2*10)", "value:-(-2*10)");
-    is_equals("value : (1 + 7 * (5 / 6 + (2-1))-1) - 8 / 3 * 10", "value:(1+(7*(5/6+(2-1))-1))-8/3*10");
+    is_equals("value : (1 + 7 * (5 / 6 + (2-1))-1) - 8 / 3 * 10", "value:(1+7*(5/6+2-1)-1)-8/3*10");
     is_equals("{ record : { age : 18; value : 1 + 2 }}", "{record:{age:18;value:1+2}}");
     is_equals("{ r : { a : 1 + 2} b : 3}", "{r:{a:1+2};b:3}");
     is_equals("{ r : { a : 1 + 2}; b : 3}", "{r:{a:1+2};b:3}"); // testing comma separator that should be OK
@@ -66,7 +67,7 @@ fn test_common() {
     is_equals("func myFunc(x,y,z) : {a : 1}", "myFunc(x,y,z):{a:1}");
     is_equals(
         "result : sales[month] + sales[month + 1] + sales[month + 2]",
-        "result:(sales[month]+sales[(month+1)])+sales[(month+2)]",
+        "result:sales[month]+sales[month+1]+sales[month+2]",
     );
 
     // constraints
@@ -107,7 +108,7 @@ fn test_common() {
     is_equals("value : [1,2,3][>1]", "value:[1,2,3][...>1]");
     is_equals("value : [1,2,3][...>1]", "value:[1,2,3][...>1]");
     is_equals("value : [1,2,3][...>=2 and ...<=3]", "value:[1,2,3][...>=2 and ...<=3]");
-    is_equals("value : [1,2,3][position-1]", "value:[1,2,3][(position-1)]");
+    is_equals("value : [1,2,3][position-1]", "value:[1,2,3][position-1]");
     is_equals("value : application.applicant[0]", "value:application.applicant[0]");
     is_equals("value : application.applicant[0].age", "value:application.applicant[0].age");
     is_equals("value : application.applicant[<=1].age", "value:application.applicant[...<=1].age");
@@ -200,8 +201,8 @@ fn test_range() {
 
     is_equals("p : 1..5", "p : 1..5");
     is_equals("p : for number in 1..5 return number * 2", "p : for number in 1..5 return number * 2");
-    is_equals("p : for number in 1..(5+inc) return number * 3", "p : for number in 1..(5+inc) return number * 3");
-    is_equals("p : for number in 1 * 0 .. 5+inc return number * 3", "p : for number in 1*0..(5+inc) return number * 3");
+    is_equals("p : for number in 1..(5+inc) return number * 3", "p : for number in 1..5+inc return number * 3");
+    is_equals("p : for number in 1 * 0 .. 5+inc return number * 3", "p : for number in 1*0..5+inc return number * 3");
 }
 
 #[test]
@@ -210,7 +211,7 @@ fn test_functions() {
 
     is_equals("p : sum(2,2 * sum(1,1))", "p : sum(2,2 * sum(1,1))");
     is_equals("p : sum(2 * sum(3,3),2 * sum(1,1))", "p : sum(2 * sum(3,3),2 * sum(1,1))");
-    is_equals("value : sum(1,2,3 + sum(2,2 * sum(0,0,0,0))) + (2 * 2)", "value:sum(1,2,(3+sum(2,2*sum(0,0,0,0))))+2*2");
+    is_equals("value : sum(1,2,3 + sum(2,2 * sum(0,0,0,0))) + (2 * 2)", "value:sum(1,2,3+sum(2,2*sum(0,0,0,0)))+2*2");
     is_equals("value : [1,1*sum(9,8^3)^2,3]", "value:[1,1*sum(9,8^3)^2,3]");
 }
 
@@ -225,7 +226,7 @@ fn test_conditionals() {
     is_equals("p : 2 >= a", "p : 2 >= a");
     is_equals("p : 3 <= a", "p : 3 <= a");
     is_equals("p : 4 <> a", "p : 4 <> a");
-    is_equals("p : 4 <> a + 1", "p : 4 <> (a + 1)");
+    is_equals("p : 4 <> a + 1", "p : 4 <> a + 1");
 
     is_equals("p : a and b", "p : a and b");
     is_equals("p : a or b", "p : a or b");
@@ -240,3 +241,102 @@ fn tokenizer_symbol_regressions() {
     is_equals("value : record.field", "value : record.field");
     is_equals("value : time('12:00:00')", "value : time('12:00:00')");
 }
+
+/// Printed expression must parse back into the same tree
+fn assert_round_trip(code: &str) {
+    let parsed = EdgeRulesModel::parse_expression(code).unwrap_or_else(|errors| panic!("`{}`: {}", code, errors));
+    let printed = parsed.to_string();
+    let reparsed = EdgeRulesModel::parse_expression(&printed)
+        .unwrap_or_else(|errors| panic!("`{}` printed as `{}`: {}", code, printed, errors));
+
+    assert_eq!(format!("{:?}", parsed), format!("{:?}", reparsed), "`{}` printed as `{}`", code, printed);
+}
+
+/// Object fields are not ordered in debug output, so printed code is compared instead
+fn assert_object_round_trip(code: &str) {
+    let printed = EdgeRulesModel::parse_expression(code).expect("object").to_string();
+    let reprinted = EdgeRulesModel::parse_expression(&printed).expect("printed object").to_string();
+
+    assert_eq!(printed, reprinted, "`{}`", code);
+}
+
+#[test]
+fn display_round_trips_for_every_expression_variant() {
+    init_test("display_round_trips_for_every_expression_variant");
+
+    // values, variables and ranges
+    assert_round_trip("1");
+    assert_round_trip("-1.5");
+    assert_round_trip("'text'");
+    assert_round_trip("true");
+    assert_round_trip("date('2024-01-01')");
+    assert_round_trip("a.b.c");
+    assert_round_trip("1..5");
+    assert_round_trip("a..b + 1");
+
+    // math operators
+    assert_round_trip("1 + 2 * 3");
+    assert_round_trip("(1 + 2) * 3");
+    assert_round_trip("10 / (2 * 5)");
+    assert_round_trip("a / (b / c)");
+    assert_round_trip("a - (b - c)");
+    assert_round_trip("2 ^ (3 ^ 2)");
+    assert_round_trip("(a * b) ^ 2");
+    assert_round_trip("-(a + b)");
+    assert_round_trip("a - b + c");
+    assert_round_trip("a + b - c");
+    assert_round_trip("(a + b) - c");
+    assert_round_trip("a + (b + c)");
+    assert_round_trip("a % (b * c)");
+
+    // logical operators and comparators
+    assert_round_trip("not a");
+    assert_round_trip("not (a and b)");
+    assert_round_trip("not (x > 1)");
+    assert_round_trip("(a or b) and c");
+    assert_round_trip("a or b and c");
+    assert_round_trip("(a xor b) or c");
+    assert_round_trip("a = (b > 1)");
+    assert_round_trip("a + 1 >= b * 2");
+
+    // function calls
+    assert_round_trip("sum(1, 2, 3)");
+    assert_round_trip("max([1, 2]) + 1");
+    assert_round_trip("calc(1, x + 1)");
+    assert_round_trip("if a > 1 then 'big' else 'small'");
+    assert_round_trip("for x in [1, 2] return x * 2");
+    assert_round_trip("x as number");
+    assert_round_trip("map([1, 2], it * 2)");
+    assert_round_trip("map([1, 2], (x) => x + 1)");
+    assert_round_trip("filter([1, 2], it > 1)");
+
+    // collections, filters, selections and objects
+    assert_round_trip("[1, 'a', [true]]");
+    assert_round_trip("[]");
+    assert_round_trip("items[...> 1]");
+    assert_round_trip("items[0]");
+    assert_round_trip("items[... = 'a'].name");
+    assert_object_round_trip("{a: 1; b: a + 1}");
+    assert_object_round_trip("{a: {b: not (x and y)}}");
+}
+
+#[test]
+fn subtraction_keeps_right_operand_grouping() {
+    let printed = EdgeRulesModel::parse_expression("a - (b - c)").unwrap().to_string();
+
+    assert_eq!(printed, "a - (b - c)");
+    assert_round_trip("a - (b - c)");
+    assert_eq!(EdgeRulesModel::parse_expression("a - b - c").unwrap().to_string(), "a - b - c");
+}
+
+#[test]
+fn logical_operators_keep_grouping() {
+    let printed = |code: &str| EdgeRulesModel::parse_expression(code).unwrap().to_string();
+
+    assert_eq!(printed("a and b or c"), "a and b or c");
+    assert_eq!(printed("a and (b or c)"), "a and (b or c)");
+    assert_eq!(printed("(a = b) or c > 1"), "a = b or c > 1");
+    assert_round_trip("a and b or c");
+    assert_round_trip("a and (b or c)");
+    assert_round_trip("a xor (b or c) and not d");
+}
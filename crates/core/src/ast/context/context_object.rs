@@ -2,9 +2,11 @@ use crate::ast::context::context_object_type::{EObjectContent, FormalParameter};
 use crate::ast::context::context_resolver::resolve_context_path;
 use crate::ast::context::duplicate_name_error::{DuplicateNameError, NameKind};
 use crate::ast::context::metadata::Metadata;
+use crate::ast::expression::StaticLink;
 use crate::ast::metaphors::functions::UserFunctionDefinition;
 use crate::ast::token::ExpressionEnum;
-use crate::ast::token::{ComplexTypeRef, UserTypeBody};
+use crate::ast::token::{ComplexTypeRef, ImportDefinition, UserTypeBody};
+use crate::ast::variable::VariableLink;
 use crate::ast::Link;
 use crate::link::linker;
 use crate::link::node_data::{ContentHolder, Node, NodeData, NodeDataEnum};
@@ -21,11 +23,13 @@ use std::rc::Rc;
 pub struct ExpressionEntry {
     pub expression: ExpressionEnum,
     pub field_type: Link<ValueType>,
+    /// `field?: value` is dropped from the output when the value is omitted
+    pub optional: bool,
 }
 
 impl From<ExpressionEnum> for ExpressionEntry {
     fn from(expression: ExpressionEnum) -> Self {
-        ExpressionEntry { expression, field_type: LinkingError::not_linked().into() }
+        ExpressionEntry { expression, field_type: LinkingError::not_linked().into(), optional: false }
     }
 }
 
@@ -35,15 +39,24 @@ impl From<ExpressionEntry> for Rc<RefCell<ExpressionEntry>> {
     }
 }
 
+/// Functions passed to each parameter, `None` for value parameters, and the copy linked with them
+pub type BoundCopy = (Vec<Option<Rc<RefCell<MethodEntry>>>>, Rc<RefCell<MethodEntry>>);
+
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 pub struct MethodEntry {
     pub function_definition: UserFunctionDefinition,
     pub field_type: Link<ValueType>,
+    /// Copies of this function linked with the functions passed to its parameters, keyed by the passed functions
+    pub bound_copies: Vec<BoundCopy>,
 }
 
 impl From<UserFunctionDefinition> for MethodEntry {
     fn from(value: UserFunctionDefinition) -> Self {
-        MethodEntry { function_definition: value, field_type: LinkingError::not_linked().into() }
+        MethodEntry {
+            function_definition: value,
+            field_type: LinkingError::not_linked().into(),
+            bound_copies: Vec::new(),
+        }
     }
 }
 
@@ -63,6 +76,8 @@ pub struct ContextObject {
     pub expressions: HashMap<&'static str, Rc<RefCell<ExpressionEntry>>>,
     /// metaphors are reference counted because they are linked to UserFunctionCall
     pub metaphors: HashMap<&'static str, Rc<RefCell<MethodEntry>>>,
+    /// first user function passed to each parameter, the body is linked with it, e.g. `f` in `applyTwice(inc, 1)`
+    pub bound_functions: HashMap<&'static str, Rc<RefCell<MethodEntry>>>,
     /// node.childs, expressions and metaphors have names
     pub all_field_names: Vec<&'static str>,
     pub field_name_set: HashSet<&'static str>,
@@ -79,6 +94,12 @@ pub struct ContextObject {
     pub metadata: Option<Metadata>,
 
     pub allow_it: bool,
+
+    /// objects spread into this object with `...source`, expanded into fields when linking
+    pub spreads: Vec<VariableLink>,
+
+    /// `import "path"` directives, resolved by `EdgeRulesModel` before the object is merged into the model
+    pub imports: Vec<ImportDefinition>,
 }
 
 impl Node<ContextObject> for ContextObject {
@@ -121,6 +142,14 @@ impl ContentHolder<ContextObject> for ContextObject {
     fn get_field_names(&self) -> Vec<&'static str> {
         self.get_field_names()
     }
+
+    fn get_leading_lines(&self) -> Vec<String> {
+        self.spreads.iter().map(|source| format!("...{}", source)).collect()
+    }
+
+    fn is_optional_field(&self, name: &str) -> bool {
+        self.expressions.get(name).is_some_and(|entry| entry.borrow().optional)
+    }
 }
 
 // @Todo: must evaluate types as well
@@ -169,7 +198,7 @@ impl ContextObject {
     }
 
     pub fn get_function(&self, name: &str) -> Option<Rc<RefCell<MethodEntry>>> {
-        Some(Rc::clone(self.metaphors.get(name)?))
+        Some(Rc::clone(self.metaphors.get(name).or_else(|| self.bound_functions.get(name))?))
     }
 
     pub fn get_user_type(&self, name: &str) -> Option<UserTypeBody> {
@@ -292,6 +321,50 @@ impl ContextObject {
         }
     }
 
+    /// Expands `...source` spreads into fields referencing the source, e.g. `name: base.name`.
+    /// Sources are resolved from `scope`. Explicit fields take precedence and a later spread overrides an earlier one.
+    pub fn expand_spreads(object: &Rc<RefCell<ContextObject>>, scope: &Rc<RefCell<ContextObject>>) -> Link<()> {
+        let spreads = std::mem::take(&mut object.borrow_mut().spreads);
+        let mut spread_fields: Vec<(&'static str, VariableLink)> = Vec::new();
+
+        for mut source in spreads {
+            let source_type = source.link(Rc::clone(scope))?;
+            let ValueType::ObjectType(source_object) = &source_type else {
+                return LinkingError::other_error(format!(
+                    "Cannot spread `{}` of type '{}', only objects can be spread",
+                    source, source_type
+                ))
+                .into();
+            };
+
+            let source_object = source_object.borrow();
+            for name in source_object.get_field_names() {
+                if source_object.metaphors.contains_key(name) {
+                    continue;
+                }
+                let mut path = source.path.clone();
+                path.push(name);
+                let link = VariableLink::new_interned_path(path);
+                match spread_fields.iter_mut().find(|(field, _)| *field == name) {
+                    Some(existing) => existing.1 = link,
+                    None => spread_fields.push((name, link)),
+                }
+            }
+        }
+
+        let mut object = object.borrow_mut();
+        spread_fields.retain(|(name, _)| !object.field_name_set.contains(name));
+
+        let names: Vec<&'static str> = spread_fields.iter().map(|(name, _)| *name).collect();
+        object.all_field_names.splice(0..0, names);
+        for (name, link) in spread_fields {
+            object.field_name_set.insert(name);
+            object.expressions.insert(name, ExpressionEntry::from(ExpressionEnum::Variable(link)).into());
+        }
+
+        Ok(())
+    }
+
     pub fn add_user_function(
         parent: &Rc<RefCell<ContextObject>>,
         definition: UserFunctionDefinition,
@@ -457,4 +530,3 @@ impl TypedValue for ContextObject {
         ValueType::ObjectType(Rc::new(RefCell::new(self.clone())))
     }
 }
-
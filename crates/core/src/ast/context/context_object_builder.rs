@@ -5,7 +5,8 @@ use crate::ast::context::duplicate_name_error::{DuplicateNameError, NameKind};
 use crate::ast::context::metadata::Metadata;
 use crate::ast::metaphors::functions::UserFunctionDefinition;
 use crate::ast::token::DefinitionEnum::{InlineUserFunction, UserFunction as UserFunctionDef};
-use crate::ast::token::{DefinitionEnum, ExpressionEnum, UserTypeBody};
+use crate::ast::token::{DefinitionEnum, ExpressionEnum, ImportDefinition, UserTypeBody};
+use crate::ast::variable::VariableLink;
 use crate::link::node_data::{Node, NodeData, NodeDataEnum};
 use crate::typesystem::types::ValueType;
 use crate::utils::intern_field_name;
@@ -13,6 +14,16 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+/// Conflict policy of `ContextObjectBuilder::merge` when both builders define the same name.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Any duplicate field, function or type name fails the merge and nothing is merged
+    ErrorOnDuplicate,
+    /// Entries of the merged builder replace entries with the same name
+    Override,
+}
+
 /// ---
 /// **ContextObjectBuilder**
 /// - Builds Execution Context Object and gets dismissed after building.
@@ -28,6 +39,8 @@ pub struct ContextObjectBuilder {
     defined_types: HashMap<String, UserTypeBody>,
     metadata: Option<Metadata>,
     allow_it: bool,
+    spreads: Vec<VariableLink>,
+    imports: Vec<ImportDefinition>,
 }
 
 impl Default for ContextObjectBuilder {
@@ -50,6 +63,8 @@ impl ContextObjectBuilder {
             defined_types: HashMap::new(),
             metadata: None,
             allow_it: false,
+            spreads: Vec::new(),
+            imports: Vec::new(),
         }
     }
 
@@ -66,6 +81,8 @@ impl ContextObjectBuilder {
             defined_types: HashMap::new(),
             metadata: None,
             allow_it: false,
+            spreads: Vec::new(),
+            imports: Vec::new(),
         }
     }
 
@@ -107,11 +124,32 @@ impl ContextObjectBuilder {
         Ok(self)
     }
 
+    /// Adds `field?: value`, the field is dropped from the output when its value is omitted
+    pub fn add_optional_expression(
+        &mut self,
+        field_name: &str,
+        field: ExpressionEnum,
+    ) -> Result<&mut Self, DuplicateNameError> {
+        self.add_expression(field_name, field)?;
+
+        if let Some(entry) = self.fields.get(field_name) {
+            entry.borrow_mut().optional = true;
+        }
+
+        Ok(self)
+    }
+
     pub fn set_expression(&mut self, field_name: &str, expression: ExpressionEnum) -> Result<(), DuplicateNameError> {
         self.remove_field(field_name);
         self.add_expression(field_name, expression).map(|_| ())
     }
 
+    /// Adds `...source` spread, source fields are resolved when linking
+    pub fn add_spread(&mut self, source: VariableLink) -> &mut Self {
+        self.spreads.push(source);
+        self
+    }
+
     pub fn add_definition(&mut self, field: DefinitionEnum) -> Result<&mut Self, DuplicateNameError> {
         match field {
             UserFunctionDef(m) => {
@@ -133,6 +171,9 @@ impl ContextObjectBuilder {
             DefinitionEnum::UserType(t) => {
                 self.insert_type_definition(t.name, t.body)?;
             }
+            DefinitionEnum::Import(import) => {
+                self.imports.push(import);
+            }
         }
         Ok(self)
     }
@@ -179,6 +220,8 @@ impl ContextObjectBuilder {
             self.insert_type_definition(key.clone(), value.clone())?;
         }
 
+        self.spreads.extend(borrowed.spreads.iter().cloned());
+
         if self.metadata.is_none() {
             self.metadata = borrowed.metadata.clone();
         }
@@ -186,6 +229,61 @@ impl ContextObjectBuilder {
         Ok(self)
     }
 
+    /// Combines another builder's fields, functions and types into this one, e.g. a base rule set with overrides.
+    /// - Conflicts are resolved by `policy`; overridden fields keep their original position.
+    pub fn merge(&mut self, other: ContextObjectBuilder, policy: MergePolicy) -> Result<&mut Self, DuplicateNameError> {
+        if policy == MergePolicy::ErrorOnDuplicate {
+            for &name in &other.field_names {
+                let kind = if other.metaphors.contains_key(name) { NameKind::Function } else { NameKind::Field };
+                self.ensure_name_unique(name, kind)?;
+            }
+
+            if let Some(name) = other.defined_types.keys().find(|name| self.defined_types.contains_key(*name)) {
+                return Err(DuplicateNameError::new(NameKind::UserType, name.as_str()));
+            }
+        }
+
+        let ContextObjectBuilder {
+            mut fields,
+            mut metaphors,
+            mut childs,
+            field_names,
+            defined_types,
+            metadata,
+            spreads,
+            imports,
+            ..
+        } = other;
+
+        for name in field_names {
+            if self.field_name_set.insert(name) {
+                self.field_names.push(name);
+            } else {
+                self.fields.remove(name);
+                self.metaphors.remove(name);
+                self.childs.remove(name);
+            }
+
+            if let Some(field) = fields.remove(name) {
+                self.fields.insert(name, field);
+            } else if let Some(child) = childs.remove(name) {
+                self.childs.insert(name, child);
+            } else if let Some(method) = metaphors.remove(name) {
+                self.metaphors.insert(name, method);
+            }
+        }
+
+        self.defined_types.extend(defined_types);
+        self.spreads.extend(spreads);
+        self.imports.extend(imports);
+
+        if self.metadata.is_none() {
+            self.metadata = metadata;
+        }
+
+        Ok(self)
+    }
+
     pub fn merge_context_object(&mut self, object: Rc<RefCell<ContextObject>>) -> Result<(), DuplicateNameError> {
         self.append(object).map(|_| ())
     }
@@ -335,6 +433,7 @@ impl ContextObjectBuilder {
         let obj = ContextObject {
             expressions: self.fields,
             metaphors: self.metaphors,
+            bound_functions: HashMap::new(),
             all_field_names: self.field_names,
             field_name_set: self.field_name_set,
             node: NodeData::new_fixed(self.childs, self.node_type),
@@ -343,6 +442,8 @@ impl ContextObjectBuilder {
             defined_types: self.defined_types,
             metadata: self.metadata,
             allow_it: self.allow_it,
+            spreads: self.spreads,
+            imports: self.imports,
         };
 
         let ctx = Rc::new(RefCell::new(obj));
@@ -20,11 +20,25 @@ use std::rc::Rc;
 pub struct FormalParameter {
     pub name: String,
     pub parameter_type: ComplexTypeRef,
+    /// Value used when the caller omits this trailing argument
+    pub default_value: Option<ValueEnum>,
+    /// Rest parameter `...xs` that collects all remaining arguments into a list
+    pub variadic: bool,
 }
 
 impl FormalParameter {
     pub fn with_type_ref(name: String, parameter_type: ComplexTypeRef) -> FormalParameter {
-        FormalParameter { name, parameter_type }
+        FormalParameter { name, parameter_type, default_value: None, variadic: false }
+    }
+
+    pub fn with_default_value(mut self, default_value: ValueEnum) -> FormalParameter {
+        self.default_value = Some(default_value);
+        self
+    }
+
+    pub fn as_variadic(mut self) -> FormalParameter {
+        self.variadic = true;
+        self
     }
 
     pub fn declared_type(&self) -> Option<&ComplexTypeRef> {
@@ -36,7 +50,12 @@ impl FormalParameter {
     }
 
     pub fn with_runtime_type(&self, value_type: ValueType) -> FormalParameter {
-        FormalParameter { name: self.name.clone(), parameter_type: ComplexTypeRef::from_value_type(value_type) }
+        FormalParameter {
+            name: self.name.clone(),
+            parameter_type: ComplexTypeRef::from_value_type(value_type),
+            default_value: self.default_value.clone(),
+            variadic: self.variadic,
+        }
     }
 
     pub fn runtime_value_type(&self) -> Option<ValueType> {
@@ -49,11 +68,21 @@ impl FormalParameter {
 
 impl Display for FormalParameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.variadic {
+            f.write_str("...")?;
+        }
+
         if self.parameter_type.is_undefined() {
-            write!(f, "{}", self.name)
+            write!(f, "{}", self.name)?;
         } else {
-            write!(f, "{}: {}", self.name, self.parameter_type)
+            write!(f, "{}: {}", self.name, self.parameter_type)?;
         }
+
+        if let Some(default_value) = &self.default_value {
+            write!(f, " = {}", default_value)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -139,6 +139,9 @@ impl FunctionContext {
                 cursor = curr.borrow().node.node_type.get_parent();
             }
 
+            // function of a child object called by its path, e.g. `deeper.f(x)` or `money.round2(x)`
+            let found_exec = found_exec.or_else(|| find_child_execution_context(&parent_exec, &parent_static));
+
             if let Some(parent) = found_exec {
                 ExecutionContext::create_temp_child_context(parent, Rc::clone(&self.body))
             } else {
@@ -148,6 +151,10 @@ impl FunctionContext {
             ExecutionContext::create_isolated_context(Rc::clone(&self.body))
         };
 
+        // an isolated function body still compares reals and reads the clock like its caller
+        ctx.borrow_mut().real_tolerance = parent_exec.borrow().real_tolerance.clone();
+        ctx.borrow_mut().clock = Rc::clone(&parent_exec.borrow().clock);
+
         input.into_iter().zip(self.parameters.iter()).for_each(|(value, arg)| {
             trace!("function {}(...) {} = {:?}", ctx.borrow().node().node_type, arg.name, &value);
             if arg.name == "it" {
@@ -162,6 +169,46 @@ impl FunctionContext {
     }
 }
 
+/// Finds the execution context of a child object `target` below the caller context or one of its parents
+fn find_child_execution_context(
+    caller: &Rc<RefCell<ExecutionContext>>,
+    target: &Rc<RefCell<ContextObject>>,
+) -> Option<Rc<RefCell<ExecutionContext>>> {
+    // static parents of the target with child names leading from each of them down to the target
+    let mut chain: Vec<(Rc<RefCell<ContextObject>>, Vec<&'static str>)> = Vec::new();
+    let mut names = Vec::new();
+    let mut static_cursor = Some(Rc::clone(target));
+    while let Some(current) = static_cursor {
+        let NodeDataEnum::Child(name, parent) = &current.borrow().node.node_type else {
+            break;
+        };
+        names.insert(0, *name);
+        static_cursor = parent.upgrade();
+        if let Some(parent) = &static_cursor {
+            chain.push((Rc::clone(parent), names.clone()));
+        }
+    }
+
+    let mut cursor = Some(Rc::clone(caller));
+    while let Some(current) = cursor {
+        let object = Rc::clone(&current.borrow().object);
+        if let Some((_, path)) = chain.iter().find(|(parent, _)| Rc::ptr_eq(parent, &object)) {
+            let mut exec = current;
+            for name in path {
+                let next = match exec.borrow().get(name) {
+                    Ok(ObjectRef(child)) => child,
+                    _ => return None,
+                };
+                exec = next;
+            }
+            return Some(exec);
+        }
+        cursor = current.borrow().node.node_type.get_parent();
+    }
+
+    None
+}
+
 // impl InlineFunctionContext {
 //     pub fn create_for(expression: Rc<RefCell<ExpressionEnum>>, parameters: Vec<FormalParameter>, parent: Rc<RefCell<ContextObject>>) -> Self {
 //         let parent_name = parent.borrow().node().get_assigned_to_field();
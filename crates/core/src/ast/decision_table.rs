@@ -0,0 +1,115 @@
+use crate::ast::context::context_object::ContextObject;
+use crate::ast::expression::{EvaluatableExpression, StaticLink};
+use crate::ast::ifthenelse::IfThenElseFunction;
+use crate::ast::operators::logical_operators::{LogicalOperator, LogicalOperatorEnum};
+use crate::ast::token::ExpressionEnum;
+use crate::ast::token::ExpressionEnum::{Collection, FunctionCall, Operator};
+use crate::ast::Link;
+use crate::runtime::execution_context::*;
+use crate::typesystem::errors::ParseErrorEnum::WrongFormat;
+use crate::typesystem::errors::{ParseErrorEnum, RuntimeError};
+use crate::typesystem::types::ValueType;
+use crate::typesystem::values::ValueEnum;
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// Rows are evaluated top to bottom and the output of the first row whose conditions all hold is returned:
+///
+/// ```text
+/// decisionTable(
+///     [score >= 90, 'A'],
+///     [score >= 50, score < 90, 'B'],
+///     ['C']
+/// )
+/// ```
+///
+/// Every row is a list of conditions followed by the output. A row without conditions is the default and must be
+/// the last one. Without a default, a table that matches no row behaves like `if ... then ...` without `else`.
+/// Internally the table is a chain of `if ... then ... else ...` expressions.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct DecisionTable {
+    chain: ExpressionEnum,
+    rows: usize,
+    has_default: bool,
+}
+
+impl DecisionTable {
+    pub fn build(arguments: Vec<ExpressionEnum>) -> Result<Self, ParseErrorEnum> {
+        if arguments.is_empty() {
+            return Err(WrongFormat("decisionTable expects at least one row".to_string()));
+        }
+
+        let mut rows = Vec::with_capacity(arguments.len());
+        let mut default = None;
+        for argument in arguments {
+            if default.is_some() {
+                return Err(WrongFormat("decisionTable default row must be the last row".to_string()));
+            }
+            let mut cells = match argument {
+                Collection(row) if !row.elements.is_empty() => row.elements,
+                other => {
+                    return Err(WrongFormat(format!(
+                        "decisionTable row must be a list of conditions followed by an output, got {}",
+                        other
+                    )))
+                }
+            };
+            let output = cells.pop().unwrap();
+            let mut conditions = cells.into_iter();
+            match conditions.next() {
+                None => default = Some(output),
+                Some(first) => {
+                    let condition = conditions.try_fold(first, |all, next| {
+                        LogicalOperator::build(LogicalOperatorEnum::And, all, next)
+                            .map(|operator| Operator(Box::new(operator)))
+                    })?;
+                    rows.push((condition, output));
+                }
+            }
+        }
+
+        let row_count = rows.len();
+        let has_default = default.is_some();
+        let mut chain = default;
+        for (condition, output) in rows.into_iter().rev() {
+            let row = IfThenElseFunction::build(condition, output, chain)?;
+            chain = Some(FunctionCall(Box::new(row)));
+        }
+
+        // at least one row is present, so the chain is never empty
+        Ok(DecisionTable { chain: chain.unwrap(), rows: row_count, has_default })
+    }
+}
+
+impl Display for DecisionTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut rows = Vec::with_capacity(self.rows + 1);
+        let mut current = Some(&self.chain);
+        for _ in 0..self.rows {
+            let Some(FunctionCall(call)) = current else { break };
+            let Some(row) = (call.as_ref() as &dyn Any).downcast_ref::<IfThenElseFunction>() else { break };
+            rows.push(format!("[{}, {}]", row.condition, row.then_expression));
+            current = row.else_expression.as_ref();
+        }
+        if let Some(default) = current.filter(|_| self.has_default) {
+            rows.push(format!("[{}]", default));
+        }
+
+        write!(f, "decisionTable({})", rows.join(", "))
+    }
+}
+
+impl StaticLink for DecisionTable {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        self.chain.link(ctx)
+    }
+}
+
+impl EvaluatableExpression for DecisionTable {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        self.chain.eval(context)
+    }
+}
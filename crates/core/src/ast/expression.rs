@@ -4,6 +4,7 @@ use crate::ast::context::context_object_type::EObjectContent;
 use crate::ast::functions::function_date::{
     parse_date_iso, parse_datetime_flexible, parse_duration_iso8601, parse_period_iso8601, parse_time_local,
 };
+use crate::ast::lambda::LambdaFunction;
 use crate::ast::token::ExpressionEnum::*;
 use crate::ast::token::*;
 use crate::ast::user_function_call::UserFunctionCall;
@@ -443,6 +444,27 @@ impl ExpressionEnum {
             _ => None,
         }
     }
+
+    pub fn as_lambda(&self) -> Option<&LambdaFunction> {
+        match self {
+            FunctionCall(function) => {
+                let any_ref = function.as_ref() as &dyn Any;
+                any_ref.downcast_ref::<LambdaFunction>()
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes ownership of the lambda, giving the expression back when it is not a lambda
+    pub fn into_lambda(self) -> Result<LambdaFunction, ExpressionEnum> {
+        match self {
+            FunctionCall(function) if (function.as_ref() as &dyn Any).is::<LambdaFunction>() => {
+                let any_box: Box<dyn Any> = function;
+                Ok(*any_box.downcast::<LambdaFunction>().expect("checked to be a lambda"))
+            }
+            other => Err(other),
+        }
+    }
 }
 
 impl PartialEq for ExpressionEnum {
@@ -12,7 +12,7 @@ use crate::link::node_data::{NodeData, NodeDataEnum};
 use crate::runtime::execution_context::*;
 use crate::tokenizer::utils::Either;
 use crate::typesystem::errors::{LinkingError, ParseErrorEnum, RuntimeError, RuntimeErrorEnum};
-use crate::typesystem::types::{Integer, ValueType};
+use crate::typesystem::types::{Integer, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum::{Array, RangeValue};
 use crate::typesystem::values::{ArrayValue, ValueEnum};
 use crate::utils::context_unwrap;
@@ -21,6 +21,9 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::ops::Range;
 use std::rc::Rc;
+/// Loop variable that is also accessible as `it`, e.g. in `map(list, it * 2)`
+const CONTEXT_VARIABLE: &str = "it";
+
 fn flatten_list_type_for_for(value_type: ValueType) -> ValueType {
     match value_type {
         ValueType::ListType(Some(inner)) => flatten_list_type_for_for(*inner),
@@ -78,11 +81,19 @@ impl ForFunction {
         parent: &Rc<RefCell<ExecutionContext>>,
         value: ExpressionEnum,
     ) -> Result<Rc<RefCell<ExecutionContext>>, RuntimeError> {
+        let context_variable = match &value {
+            Value(loop_value) if self.in_loop_variable == CONTEXT_VARIABLE => Some(loop_value.clone()),
+            _ => None,
+        };
+
         let mut obj = ContextObjectBuilder::new();
         obj.add_expression(self.in_loop_variable.as_str(), value)
             .map_err(|err| RuntimeError::eval_error(err.to_string()))?;
 
-        Ok(ExecutionContext::create_temp_child_context(Rc::clone(parent), obj.build()))
+        let ctx = ExecutionContext::create_temp_child_context(Rc::clone(parent), obj.build());
+        ctx.borrow_mut().context_variable = context_variable;
+
+        Ok(ctx)
     }
 
     fn iterate_values(
@@ -228,6 +239,10 @@ impl StaticLink for ForFunction {
                 }
             };
 
+            if self.in_loop_variable == CONTEXT_VARIABLE {
+                self.return_expression.borrow_mut().context_type = Some(item_type.clone());
+            }
+
             let parameter_type = ComplexTypeRef::from_value_type(item_type);
             let for_parameter = FormalParameter::with_type_ref(self.in_loop_variable.clone(), parameter_type);
 
@@ -255,3 +270,231 @@ impl StaticLink for ForFunction {
         self.return_type.clone()
     }
 }
+
+//--------------------------------------------------------------------------------------------------
+
+/// map(in_expression, it * 2) or map(in_expression, (x) => x * 2)
+/// is the same as `for x in in_expression return x * 2`
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct MapFunction {
+    pub projection: ForFunction,
+    pub is_lambda: bool,
+}
+
+impl MapFunction {
+    pub fn build(in_expression: ExpressionEnum, projection: ExpressionEnum) -> Result<Self, ParseErrorEnum> {
+        match projection.into_lambda() {
+            Ok(lambda) => {
+                let (mut parameters, body) = lambda.into_parts();
+                if parameters.len() != 1 {
+                    return Err(ParseErrorEnum::WrongFormat(format!(
+                        "map lambda expects exactly 1 parameter, got {}",
+                        parameters.len()
+                    )));
+                }
+                let projection = ForFunction::new(parameters.remove(0), in_expression, body)?;
+                Ok(MapFunction { projection, is_lambda: true })
+            }
+            Err(projection) => {
+                let projection = ForFunction::new(CONTEXT_VARIABLE.to_string(), in_expression, projection)?;
+                Ok(MapFunction { projection, is_lambda: false })
+            }
+        }
+    }
+}
+
+impl Display for MapFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let projection = &self.projection;
+        let return_expression = context_unwrap(projection.return_expression.borrow().to_string());
+        if self.is_lambda {
+            write!(f, "map({}, ({}) => {})", projection.in_expression, projection.in_loop_variable, return_expression)
+        } else {
+            write!(f, "map({}, {})", projection.in_expression, return_expression)
+        }
+    }
+}
+
+impl StaticLink for MapFunction {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        self.projection.link(ctx)
+    }
+}
+
+impl EvaluatableExpression for MapFunction {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        self.projection.eval(context)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+
+/// Accumulator variable of `reduce` when no lambda is given
+const ACCUMULATOR_VARIABLE: &str = "acc";
+
+/// reduce(in_expression, initial, acc + it) or reduce(in_expression, initial, (acc, x) => acc + x)
+/// folds the list from left to right: the body is evaluated for every element with the accumulator bound as `acc`
+/// and the element as `it`, or under the lambda parameter names, and its result becomes the next accumulator.
+/// The initial value sets the accumulator type and is returned as is for an empty list.
+/// A special value in place of the list, e.g. `Missing`, is returned as the result without evaluating the body.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct ReduceFunction {
+    pub accumulator_variable: String,
+    pub in_loop_variable: String,
+    pub in_expression: ExpressionEnum,
+    pub initial: ExpressionEnum,
+    /// In definition body is wrapped in InlineFunctionContext
+    pub body: Rc<RefCell<ContextObject>>,
+    pub is_lambda: bool,
+    pub return_type: Link<ValueType>,
+}
+
+impl ReduceFunction {
+    pub fn build(
+        in_expression: ExpressionEnum,
+        initial: ExpressionEnum,
+        body: ExpressionEnum,
+    ) -> Result<Self, ParseErrorEnum> {
+        let (accumulator_variable, in_loop_variable, body, is_lambda) = match body.into_lambda() {
+            Ok(lambda) => {
+                let (mut parameters, body) = lambda.into_parts();
+                if parameters.len() != 2 {
+                    return Err(ParseErrorEnum::WrongFormat(format!(
+                        "reduce lambda expects exactly 2 parameters, got {}",
+                        parameters.len()
+                    )));
+                }
+                let in_loop_variable = parameters.remove(1);
+                (parameters.remove(0), in_loop_variable, body, true)
+            }
+            Err(body) => (ACCUMULATOR_VARIABLE.to_string(), CONTEXT_VARIABLE.to_string(), body, false),
+        };
+
+        let mut builder = ContextObjectBuilder::new();
+        builder.add_expression(RETURN_EXPRESSION, body)?;
+
+        Ok(ReduceFunction {
+            accumulator_variable,
+            in_loop_variable,
+            in_expression,
+            initial,
+            body: builder.build(),
+            is_lambda,
+            return_type: LinkingError::not_linked().into(),
+        })
+    }
+
+    fn create_step_context(
+        &self,
+        parent: &Rc<RefCell<ExecutionContext>>,
+        accumulator: ValueEnum,
+        value: ValueEnum,
+    ) -> Result<Rc<RefCell<ExecutionContext>>, RuntimeError> {
+        let context_variable = (self.in_loop_variable == CONTEXT_VARIABLE).then(|| value.clone());
+
+        let mut obj = ContextObjectBuilder::new();
+        obj.add_expression(self.accumulator_variable.as_str(), Value(accumulator))
+            .map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+        obj.add_expression(self.in_loop_variable.as_str(), Value(value))
+            .map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+
+        let ctx = ExecutionContext::create_temp_child_context(Rc::clone(parent), obj.build());
+        ctx.borrow_mut().context_variable = context_variable;
+
+        Ok(ctx)
+    }
+}
+
+impl Display for ReduceFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let body = context_unwrap(self.body.borrow().to_string());
+        if self.is_lambda {
+            write!(
+                f,
+                "reduce({}, {}, ({}, {}) => {})",
+                self.in_expression, self.initial, self.accumulator_variable, self.in_loop_variable, body
+            )
+        } else {
+            write!(f, "reduce({}, {}, {})", self.in_expression, self.initial, body)
+        }
+    }
+}
+
+impl StaticLink for ReduceFunction {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let list_type = self.in_expression.link(Rc::clone(&ctx))?;
+
+            let item_type = match list_type {
+                ValueType::ListType(list_item_type) => list_item_type
+                    .as_ref()
+                    .map(|inner| (**inner).clone())
+                    .map(flatten_list_type_for_for)
+                    .unwrap_or(ValueType::UndefinedType),
+                ValueType::RangeType => ValueType::NumberType,
+                _ => {
+                    return LinkingError::other_error(format!("Cannot reduce non list type `{}`", list_type)).into();
+                }
+            };
+            let accumulator_type = self.initial.link(Rc::clone(&ctx))?;
+
+            if self.in_loop_variable == CONTEXT_VARIABLE {
+                self.body.borrow_mut().context_type = Some(item_type.clone());
+            }
+
+            let parameters = [
+                (self.accumulator_variable.clone(), accumulator_type.clone()),
+                (self.in_loop_variable.clone(), item_type),
+            ];
+            for (name, parameter_type) in parameters {
+                let parameter = FormalParameter::with_type_ref(name, ComplexTypeRef::from_value_type(parameter_type));
+                self.body.borrow_mut().parameters.push(parameter);
+            }
+            self.body.borrow_mut().node = NodeData::new(NodeDataEnum::Internal(Rc::downgrade(&ctx), None));
+
+            link_parts(Rc::clone(&self.body))?;
+
+            let body_type =
+                self.body.borrow().expressions.get(RETURN_EXPRESSION).unwrap().borrow().field_type.clone()?;
+            LinkingError::expect_same_types("reduce", accumulator_type, body_type.clone())?;
+
+            self.return_type = Ok(body_type);
+        }
+
+        self.return_type.clone()
+    }
+}
+
+impl EvaluatableExpression for ReduceFunction {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let values: Vec<ValueEnum> = match self.in_expression.eval(Rc::clone(&context))? {
+            Array(ArrayValue::EmptyUntyped) => Vec::new(),
+            Array(ArrayValue::PrimitivesArray { values, .. }) => values,
+            Array(ArrayValue::ObjectsArray { values, .. }) => values.into_iter().map(ValueEnum::Reference).collect(),
+            RangeValue(range) => range.map(ValueEnum::from).collect(),
+            other => {
+                return match other.special_value() {
+                    Some(special) => {
+                        Ok(ValueEnum::special_for_type(&self.return_type.clone()?, special.clone()).unwrap_or(other))
+                    }
+                    None => RuntimeError::eval_error(format!(
+                        "Cannot reduce '{}' because data type is {} and not an array",
+                        self.in_expression,
+                        other.get_type()
+                    ))
+                    .into(),
+                }
+            }
+        };
+
+        let body = self.body.borrow();
+        let body = body.expressions.get(RETURN_EXPRESSION).unwrap().borrow();
+        let mut accumulator = self.initial.eval(Rc::clone(&context))?;
+        for value in values {
+            let ctx = self.create_step_context(&context, accumulator, value)?;
+            accumulator = body.expression.eval(ctx)?;
+        }
+
+        Ok(accumulator)
+    }
+}
@@ -1,17 +1,22 @@
+use crate::ast::operators::math_operators::shift_date_by_months;
+use crate::ast::token::into_valid;
 use crate::ast::Link;
 use crate::typesystem::errors::{LinkingError, RuntimeError};
+use crate::typesystem::types::number::NumberEnum;
 use crate::typesystem::types::string::StringEnum;
-use crate::typesystem::types::ValueType::{DateTimeType, DateType, DurationType, PeriodType, StringType};
-use crate::typesystem::types::{TypedValue, ValueType};
+use crate::typesystem::types::ValueType::{DateTimeType, DateType, DurationType, NumberType, PeriodType, StringType};
+use crate::typesystem::types::{SpecialValueEnum, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum;
 use crate::typesystem::values::ValueEnum::{
-    DateTimeValue, DateValue, DurationValue as DurationVariant, PeriodValue as PeriodVariant, StringValue, TimeValue,
+    BooleanValue, DateTimeValue, DateValue, DurationValue as DurationVariant, NumberValue,
+    PeriodValue as PeriodVariant, StringValue, TimeValue,
 };
-use crate::typesystem::values::ValueOrSv;
+use crate::typesystem::values::{ArrayValue, ValueOrSv};
 use crate::typesystem::values::{DurationValue, PeriodValue};
+use std::collections::BTreeSet;
 use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
-use time::{Month, OffsetDateTime, PrimitiveDateTime};
+use time::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, Weekday};
 
 pub fn expect_string_arg(arg: ValueType) -> Link<()> {
     LinkingError::expect_type(None, arg, &[StringType]).map(|_| ())
@@ -21,6 +26,43 @@ pub fn expect_date_arg(arg: ValueType) -> Link<()> {
     LinkingError::expect_type(None, arg, &[DateType]).map(|_| ())
 }
 
+pub fn expect_duration_arg(arg: ValueType) -> Link<()> {
+    LinkingError::expect_type(None, arg, &[DurationType]).map(|_| ())
+}
+
+pub fn expect_datetime_arg(arg: ValueType) -> Link<()> {
+    LinkingError::expect_type(None, arg, &[DateTimeType]).map(|_| ())
+}
+
+pub fn expect_date_or_datetime_arg(arg: ValueType) -> Link<()> {
+    LinkingError::expect_type(None, arg, &[DateType, DateTimeType]).map(|_| ())
+}
+
+pub fn validate_multi_business_day(args: Vec<ValueType>) -> Link<()> {
+    expect_date_or_datetime_arg(args[0].clone())?;
+    expect_holidays_arg(args.get(1))
+}
+
+pub fn validate_multi_add_business_days(args: Vec<ValueType>) -> Link<()> {
+    expect_date_arg(args[0].clone())?;
+    LinkingError::expect_type(None, args[1].clone(), &[NumberType])?;
+    expect_holidays_arg(args.get(2))
+}
+
+/// Optional list of ISO date strings
+fn expect_holidays_arg(arg: Option<&ValueType>) -> Link<()> {
+    match arg {
+        None | Some(ValueType::ListType(None)) => Ok(()),
+        Some(ValueType::ListType(Some(inner))) if matches!(**inner, StringType | ValueType::UndefinedType) => Ok(()),
+        Some(other) => LinkingError::types_not_compatible(
+            Some("holidays".to_string()),
+            other.clone(),
+            Some(vec![ValueType::list_of(StringType)]),
+        )
+        .into(),
+    }
+}
+
 pub fn parse_date_iso(s: &str) -> Option<time::Date> {
     let fmt = format_description!("[year]-[month]-[day]");
     time::Date::parse(s, &fmt).ok()
@@ -45,24 +87,36 @@ pub fn parse_datetime_flexible(s: &str) -> Option<OffsetDateTime> {
     }
 
     // 3. Try Primitive (No Offset) - Fallback to UTC
+    parse_datetime_local(s).map(PrimitiveDateTime::assume_utc)
+}
+
+/// Date and time without an offset, with or without seconds
+pub fn parse_datetime_local(s: &str) -> Option<PrimitiveDateTime> {
     // We try with seconds first, then without.
     let fmt_prim = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
     if let Ok(dt) = PrimitiveDateTime::parse(s, &fmt_prim) {
-        return Some(dt.assume_utc());
+        return Some(dt);
     }
 
     // Try with milliseconds
     let fmt_prim_ms = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond]");
     if let Ok(dt) = PrimitiveDateTime::parse(s, &fmt_prim_ms) {
-        return Some(dt.assume_utc());
+        return Some(dt);
     }
 
     let fmt_prim_no_sec = format_description!("[year]-[month]-[day]T[hour]:[minute]");
-    if let Ok(dt) = PrimitiveDateTime::parse(s, &fmt_prim_no_sec) {
-        return Some(dt.assume_utc());
+    PrimitiveDateTime::parse(s, &fmt_prim_no_sec).ok()
+}
+
+/// Offset such as `'+02:00'`, `'-05:30'` or `'Z'`
+pub fn parse_offset(s: &str) -> Result<UtcOffset, RuntimeError> {
+    if s == "Z" {
+        return Ok(UtcOffset::UTC);
     }
 
-    None
+    let fmt = format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+    UtcOffset::parse(s, &fmt)
+        .map_err(|_| RuntimeError::eval_error(format!("Invalid offset '{}', expected a value such as '+02:00'", s)))
 }
 
 pub fn parse_duration_iso8601(s: &str) -> Result<DurationValue, RuntimeError> {
@@ -205,11 +259,11 @@ pub fn parse_period_iso8601(s: &str) -> Result<PeriodValue, RuntimeError> {
             if idx < bytes.len() {
                 match bytes[idx] {
                     b'Y' => {
-                        years = num as i32;
+                        years = i32::try_from(num).map_err(|_| RuntimeError::parsing_from_string(PeriodType, 0))?;
                         saw_any = true;
                     }
                     b'M' => {
-                        months = num as i32;
+                        months = i32::try_from(num).map_err(|_| RuntimeError::parsing_from_string(PeriodType, 0))?;
                         saw_any = true;
                     }
                     b'D' => {
@@ -283,17 +337,7 @@ pub fn eval_calendar_diff(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum
         (DateValue(ValueOrSv::Value(start)), DateValue(ValueOrSv::Value(end))) => {
             let (negative, earlier, later) = if start <= end { (false, start, end) } else { (true, end, start) };
 
-            let mut months_total = i64::from(later.year() - earlier.year()) * 12
-                + i64::from(later.month() as i32 - earlier.month() as i32);
-            if months_total < 0 {
-                months_total = 0;
-            }
-
-            let mut anchor = shift_date_by_months_safe(earlier, months_total)?;
-            if anchor > later && months_total > 0 {
-                months_total -= 1;
-                anchor = shift_date_by_months_safe(earlier, months_total)?;
-            }
+            let (months_total, anchor) = whole_months_between(earlier, later)?;
 
             let day_diff = (later - anchor).whole_days();
             let period = PeriodValue::from_total_parts(i128::from(months_total), i128::from(day_diff), negative)?;
@@ -303,15 +347,58 @@ pub fn eval_calendar_diff(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum
     }
 }
 
-pub(crate) fn last_day_of_month(year: i32, month: u8) -> u8 {
-    fn is_leap_year(year: i32) -> bool {
-        (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+/// Number of whole months from `earlier` to `later` together with the date reached after adding them to `earlier`
+fn whole_months_between(earlier: time::Date, later: time::Date) -> Result<(i64, time::Date), RuntimeError> {
+    let mut months_total =
+        i64::from(later.year() - earlier.year()) * 12 + i64::from(later.month() as i32 - earlier.month() as i32);
+    if months_total < 0 {
+        months_total = 0;
+    }
+
+    let mut anchor = shift_date_by_months_safe(earlier, months_total)?;
+    if anchor > later && months_total > 0 {
+        months_total -= 1;
+        anchor = shift_date_by_months_safe(earlier, months_total)?;
+    }
+
+    Ok((months_total, anchor))
+}
+
+/// `daysBetween(start, end)` is the number of days from `start` to `end`, negative when `end` comes first
+pub fn eval_days_between(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match (left, right) {
+        (DateValue(ValueOrSv::Value(start)), DateValue(ValueOrSv::Value(end))) => {
+            Ok(NumberValue(NumberEnum::Int((end - start).whole_days())))
+        }
+        (DateValue(ValueOrSv::Sv(sv)), _) | (_, DateValue(ValueOrSv::Sv(sv))) => Ok(NumberValue(NumberEnum::SV(sv))),
+        (_left_value, _right_value) => RuntimeError::internal_integrity_error(302).into(),
+    }
+}
+
+/// `monthsBetween(start, end)` is the number of whole calendar months from `start` to `end`, negative when `end`
+/// comes first. A month is complete once the same day of month is reached, or the month end for shorter months.
+pub fn eval_months_between(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match (left, right) {
+        (DateValue(ValueOrSv::Value(start)), DateValue(ValueOrSv::Value(end))) => {
+            let (negative, earlier, later) = if start <= end { (false, start, end) } else { (true, end, start) };
+            let (months, _) = whole_months_between(earlier, later)?;
+            Ok(NumberValue(NumberEnum::Int(if negative { -months } else { months })))
+        }
+        (DateValue(ValueOrSv::Sv(sv)), _) | (_, DateValue(ValueOrSv::Sv(sv))) => Ok(NumberValue(NumberEnum::SV(sv))),
+        (_left_value, _right_value) => RuntimeError::internal_integrity_error(303).into(),
     }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+pub(crate) fn last_day_of_month(year: i32, month: u8) -> u8 {
     match month {
         1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
         4 | 6 | 9 | 11 => 30,
         2 => {
-            if is_leap_year(year) {
+            if is_leap_year(i64::from(year)) {
                 29
             } else {
                 28
@@ -373,6 +460,237 @@ pub fn eval_last_day_of_month(value: ValueEnum) -> Result<ValueEnum, RuntimeErro
     }
 }
 
+/// Magnitude of a duration, for example `absDuration(date('2024-01-01') - date('2024-01-05'))` is `P4D`
+pub fn eval_abs_duration(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        DurationVariant(ValueOrSv::Value(duration)) => {
+            let magnitude = DurationValue::from_signed_seconds(duration.signed_seconds().abs())?;
+            Ok(DurationVariant(ValueOrSv::Value(magnitude)))
+        }
+        DurationVariant(ValueOrSv::Sv(sv)) => Ok(DurationVariant(ValueOrSv::Sv(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// `-1`, `0` or `1` depending on the sign of a duration
+pub fn eval_duration_sign(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        DurationVariant(ValueOrSv::Value(duration)) => Ok(ValueEnum::from(duration.signed_seconds().signum() as i64)),
+        DurationVariant(ValueOrSv::Sv(sv)) => Ok(NumberValue(NumberEnum::SV(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// Calendar date of a date or datetime, `None` for special values
+fn calendar_date(value: &ValueEnum) -> Result<Option<time::Date>, RuntimeError> {
+    match value {
+        DateValue(ValueOrSv::Value(date)) => Ok(Some(*date)),
+        DateTimeValue(ValueOrSv::Value(datetime)) => Ok(Some(datetime.date())),
+        DateValue(ValueOrSv::Sv(_)) | DateTimeValue(ValueOrSv::Sv(_)) => Ok(None),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+fn is_weekend(date: time::Date) -> bool {
+    matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+}
+
+/// Special value of an operand as a predicate result, held as a string the same way omitted booleans are
+fn special_predicate_result(value: &ValueEnum) -> ValueEnum {
+    match value.special_value() {
+        Some(sv) => StringValue(StringEnum::SV(sv.clone())),
+        None => unreachable!("predicates fall back to the special value of an operand only"),
+    }
+}
+
+pub fn eval_is_weekend(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match calendar_date(&value)? {
+        Some(date) => Ok(BooleanValue(is_weekend(date))),
+        None => Ok(special_predicate_result(&value)),
+    }
+}
+
+/// `startOfMonth(date)` is the first day of the month, a datetime keeps its time of day and offset
+pub fn eval_start_of_month(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    snap_date(value, |date| date.replace_day(1))
+}
+
+/// `endOfMonth(date)` is the last day of the month, a datetime keeps its time of day and offset
+pub fn eval_end_of_month(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    snap_date(value, |date| date.replace_day(last_day_of_month(date.year(), date.month() as u8)))
+}
+
+/// `startOfYear(date)` is January 1 of the same year, a datetime keeps its time of day and offset
+pub fn eval_start_of_year(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    snap_date(value, |date| time::Date::from_calendar_date(date.year(), Month::January, 1))
+}
+
+/// `endOfYear(date)` is December 31 of the same year, a datetime keeps its time of day and offset
+pub fn eval_end_of_year(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    snap_date(value, |date| time::Date::from_calendar_date(date.year(), Month::December, 31))
+}
+
+fn snap_date(
+    value: ValueEnum,
+    snap: impl Fn(time::Date) -> Result<time::Date, time::error::ComponentRange>,
+) -> Result<ValueEnum, RuntimeError> {
+    let snap = |date| snap(date).map_err(|err| RuntimeError::eval_error(err.to_string()));
+    match value {
+        DateValue(ValueOrSv::Value(date)) => Ok(DateValue(ValueOrSv::Value(snap(date)?))),
+        DateTimeValue(ValueOrSv::Value(datetime)) => {
+            Ok(DateTimeValue(ValueOrSv::Value(datetime.replace_date(snap(datetime.date())?))))
+        }
+        DateValue(ValueOrSv::Sv(_)) | DateTimeValue(ValueOrSv::Sv(_)) => Ok(value),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+pub fn expect_date_datetime_or_number_arg(arg: ValueType) -> Link<()> {
+    LinkingError::expect_type(None, arg, &[DateType, DateTimeType, NumberType]).map(|_| ())
+}
+
+/// `isLeapYear(date)` or `isLeapYear(2024)` by the Gregorian rule
+pub fn eval_is_leap_year(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let year = match &value {
+        NumberValue(NumberEnum::SV(_)) => return Ok(special_predicate_result(&value)),
+        NumberValue(_) => value
+            .as_i64()
+            .ok_or_else(|| RuntimeError::eval_error(format!("isLeapYear expects a whole year, got {}", value)))?,
+        other => match calendar_date(other)? {
+            Some(date) => i64::from(date.year()),
+            None => return Ok(special_predicate_result(other)),
+        },
+    };
+    Ok(BooleanValue(is_leap_year(year)))
+}
+
+/// Parses an optional list of ISO date strings, special values in the list are skipped
+fn holiday_dates(holidays: Option<&ValueEnum>) -> Result<BTreeSet<time::Date>, RuntimeError> {
+    let values = match holidays {
+        None | Some(ValueEnum::Array(ArrayValue::EmptyUntyped)) => &[][..],
+        Some(ValueEnum::Array(ArrayValue::PrimitivesArray { values, .. })) => values.as_slice(),
+        Some(other) => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+
+    let mut dates = BTreeSet::new();
+    for holiday in values {
+        match holiday {
+            StringValue(StringEnum::String(raw)) => {
+                dates.insert(parse_date_iso(raw).ok_or_else(|| RuntimeError::parsing_from_string(DateType, 0))?);
+            }
+            StringValue(StringEnum::SV(_)) => {}
+            other => return RuntimeError::type_not_supported(other.get_type()).into(),
+        }
+    }
+    Ok(dates)
+}
+
+fn is_business_day(date: time::Date, holidays: &BTreeSet<time::Date>) -> bool {
+    !is_weekend(date) && !holidays.contains(&date)
+}
+
+/// `isBusinessDay(date)` or `isBusinessDay(date, holidays)` where holidays are ISO dates such as `'2024-12-25'`
+pub fn eval_is_business_day(
+    args: Vec<Result<ValueEnum, RuntimeError>>,
+    _ret: ValueType,
+) -> Result<ValueEnum, RuntimeError> {
+    let vals = into_valid(args)?;
+    // validation ensures 1 or 2 arguments
+    let Some(date) = calendar_date(&vals[0])? else {
+        return Ok(special_predicate_result(&vals[0]));
+    };
+
+    Ok(BooleanValue(is_business_day(date, &holiday_dates(vals.get(1))?)))
+}
+
+pub fn validate_binary_date_number(left: ValueType, right: ValueType) -> Link<()> {
+    LinkingError::expect_type(None, left, &[DateType, DateTimeType])?;
+    LinkingError::expect_type(None, right, &[NumberType]).map(|_| ())
+}
+
+/// `addMonths(date, n)` moves a date or datetime `n` calendar months, backward when `n` is negative. The day is clamped
+/// to the last day of the target month the same way `date + period('P1M')` does.
+pub fn eval_add_months(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    shift_by_months(left, right, 1, "addMonths")
+}
+
+/// `addYears(date, n)` moves a date or datetime `n` years, so February 29 becomes February 28 in a common year
+pub fn eval_add_years(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    shift_by_months(left, right, 12, "addYears")
+}
+
+fn shift_by_months(value: ValueEnum, count: ValueEnum, unit: i128, name: &str) -> Result<ValueEnum, RuntimeError> {
+    let count = match count {
+        NumberValue(NumberEnum::SV(sv)) => {
+            return match value {
+                DateTimeValue(_) => Ok(DateTimeValue(ValueOrSv::Sv(sv))),
+                _ => Ok(DateValue(ValueOrSv::Sv(sv))),
+            }
+        }
+        other => i128::from(
+            other
+                .as_i64()
+                .ok_or_else(|| RuntimeError::eval_error(format!("{} expects a whole number, got {}", name, other)))?,
+        ),
+    };
+    let months = count
+        .checked_mul(unit)
+        .ok_or_else(|| RuntimeError::eval_error("Month offset is out of range for date adjustment".to_string()))?;
+
+    match value {
+        DateValue(ValueOrSv::Value(date)) => Ok(DateValue(ValueOrSv::Value(shift_date_by_months(date, months)?))),
+        DateTimeValue(ValueOrSv::Value(datetime)) => {
+            let date = shift_date_by_months(datetime.date(), months)?;
+            Ok(DateTimeValue(ValueOrSv::Value(datetime.replace_date(date))))
+        }
+        DateValue(ValueOrSv::Sv(_)) | DateTimeValue(ValueOrSv::Sv(_)) => Ok(value),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// `addBusinessDays(date, n)` or `addBusinessDays(date, n, holidays)` moves `n` business days forward, or backward
+/// when `n` is negative, skipping weekends and holidays. With `n = 0` the date is returned as is.
+pub fn eval_add_business_days(
+    args: Vec<Result<ValueEnum, RuntimeError>>,
+    _ret: ValueType,
+) -> Result<ValueEnum, RuntimeError> {
+    let vals = into_valid(args)?;
+    // validation ensures 2 or 3 arguments
+    let mut date = match &vals[0] {
+        DateValue(ValueOrSv::Value(date)) => *date,
+        DateValue(ValueOrSv::Sv(sv)) => return Ok(DateValue(ValueOrSv::Sv(sv.clone()))),
+        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+    let days = match &vals[1] {
+        NumberValue(NumberEnum::SV(sv)) => return Ok(DateValue(ValueOrSv::Sv(sv.clone()))),
+        other => other.as_i64().ok_or_else(|| {
+            RuntimeError::eval_error(format!("addBusinessDays expects a whole number of days, got {}", other))
+        })?,
+    };
+    let holidays = holiday_dates(vals.get(2))?;
+
+    let mut remaining = days.unsigned_abs();
+    while remaining > 0 {
+        let next = if days > 0 { date.next_day() } else { date.previous_day() };
+        date = next.ok_or_else(|| RuntimeError::eval_error("addBusinessDays is out of the date range".to_string()))?;
+        if is_business_day(date, &holidays) {
+            remaining -= 1;
+        }
+    }
+
+    Ok(DateValue(ValueOrSv::Value(date)))
+}
+
+/// Current date in UTC of the evaluation instant
+pub fn eval_today(instant: OffsetDateTime) -> Result<ValueEnum, RuntimeError> {
+    Ok(DateValue(ValueOrSv::Value(instant.date())))
+}
+
+/// Current date and time in UTC, read once per evaluation
+pub fn eval_now(instant: OffsetDateTime) -> Result<ValueEnum, RuntimeError> {
+    Ok(DateTimeValue(ValueOrSv::Value(instant)))
+}
+
 pub fn eval_date(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let StringValue(ref s) = value {
         if let StringEnum::String(raw) = s.clone() {
@@ -409,6 +727,71 @@ pub fn eval_datetime(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     RuntimeError::type_not_supported(value.get_type()).into()
 }
 
+pub fn validate_binary_datetime_string(left: ValueType, right: ValueType) -> Link<()> {
+    expect_datetime_arg(left)?;
+    expect_string_arg(right)
+}
+
+pub fn return_datetime_type_binary(_: ValueType, _: ValueType) -> ValueType {
+    DateTimeType
+}
+
+/// Offset argument, `Err` with the datetime to return when the offset is a special value
+fn offset_arg(value: ValueEnum) -> Result<Result<UtcOffset, ValueEnum>, RuntimeError> {
+    match value {
+        StringValue(StringEnum::String(raw)) => parse_offset(raw.as_str()).map(Ok),
+        StringValue(StringEnum::SV(sv)) => Ok(Err(DateTimeValue(ValueOrSv::Sv(sv)))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+fn datetime_at_offset(datetime: OffsetDateTime, offset: UtcOffset) -> Result<ValueEnum, RuntimeError> {
+    let converted = datetime.checked_to_offset(offset).ok_or_else(|| {
+        RuntimeError::eval_error(format!("Datetime {} is out of range at offset {}", datetime, offset))
+    })?;
+    Ok(DateTimeValue(ValueOrSv::Value(converted)))
+}
+
+/// `datetimeTz('2024-06-01T10:00:00', '+02:00')` reads the local date and time at the given offset. A string that
+/// carries its own offset is converted to the given one.
+pub fn eval_datetime_tz(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let offset = match offset_arg(right)? {
+        Ok(offset) => offset,
+        Err(sv) => return Ok(sv),
+    };
+    match left {
+        StringValue(StringEnum::String(raw)) => {
+            if let Some(local) = parse_datetime_local(raw.as_str()) {
+                return Ok(DateTimeValue(ValueOrSv::Value(local.assume_offset(offset))));
+            }
+            match parse_datetime_flexible(raw.as_str()) {
+                Some(datetime) => datetime_at_offset(datetime, offset),
+                None => RuntimeError::parsing_from_string(DateTimeType, 0).into(),
+            }
+        }
+        StringValue(StringEnum::SV(sv)) => Ok(DateTimeValue(ValueOrSv::Sv(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// The same instant at offset `+00:00`
+pub fn eval_to_utc(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_to_offset(value, StringValue(StringEnum::String("Z".to_string())))
+}
+
+/// The same instant at the given offset, for example `toOffset(datetime('2024-06-01T08:00:00'), '+02:00')` is
+/// `2024-06-01T10:00:00+02:00`
+pub fn eval_to_offset(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match left {
+        DateTimeValue(ValueOrSv::Value(datetime)) => match offset_arg(right)? {
+            Ok(offset) => datetime_at_offset(datetime, offset),
+            Err(sv) => Ok(sv),
+        },
+        DateTimeValue(ValueOrSv::Sv(sv)) => Ok(DateTimeValue(ValueOrSv::Sv(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
 pub fn eval_duration(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let StringValue(ref s) = value {
         if let StringEnum::String(raw) = s.clone() {
@@ -427,6 +810,30 @@ pub fn eval_period(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     RuntimeError::type_not_supported(value.get_type()).into()
 }
 
+/// Same as `duration`, but malformed input gives `NotApplicable` instead of an error
+pub fn eval_parse_duration(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        StringValue(StringEnum::String(raw)) => Ok(DurationVariant(match parse_duration_iso8601(raw.as_str()) {
+            Ok(duration) => ValueOrSv::Value(duration),
+            Err(_) => ValueOrSv::Sv(SpecialValueEnum::not_applicable(format!("invalid duration {}", raw))),
+        })),
+        StringValue(StringEnum::SV(sv)) => Ok(DurationVariant(ValueOrSv::Sv(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// Same as `period`, but malformed input gives `NotApplicable` instead of an error
+pub fn eval_parse_period(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        StringValue(StringEnum::String(raw)) => Ok(PeriodVariant(match parse_period_iso8601(raw.as_str()) {
+            Ok(period) => ValueOrSv::Value(period),
+            Err(_) => ValueOrSv::Sv(SpecialValueEnum::not_applicable(format!("invalid period {}", raw))),
+        })),
+        StringValue(StringEnum::SV(sv)) => Ok(PeriodVariant(ValueOrSv::Sv(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,26 +1,35 @@
-use crate::ast::functions::function_numeric::list_item_as_second_arg;
+use crate::ast::context::context_object::ContextObject;
+use crate::ast::context::context_object_builder::ContextObjectBuilder;
+use crate::ast::context::context_object_type::EObjectContent;
+use crate::ast::functions::function_numeric::{add_promoting, list_item_as_second_arg, multiply_promoting};
 use crate::ast::functions::function_string as strf;
-use crate::ast::token::into_valid;
+use crate::ast::token::{into_valid, ExpressionEnum};
 use crate::ast::Link;
+use crate::link::linker;
+use crate::link::node_data::{ContentHolder, Node};
 use crate::runtime::execution_context::ExecutionContext;
 use crate::typesystem::errors::{LinkingError, RuntimeError};
 use crate::typesystem::types::number::NumberEnum;
+use crate::typesystem::types::string::StringEnum;
 use crate::typesystem::types::string::StringEnum::{Char as SChar, String as SString};
-use crate::typesystem::types::ValueType::{BooleanType, ListType, NumberType, StringType};
+use crate::typesystem::types::ValueType::{BooleanType, DurationType, ListType, NumberType, StringType};
 use crate::typesystem::types::{Float, Integer, SpecialValueEnum, TypedValue, ValueType};
-use crate::typesystem::values::ValueEnum::{Array, BooleanValue, NumberValue, StringValue};
-use crate::typesystem::values::{ArrayValue, ValueEnum};
+use crate::typesystem::values::ValueEnum::{Array, BooleanValue, DurationValue, NumberValue, Reference, StringValue};
+use crate::typesystem::values::{ArrayValue, DurationValue as DurationStruct, ValueEnum, ValueOrSv};
 use rust_decimal::prelude::*;
 use rust_decimal::MathematicalOps;
 use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
-fn as_int(v: &ValueEnum) -> Option<i64> {
+fn as_int(v: &ValueEnum) -> Result<i64, RuntimeError> {
     match v {
-        NumberValue(NumberEnum::Int(i)) => Some(*i),
-        NumberValue(NumberEnum::Real(r)) => r.to_i64(),
-        _ => None,
+        NumberValue(NumberEnum::Int(i)) => Ok(*i),
+        NumberValue(NumberEnum::Real(r)) => {
+            r.to_i64().ok_or_else(|| RuntimeError::eval_error(format!("Number {} is out of integer range", r)))
+        }
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
 
@@ -45,7 +54,7 @@ fn build_array_from_parts(values: Vec<ValueEnum>, mut item_type: ValueType) -> R
     Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values, item_type }))
 }
 
-fn merge_item_type(target: &mut ValueType, candidate: ValueType) -> Result<(), RuntimeError> {
+pub(crate) fn merge_item_type(target: &mut ValueType, candidate: ValueType) -> Result<(), RuntimeError> {
     if matches!(candidate, ValueType::UndefinedType) {
         return Ok(());
     }
@@ -73,6 +82,82 @@ fn merge_item_type_from_value(target: &mut ValueType, value: &ValueEnum) -> Resu
     merge_item_type(target, value.get_type())
 }
 
+fn is_duration_list(item_type: &ValueType, values: &[ValueEnum]) -> bool {
+    matches!(item_type, DurationType) || matches!(values.first(), Some(DurationValue(_)))
+}
+
+/// Collects signed seconds of every duration in the list.
+/// Returns the first special value instead if any item is not a concrete duration.
+fn collect_duration_seconds(values: Vec<ValueEnum>) -> Result<Result<Vec<Float>, SpecialValueEnum>, RuntimeError> {
+    let mut seconds: Vec<Float> = Vec::with_capacity(values.len());
+    for v in values {
+        match v {
+            DurationValue(ValueOrSv::Value(duration)) => seconds.push(Float::from(duration.signed_seconds())),
+            DurationValue(ValueOrSv::Sv(sv)) => return Ok(Err(sv)),
+            other => return RuntimeError::type_not_supported(other.get_type()).into(),
+        }
+    }
+    Ok(Ok(seconds))
+}
+
+fn duration_from_float_seconds(seconds: Float) -> Result<ValueEnum, RuntimeError> {
+    // banker's rounding, see DATETIME_SPEC.md
+    let whole = seconds.round().to_i128().ok_or_else(|| RuntimeError::eval_error("duration overflow".to_string()))?;
+    Ok(ValueEnum::from(DurationStruct::from_signed_seconds(whole)?))
+}
+
+/// Running mean and squared deviations (Welford), so large values are never summed up
+#[derive(Default)]
+struct RunningMoments {
+    count: Float,
+    mean: Float,
+    m2: Float,
+}
+
+impl RunningMoments {
+    fn of(nums: &[Float]) -> Self {
+        let mut moments = RunningMoments::default();
+        nums.iter().for_each(|x| moments.push(*x));
+        moments
+    }
+
+    fn push(&mut self, x: Float) {
+        self.count += Float::ONE;
+        let delta = x - self.mean;
+        self.mean += delta / self.count;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn population_variance(&self) -> Float {
+        self.m2 / self.count
+    }
+
+    /// Divides by `n - 1`, so it is not defined for fewer than two values
+    fn sample_variance(&self) -> Option<Float> {
+        (self.count > Float::ONE).then(|| self.m2 / (self.count - Float::ONE))
+    }
+}
+
+fn float_mean(nums: &[Float]) -> Float {
+    RunningMoments::of(nums).mean
+}
+
+fn float_median(mut nums: Vec<Float>) -> Float {
+    nums.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let n = nums.len();
+    if n % 2 == 1 {
+        nums[n / 2]
+    } else {
+        let two = Float::from(2);
+        (nums[n / 2 - 1] + nums[n / 2]) / two
+    }
+}
+
+fn standard_deviation(variance: Float) -> Float {
+    // sqrt returns Option<Decimal> in MathematicalOps
+    variance.sqrt().unwrap_or(Float::ZERO)
+}
+
 // ---------------- Validators and return type helpers ----------------
 
 pub fn validate_unary_list(arg: ValueType) -> Link<()> {
@@ -99,6 +184,15 @@ pub fn validate_unary_list_numbers(arg: ValueType) -> Link<()> {
     }
 }
 
+pub fn validate_unary_list_numbers_or_durations(arg: ValueType) -> Link<()> {
+    if let ListType(Some(inner)) = &arg {
+        if matches!(inner.as_ref(), DurationType) {
+            return Ok(());
+        }
+    }
+    validate_unary_list_numbers(arg)
+}
+
 pub fn validate_unary_boolean_list(arg: ValueType) -> Link<()> {
     match arg {
         ListType(Some(inner)) => LinkingError::expect_type(None, *inner, &[BooleanType]).map(|_| ()),
@@ -112,6 +206,13 @@ pub fn validate_unary_boolean_list(arg: ValueType) -> Link<()> {
     }
 }
 
+pub fn return_number_or_duration_type(arg: ValueType) -> ValueType {
+    match arg {
+        ListType(Some(inner)) if matches!(inner.as_ref(), DurationType) => DurationType,
+        _ => NumberType,
+    }
+}
+
 pub fn return_same_list_type(arg: ValueType) -> ValueType {
     arg
 }
@@ -119,6 +220,11 @@ pub fn return_list_undefined(_args: &[ValueType]) -> ValueType {
     ListType(None)
 }
 
+/// Same list type as the first argument, so object fields of the result can be linked
+pub fn return_multi_same_list_type(args: &[ValueType]) -> ValueType {
+    args.first().cloned().unwrap_or(ListType(None))
+}
+
 pub fn return_flatten_type(arg: ValueType) -> ValueType {
     match arg {
         ListType(Some(inner)) => {
@@ -166,7 +272,7 @@ pub fn return_index_of_type(left: ValueType, _right: ValueType) -> ValueType {
     }
 }
 
-pub fn validate_unary_reverse_mixed(arg: ValueType) -> Link<()> {
+pub fn validate_unary_list_or_string(arg: ValueType) -> Link<()> {
     if matches!(arg, StringType) {
         strf::validate_unary_string(arg)
     } else {
@@ -204,7 +310,7 @@ pub fn eval_product(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
             for v in values {
                 if let NumberValue(n) = v {
                     acc = Some(match acc {
-                        Some(a) => a * n,
+                        Some(a) => multiply_promoting(a, n),
                         None => n,
                     });
                 } else {
@@ -221,6 +327,36 @@ pub fn eval_product(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
+/// Running totals of a number list, e.g. `[1, 2, 3]` becomes `[1, 3, 6]`
+pub fn eval_cumulative_sum(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        ValueEnum::Array(ArrayValue::PrimitivesArray { values, .. }) if !values.is_empty() => {
+            let mut totals = Vec::with_capacity(values.len());
+            let mut acc: Option<NumberEnum> = None;
+            for v in values {
+                if let NumberValue(n) = v {
+                    let total = match acc {
+                        Some(a) => add_promoting(a, n),
+                        None => n,
+                    };
+                    totals.push(NumberValue(total.clone()));
+                    acc = Some(total);
+                } else {
+                    return RuntimeError::type_not_supported(v.get_type()).into();
+                }
+            }
+            Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values: totals, item_type: NumberType }))
+        }
+        ValueEnum::Array(ArrayValue::EmptyUntyped) | ValueEnum::Array(ArrayValue::PrimitivesArray { .. }) => {
+            Ok(ValueEnum::Array(ArrayValue::EmptyUntyped))
+        }
+        ValueEnum::Array(ArrayValue::ObjectsArray { object_type, .. }) => {
+            RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
+        }
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
 pub fn eval_mean(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
         ValueEnum::Array(ArrayValue::EmptyUntyped) => {
@@ -229,27 +365,28 @@ pub fn eval_mean(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
         ValueEnum::Array(ArrayValue::ObjectsArray { object_type, .. }) => {
             RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
         }
-        ValueEnum::Array(ArrayValue::PrimitivesArray { values, .. }) => {
-            let mut sum = Float::ZERO;
-            let mut count = Float::ZERO;
-            let one = Float::from(1);
+        ValueEnum::Array(ArrayValue::PrimitivesArray { values, item_type }) => {
+            if is_duration_list(&item_type, &values) {
+                return match collect_duration_seconds(values)? {
+                    Err(sv) => Ok(DurationValue(ValueOrSv::Sv(sv))),
+                    Ok(seconds) if seconds.is_empty() => {
+                        Ok(DurationValue(ValueOrSv::Sv(SpecialValueEnum::missing_for(None))))
+                    }
+                    Ok(seconds) => duration_from_float_seconds(float_mean(&seconds)),
+                };
+            }
+            let mut moments = RunningMoments::default();
             for v in values {
                 match v {
-                    NumberValue(NumberEnum::Int(i)) => {
-                        sum += Float::from(i);
-                        count += one;
-                    }
-                    NumberValue(NumberEnum::Real(r)) => {
-                        sum += r;
-                        count += one;
-                    }
+                    NumberValue(NumberEnum::Int(i)) => moments.push(Float::from(i)),
+                    NumberValue(NumberEnum::Real(r)) => moments.push(r),
                     _ => return RuntimeError::type_not_supported(v.get_type()).into(),
                 }
             }
-            if count == Float::ZERO {
+            if moments.count == Float::ZERO {
                 Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))))
             } else {
-                Ok(NumberValue(NumberEnum::from(sum / count)))
+                Ok(NumberValue(NumberEnum::from(moments.mean)))
             }
         }
         other => RuntimeError::type_not_supported(other.get_type()).into(),
@@ -264,7 +401,16 @@ pub fn eval_median(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
         ValueEnum::Array(ArrayValue::ObjectsArray { object_type, .. }) => {
             RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
         }
-        ValueEnum::Array(ArrayValue::PrimitivesArray { mut values, .. }) => {
+        ValueEnum::Array(ArrayValue::PrimitivesArray { mut values, item_type }) => {
+            if is_duration_list(&item_type, &values) {
+                return match collect_duration_seconds(values)? {
+                    Err(sv) => Ok(DurationValue(ValueOrSv::Sv(sv))),
+                    Ok(seconds) if seconds.is_empty() => {
+                        Ok(DurationValue(ValueOrSv::Sv(SpecialValueEnum::missing_for(None))))
+                    }
+                    Ok(seconds) => duration_from_float_seconds(float_median(seconds)),
+                };
+            }
             if values.is_empty() {
                 return Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))));
             }
@@ -276,59 +422,113 @@ pub fn eval_median(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
                     _ => return RuntimeError::type_not_supported(v.get_type()).into(),
                 }
             }
-            nums.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-            let n = nums.len();
-            let med = if n % 2 == 1 {
-                nums[n / 2]
-            } else {
-                let two = Float::from(2);
-                (nums[n / 2 - 1] + nums[n / 2]) / two
-            };
-            Ok(NumberValue(NumberEnum::from(med)))
+            Ok(NumberValue(NumberEnum::from(float_median(nums))))
         }
         other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
 
+/// Percentile `p` (0..100) of a number list, linearly interpolated between the neighbouring ranks
+pub fn eval_percentile(value: ValueEnum, percentile: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let p = match percentile {
+        NumberValue(NumberEnum::Int(i)) => Float::from(i),
+        NumberValue(NumberEnum::Real(r)) => r,
+        NumberValue(NumberEnum::SV(sv)) => return Ok(NumberValue(NumberEnum::SV(sv))),
+        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+    let hundred = Float::from(100);
+    if p < Float::ZERO || p > hundred {
+        return Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::not_applicable(format!(
+            "percentile {} is out of range [0, 100]",
+            p
+        )))));
+    }
+
+    let values = match value {
+        ValueEnum::Array(ArrayValue::PrimitivesArray { values, .. }) if !values.is_empty() => values,
+        ValueEnum::Array(ArrayValue::EmptyUntyped) | ValueEnum::Array(ArrayValue::PrimitivesArray { .. }) => {
+            return Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))));
+        }
+        ValueEnum::Array(ArrayValue::ObjectsArray { object_type, .. }) => {
+            return RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into();
+        }
+        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+    let mut nums: Vec<Float> = Vec::with_capacity(values.len());
+    for v in values {
+        match v {
+            NumberValue(NumberEnum::Int(i)) => nums.push(Float::from(i)),
+            NumberValue(NumberEnum::Real(r)) => nums.push(r),
+            _ => return RuntimeError::type_not_supported(v.get_type()).into(),
+        }
+    }
+    nums.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let rank = p / hundred * Float::from(nums.len() - 1);
+    let lower = rank.floor();
+    let below = nums[lower.to_usize().unwrap_or(0)];
+    let above = nums[rank.ceil().to_usize().unwrap_or(0)];
+    Ok(NumberValue(NumberEnum::Real(below + (above - below) * (rank - lower))))
+}
+
+/// Population standard deviation. For duration lists the result is a number of seconds.
 pub fn eval_stddev(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_dispersion(value, |moments| Some(standard_deviation(moments.population_variance())))
+}
+
+/// Sample standard deviation (`n - 1` divisor), `Missing` for fewer than two values
+pub fn eval_stddev_sample(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_dispersion(value, |moments| moments.sample_variance().map(standard_deviation))
+}
+
+/// Population variance. For duration lists the result is in seconds squared.
+pub fn eval_variance(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_dispersion(value, |moments| Some(moments.population_variance()))
+}
+
+/// Sample variance (`n - 1` divisor), `Missing` for fewer than two values
+pub fn eval_variance_sample(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_dispersion(value, RunningMoments::sample_variance)
+}
+
+fn eval_dispersion(value: ValueEnum, measure: fn(&RunningMoments) -> Option<Float>) -> Result<ValueEnum, RuntimeError> {
+    let missing = || Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))));
     match value {
-        ValueEnum::Array(ArrayValue::EmptyUntyped) => {
-            Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))))
-        }
+        ValueEnum::Array(ArrayValue::EmptyUntyped) => missing(),
         ValueEnum::Array(ArrayValue::ObjectsArray { object_type, .. }) => {
             RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
         }
-        ValueEnum::Array(ArrayValue::PrimitivesArray { values, .. }) => {
+        ValueEnum::Array(ArrayValue::PrimitivesArray { values, item_type }) => {
             if values.is_empty() {
-                return Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))));
+                return missing();
             }
-            let mut nums: Vec<Float> = Vec::with_capacity(values.len());
-            for v in values {
-                match v {
-                    NumberValue(NumberEnum::Int(i)) => nums.push(Float::from(i)),
-                    NumberValue(NumberEnum::Real(r)) => nums.push(r),
-                    _ => return RuntimeError::type_not_supported(v.get_type()).into(),
+            let moments = if is_duration_list(&item_type, &values) {
+                match collect_duration_seconds(values)? {
+                    Err(sv) => return Ok(NumberValue(NumberEnum::SV(sv))),
+                    Ok(seconds) => RunningMoments::of(&seconds),
+                }
+            } else {
+                let mut moments = RunningMoments::default();
+                for v in values {
+                    match v {
+                        NumberValue(NumberEnum::Int(i)) => moments.push(Float::from(i)),
+                        NumberValue(NumberEnum::Real(r)) => moments.push(r),
+                        _ => return RuntimeError::type_not_supported(v.get_type()).into(),
+                    }
                 }
+                moments
+            };
+            match measure(&moments) {
+                Some(result) => Ok(NumberValue(NumberEnum::from(result))),
+                None => missing(),
             }
-            let count = Float::from(nums.len());
-            let mean = nums.iter().sum::<Float>() / count;
-            // Use iterator map and sum
-            let var = nums
-                .iter()
-                .map(|x| {
-                    let diff = *x - mean;
-                    diff * diff
-                })
-                .sum::<Float>()
-                / count;
-
-            // sqrt returns Option<Decimal> in MathematicalOps
-            Ok(NumberValue(NumberEnum::from(var.sqrt().unwrap_or(Float::ZERO))))
         }
         other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
 
+/// Returns all most frequent values of any primitive list. Ties are not broken: every value sharing the highest
+/// count is returned, in the order it was first seen in the input.
 pub fn eval_mode(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
         ValueEnum::Array(ArrayValue::EmptyUntyped) => Ok(ValueEnum::Array(ArrayValue::EmptyUntyped)),
@@ -401,47 +601,40 @@ pub fn eval_any(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
 pub fn eval_sublist(args: Vec<Result<ValueEnum, RuntimeError>>, ret: ValueType) -> Result<ValueEnum, RuntimeError> {
     let vals = into_valid(args)?;
     // validation ensures 2 or 3 arguments
-    let (items, source_item_type) = match &vals[0] {
-        ValueEnum::Array(array) => match array {
-            ArrayValue::ObjectsArray { object_type, .. } => {
-                return RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(Rc::clone(
-                    object_type,
-                ))))
-                .into();
-            }
-            ArrayValue::PrimitivesArray { .. } | ArrayValue::EmptyUntyped => {
-                let item_type = array.item_type().unwrap_or(ValueType::UndefinedType);
-                (array.clone_primitive_values().unwrap_or_default(), item_type)
-            }
-        },
-        other => return RuntimeError::type_not_supported(other.get_type()).into(),
-    };
-    let start = as_int(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?; // 1-based
-    let len_opt = if vals.len() == 3 {
-        Some(as_int(&vals[2]).ok_or_else(|| RuntimeError::type_not_supported(vals[2].get_type()))?)
-    } else {
-        None
+    let ValueEnum::Array(array) = &vals[0] else {
+        return RuntimeError::type_not_supported(vals[0].get_type()).into();
     };
-    let n = items.len() as i64;
-    let i = (start - 1).max(0).min(n);
+    // 1-based, a negative start counts from the end: -1 is the last element
+    let start = as_int(&vals[1])?;
+    let len_opt = if vals.len() == 3 { Some(as_int(&vals[2])?) } else { None };
+    let n = array.len() as i64;
+    let i = if start < 0 { n.saturating_add(start) } else { start.saturating_sub(1) }.clamp(0, n);
     let j = match len_opt {
-        Some(l) => (i + l).min(n),
+        Some(l) => i.saturating_add(l).clamp(i, n),
         None => n,
     };
     let (ii, jj) = (i as usize, j as usize);
-    let out: Vec<ValueEnum> = items.iter().take(jj).skip(ii).cloned().collect();
-    let result_item_type = match ret {
-        ValueType::ListType(Some(inner)) => *inner,
-        ValueType::ListType(None) => source_item_type,
-        other => other,
-    };
-    build_array_from_parts(out, result_item_type)
+
+    match array {
+        ArrayValue::ObjectsArray { values, object_type } => {
+            let values = values[ii..jj].to_vec();
+            Ok(Array(ArrayValue::ObjectsArray { values, object_type: Rc::clone(object_type) }))
+        }
+        ArrayValue::PrimitivesArray { .. } | ArrayValue::EmptyUntyped => {
+            let source_item_type = array.item_type().unwrap_or(ValueType::UndefinedType);
+            let items = array.clone_primitive_values().unwrap_or_default();
+            let out: Vec<ValueEnum> = items.into_iter().take(jj).skip(ii).collect();
+            let result_item_type = match ret {
+                ValueType::ListType(Some(inner)) => *inner,
+                ValueType::ListType(None) => source_item_type,
+                other => other,
+            };
+            build_array_from_parts(out, result_item_type)
+        }
+    }
 }
 
 pub fn validate_multi_sublist(args: Vec<ValueType>) -> Link<()> {
-    if !(args.len() == 2 || args.len() == 3) {
-        return LinkingError::other_error("sublist expects 2 or 3 arguments".to_string()).into();
-    }
     LinkingError::expect_array_type(None, args[0].clone())?;
     LinkingError::expect_type(None, args[1].clone(), &[NumberType])?;
     if args.len() == 3 {
@@ -450,20 +643,41 @@ pub fn validate_multi_sublist(args: Vec<ValueType>) -> Link<()> {
     Ok(())
 }
 
+pub fn eval_deep_clone(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    Ok(value.deep_clone())
+}
+
+/// Object elements are copied, so the result shares no evaluation state with the source list
 pub fn eval_append(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
     // validation ensures at least 1 argument
-    let vals = into_valid(args)?;
+    let mut vals = into_valid(args)?;
     let (mut items, mut item_type) = match &vals[0] {
         ValueEnum::Array(array) => match array {
-            ArrayValue::ObjectsArray { object_type, .. } => {
-                return RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(Rc::clone(
-                    object_type,
-                ))))
-                .into();
+            ArrayValue::ObjectsArray { .. } => {
+                let rest = vals.split_off(1);
+                let Some(Array(ArrayValue::ObjectsArray { values, object_type })) = vals.pop() else {
+                    unreachable!("first argument is an object list")
+                };
+                let mut values: Vec<_> = values.iter().map(ExecutionContext::deep_clone).collect();
+                let list_item_type = ValueType::ObjectType(Rc::clone(&object_type));
+                for v in rest {
+                    match v {
+                        Reference(object) => {
+                            let item_type = ValueType::ObjectType(Rc::clone(&object.borrow().object));
+                            if item_type != list_item_type {
+                                return RuntimeError::type_not_supported(item_type).into();
+                            }
+                            values.push(ExecutionContext::deep_clone(&object));
+                        }
+                        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+                    }
+                }
+                return Ok(Array(ArrayValue::ObjectsArray { values, object_type }));
             }
             ArrayValue::PrimitivesArray { .. } | ArrayValue::EmptyUntyped => {
                 let item_type = array.item_type().unwrap_or(ValueType::UndefinedType);
-                (array.clone_primitive_values().unwrap_or_default(), item_type)
+                let values = array.clone_primitive_values().unwrap_or_default();
+                (values.iter().map(ValueEnum::deep_clone).collect::<Vec<_>>(), item_type)
             }
         },
         _ => return RuntimeError::type_not_supported(vals[0].get_type()).into(),
@@ -472,7 +686,7 @@ pub fn eval_append(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType)
         if matches!(item_type, ValueType::UndefinedType) {
             item_type = v.get_type();
         }
-        items.push(v);
+        items.push(v.deep_clone());
     }
     let out: Vec<ValueEnum> = items;
     build_array_from_parts(out, item_type)
@@ -489,12 +703,15 @@ pub fn validate_multi_append(args: Vec<ValueType>) -> Link<()> {
     Ok(())
 }
 
+/// Object elements are copied, so the result shares no evaluation state with the source lists
 pub fn eval_concatenate(
     args: Vec<Result<ValueEnum, RuntimeError>>,
     _ret: ValueType,
 ) -> Result<ValueEnum, RuntimeError> {
     let vals = into_valid(args)?;
     let mut out_items: Vec<ValueEnum> = Vec::new();
+    let mut out_objects: Vec<Rc<RefCell<ExecutionContext>>> = Vec::new();
+    let mut object_type: Option<Rc<RefCell<ContextObject>>> = None;
     let mut item_type = ValueType::UndefinedType;
 
     for value in vals {
@@ -507,13 +724,20 @@ pub fn eval_concatenate(
                     out_items.push(v);
                 }
             }
-            Array(ArrayValue::ObjectsArray { object_type, .. }) => {
-                return RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into();
+            Array(ArrayValue::ObjectsArray { values, object_type: array_object_type }) => {
+                // object lists are merged only if their object types match, same as in linking
+                merge_item_type(&mut item_type, ValueType::ObjectType(Rc::clone(&array_object_type)))?;
+                object_type.get_or_insert(array_object_type);
+                out_objects.extend(values.iter().map(ExecutionContext::deep_clone));
             }
             other => return RuntimeError::type_not_supported(other.get_type()).into(),
         }
     }
 
+    if let Some(object_type) = object_type {
+        return Ok(Array(ArrayValue::ObjectsArray { values: out_objects, object_type }));
+    }
+
     build_array_from_parts(out_items, item_type)
 }
 
@@ -541,8 +765,8 @@ pub fn eval_insert_before(
     // validation ensures 3 arguments
 
     let (mut items, mut item_type) = clone_array_parts(&vals[0])?;
-    let pos = as_int(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
-    let idx = ((pos - 1).max(0) as usize).min(items.len());
+    let pos = as_int(&vals[1])?;
+    let idx = usize::try_from(pos.saturating_sub(1)).unwrap_or(0).min(items.len());
 
     let value = vals[2].clone();
     merge_item_type_from_value(&mut item_type, &value)?;
@@ -552,23 +776,20 @@ pub fn eval_insert_before(
 }
 
 pub fn validate_multi_insert_before(args: Vec<ValueType>) -> Link<()> {
-    if args.len() != 3 {
-        return LinkingError::other_error("insertBefore expects 3 arguments".to_string()).into();
-    }
     let inner = LinkingError::expect_array_type(None, args[0].clone())?;
     LinkingError::expect_type(None, args[1].clone(), &[NumberType])?;
     LinkingError::expect_same_types("insertBefore", inner, args[2].clone()).map(|_| ())
 }
 
 pub fn eval_remove(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    let pos = as_int(&right).ok_or_else(|| RuntimeError::type_not_supported(right.get_type()))?;
+    let pos = as_int(&right)?;
 
     match left {
         Array(ArrayValue::EmptyUntyped) => Ok(Array(ArrayValue::EmptyUntyped)),
         Array(ArrayValue::PrimitivesArray { values, item_type }) => {
             let mut res: Vec<ValueEnum> = Vec::with_capacity(values.len());
             for (i, v) in values.into_iter().enumerate() {
-                if (i as i64) != (pos - 1) {
+                if (i as i64) != pos.saturating_sub(1) {
                     res.push(v);
                 }
             }
@@ -577,7 +798,7 @@ pub fn eval_remove(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Runti
         Array(ArrayValue::ObjectsArray { values, object_type }) => {
             let mut res: Vec<Rc<RefCell<ExecutionContext>>> = Vec::with_capacity(values.len());
             for (i, v) in values.into_iter().enumerate() {
-                if (i as i64) != (pos - 1) {
+                if (i as i64) != pos.saturating_sub(1) {
                     res.push(v);
                 }
             }
@@ -621,6 +842,46 @@ pub fn eval_index_of_mixed(left: ValueEnum, right: ValueEnum) -> Result<ValueEnu
     }
 }
 
+/// 1-based position of the first number that no later number is better than
+fn index_of_extremum(
+    values: ValueEnum,
+    is_better: fn(&NumberEnum, &NumberEnum) -> bool,
+) -> Result<ValueEnum, RuntimeError> {
+    match values {
+        Array(ArrayValue::EmptyUntyped) => Ok(NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None)))),
+        Array(ArrayValue::PrimitivesArray { values, .. }) => {
+            let mut best: Option<(usize, NumberEnum)> = None;
+            for (i, v) in values.into_iter().enumerate() {
+                let NumberValue(number) = v else {
+                    return RuntimeError::type_not_supported(v.get_type()).into();
+                };
+                // ties keep the first occurrence
+                if best.as_ref().is_none_or(|(_, current)| is_better(&number, current)) {
+                    best = Some((i, number));
+                }
+            }
+            Ok(match best {
+                Some((i, _)) => ValueEnum::from((i as Integer) + 1),
+                None => NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(None))),
+            })
+        }
+        Array(ArrayValue::ObjectsArray { object_type, .. }) => {
+            RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
+        }
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// 1-based position of the largest number, `indexOfMax([3, 9, 9])` is `2`
+pub fn eval_index_of_max(values: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    index_of_extremum(values, |candidate, current| candidate > current)
+}
+
+/// 1-based position of the smallest number, `indexOfMin([3, 1, 1])` is `2`
+pub fn eval_index_of_min(values: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    index_of_extremum(values, |candidate, current| candidate < current)
+}
+
 pub fn eval_union(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
     let vals = into_valid(args)?;
     let mut out: Vec<ValueEnum> = Vec::new();
@@ -648,15 +909,70 @@ pub fn eval_union(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -
     build_array_from_parts(out, item_type)
 }
 
-pub fn validate_multi_union(args: Vec<ValueType>) -> Link<()> {
+/// Elements of a set operation argument with duplicates removed, object lists are not supported
+fn set_operand(value: ValueEnum, item_type: &mut ValueType) -> Result<Vec<ValueEnum>, RuntimeError> {
+    match value {
+        Array(ArrayValue::EmptyUntyped) => Ok(Vec::new()),
+        Array(ArrayValue::PrimitivesArray { values, item_type: array_item_type }) => {
+            merge_item_type(item_type, array_item_type)?;
+            let mut out: Vec<ValueEnum> = Vec::new();
+            for v in values {
+                merge_item_type_from_value(item_type, &v)?;
+                if !out.iter().any(|existing| existing == &v) {
+                    out.push(v);
+                }
+            }
+            Ok(out)
+        }
+        Array(ArrayValue::ObjectsArray { object_type, .. }) => {
+            RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
+        }
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// Distinct elements of the first list that are present in every other list, in the order of the first list
+pub fn eval_intersection(
+    args: Vec<Result<ValueEnum, RuntimeError>>,
+    _ret: ValueType,
+) -> Result<ValueEnum, RuntimeError> {
+    let mut vals = into_valid(args)?.into_iter();
+    let mut item_type = ValueType::UndefinedType;
+    // validation ensures at least 1 argument
+    let mut out = set_operand(vals.next().unwrap_or(Array(ArrayValue::EmptyUntyped)), &mut item_type)?;
+
+    for value in vals {
+        let other = set_operand(value, &mut item_type)?;
+        out.retain(|v| other.contains(v));
+    }
+
+    build_array_from_parts(out, item_type)
+}
+
+/// Distinct elements of the first list that are not present in any other list, in the order of the first list
+pub fn eval_difference(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    let mut vals = into_valid(args)?.into_iter();
+    let mut item_type = ValueType::UndefinedType;
+    // validation ensures at least 1 argument
+    let mut out = set_operand(vals.next().unwrap_or(Array(ArrayValue::EmptyUntyped)), &mut item_type)?;
+
+    for value in vals {
+        let other = set_operand(value, &mut item_type)?;
+        out.retain(|v| !other.contains(v));
+    }
+
+    build_array_from_parts(out, item_type)
+}
+
+fn validate_multi_set(name: &str, args: Vec<ValueType>) -> Link<()> {
     if args.is_empty() {
-        return LinkingError::other_error("union expects at least 1 argument".to_string()).into();
+        return LinkingError::other_error(format!("{} expects at least 1 argument", name)).into();
     }
     let mut base: Option<ValueType> = None;
     for t in args {
         let inner = LinkingError::expect_array_type(None, t)?;
         if let Some(b) = &base {
-            LinkingError::expect_same_types("union", b.clone(), inner.clone())?;
+            LinkingError::expect_same_types(name, b.clone(), inner.clone())?;
         } else {
             base = Some(inner);
         }
@@ -664,6 +980,18 @@ pub fn validate_multi_union(args: Vec<ValueType>) -> Link<()> {
     Ok(())
 }
 
+pub fn validate_multi_union(args: Vec<ValueType>) -> Link<()> {
+    validate_multi_set("union", args)
+}
+
+pub fn validate_multi_intersection(args: Vec<ValueType>) -> Link<()> {
+    validate_multi_set("intersection", args)
+}
+
+pub fn validate_multi_difference(args: Vec<ValueType>) -> Link<()> {
+    validate_multi_set("difference", args)
+}
+
 pub fn eval_distinct(values: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match values {
         Array(ArrayValue::EmptyUntyped) => Ok(Array(ArrayValue::EmptyUntyped)),
@@ -753,12 +1081,15 @@ pub fn eval_flatten(values: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
-fn value_ordering(left: &ValueEnum, right: &ValueEnum) -> Ordering {
+/// Numbers are compared by value regardless of integer or real representation, so `1` and `1.0` are equal
+pub(crate) fn value_ordering(left: &ValueEnum, right: &ValueEnum) -> Ordering {
     match (left, right) {
         (NumberValue(NumberEnum::Int(x)), NumberValue(NumberEnum::Int(y))) => x.cmp(y),
         (NumberValue(NumberEnum::Real(x)), NumberValue(NumberEnum::Real(y))) => {
             x.partial_cmp(y).unwrap_or(Ordering::Equal)
         }
+        (NumberValue(NumberEnum::Int(x)), NumberValue(NumberEnum::Real(y))) => Float::from(*x).cmp(y),
+        (NumberValue(NumberEnum::Real(x)), NumberValue(NumberEnum::Int(y))) => x.cmp(&Float::from(*y)),
         (StringValue(SString(a)), StringValue(SString(b))) => a.cmp(b),
         (StringValue(SString(a)), StringValue(SChar(b))) => a.cmp(&b.to_string()),
         (StringValue(SChar(a)), StringValue(SString(b))) => a.to_string().cmp(b),
@@ -767,6 +1098,7 @@ fn value_ordering(left: &ValueEnum, right: &ValueEnum) -> Ordering {
     }
 }
 
+/// Ascending sort. The sort is stable: items with equal keys keep their original order.
 pub fn eval_sort(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
         Array(ArrayValue::EmptyUntyped) => Ok(Array(ArrayValue::EmptyUntyped)),
@@ -782,6 +1114,7 @@ pub fn eval_sort(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
+/// Descending sort. The sort is stable: items with equal keys keep their original order.
 pub fn eval_sort_desc(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
         Array(ArrayValue::EmptyUntyped) => Ok(Array(ArrayValue::EmptyUntyped)),
@@ -797,6 +1130,11 @@ pub fn eval_sort_desc(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
+pub fn validate_binary_list_numbers_number(left: ValueType, right: ValueType) -> Link<()> {
+    validate_unary_list_numbers(left)?;
+    LinkingError::expect_type(None, right, &[NumberType]).map(|_| ())
+}
+
 pub fn validate_binary_partition(left: ValueType, right: ValueType) -> Link<()> {
     LinkingError::expect_array_type(None, left)?;
     LinkingError::expect_type(None, right, &[NumberType]).map(|_| ())
@@ -869,16 +1207,167 @@ pub fn validate_multi_join(args: Vec<ValueType>) -> Link<()> {
     Ok(())
 }
 
+/// Joins a string field of every object, objects whose field holds a special value are skipped like in `join`
+pub fn eval_join_by(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    let vals = into_valid(args)?;
+    // validation ensures 3 arguments
+    let (Some(field), Some(delim)) = (vals[1].as_str(), vals[2].as_str()) else {
+        return RuntimeError::type_not_supported(vals[1].get_type()).into();
+    };
+    let objects = match &vals[0] {
+        Array(ArrayValue::ObjectsArray { values, .. }) => values.as_slice(),
+        Array(array) if array.is_empty() => &[],
+        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+
+    let mut parts: Vec<String> = Vec::with_capacity(objects.len());
+    for object in objects {
+        let content = object
+            .borrow()
+            .get(&field)
+            .map_err(|_| RuntimeError::field_not_found(&object.borrow().node().node_type.to_string(), &field))?;
+        let value = match content {
+            EObjectContent::ConstantValue(value) => value,
+            EObjectContent::ExpressionRef(entry) => entry.borrow().expression.eval(Rc::clone(object))?,
+            _ => return RuntimeError::eval_error(format!("joinBy field '{}' must be a string", field)).into(),
+        };
+        match value {
+            StringValue(SString(text)) => parts.push(text),
+            StringValue(SChar(c)) => parts.push(c.to_string()),
+            StringValue(_) => {}
+            other => {
+                return RuntimeError::eval_error(format!(
+                    "joinBy field '{}' must be a string, got {}",
+                    field,
+                    other.get_type()
+                ))
+                .into()
+            }
+        }
+    }
+    Ok(StringValue(SString(parts.join(&delim))))
+}
+
+pub fn validate_multi_join_by(args: Vec<ValueType>) -> Link<()> {
+    match LinkingError::expect_array_type(None, args[0].clone())? {
+        ValueType::ObjectType(_) | ValueType::UndefinedType => {}
+        other => return LinkingError::types_not_compatible(None, other, None).into(),
+    }
+    LinkingError::expect_type(Some("field".to_string()), args[1].clone(), &[StringType])?;
+    LinkingError::expect_type(None, args[2].clone(), &[StringType]).map(|_| ())
+}
+
+/// A list or a string without items
 pub fn eval_is_empty(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let Array(array) = value {
-        Ok(BooleanValue(array.is_empty()))
-    } else {
-        RuntimeError::type_not_supported(value.get_type()).into()
+    match value {
+        Array(array) => Ok(BooleanValue(array.is_empty())),
+        StringValue(SString(s)) => Ok(BooleanValue(s.is_empty())),
+        StringValue(SChar(_)) => Ok(BooleanValue(false)),
+        special @ StringValue(StringEnum::SV(_)) => Ok(special),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+/// Counts numbers in fixed-width buckets, returning `{ bucket: <lower bound>; count: <int> }` objects in ascending
+/// bucket order. Only buckets that contain at least one number are listed.
+pub fn eval_histogram(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let size = match right {
+        NumberValue(NumberEnum::Int(i)) if i > 0 => Float::from(i),
+        NumberValue(NumberEnum::Real(r)) if r > Float::ZERO => r,
+        special @ NumberValue(NumberEnum::SV(_)) => return Ok(special),
+        other => {
+            return RuntimeError::eval_error(format!("histogram bucket size must be positive, got {}", other)).into()
+        }
+    };
+
+    let values = match left {
+        Array(ArrayValue::PrimitivesArray { values, .. }) => values,
+        Array(ArrayValue::EmptyUntyped) => return Ok(Array(ArrayValue::EmptyUntyped)),
+        Array(ArrayValue::ObjectsArray { object_type, .. }) => {
+            return RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into();
+        }
+        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+
+    let mut buckets: BTreeMap<Float, Integer> = BTreeMap::new();
+    for v in values {
+        let number = match v {
+            NumberValue(NumberEnum::Int(i)) => Float::from(i),
+            NumberValue(NumberEnum::Real(r)) => r,
+            _ => return RuntimeError::type_not_supported(v.get_type()).into(),
+        };
+        let lower = number
+            .checked_div(size)
+            .and_then(|index| index.floor().checked_mul(size))
+            .ok_or_else(|| RuntimeError::eval_error(format!("histogram bucket of {} is out of range", number)))?;
+        *buckets.entry(lower.normalize()).or_default() += 1;
+    }
+
+    let mut rows = Vec::with_capacity(buckets.len());
+    for (lower, count) in buckets {
+        let row = ExecutionContext::create_isolated_context(histogram_row(NumberEnum::from(lower), count)?);
+        ExecutionContext::eval_all_fields(&row)?;
+        rows.push(Reference(row));
+    }
+    Ok(Array(ArrayValue::from_values(rows)?))
+}
+
+fn histogram_row(bucket: NumberEnum, count: Integer) -> Result<Rc<RefCell<ContextObject>>, RuntimeError> {
+    let mut builder = ContextObjectBuilder::new();
+    builder
+        .add_expression("bucket", ExpressionEnum::from(NumberValue(bucket)))
+        .and_then(|builder| builder.add_expression("count", ExpressionEnum::from(NumberValue(NumberEnum::Int(count)))))
+        .map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+    let row = builder.build();
+    linker::link_parts(Rc::clone(&row)).map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+    Ok(row)
+}
+
+pub fn return_histogram_type(_left: ValueType, _right: ValueType) -> ValueType {
+    match histogram_row(NumberEnum::Int(0), 0) {
+        Ok(row) => ValueType::list_of(ValueType::ObjectType(row)),
+        Err(_) => ValueType::list_of(ValueType::UndefinedType),
+    }
+}
+
+pub fn validate_binary_list_list(left: ValueType, right: ValueType) -> Link<()> {
+    LinkingError::expect_array_type(None, left)?;
+    LinkingError::expect_array_type(None, right).map(|_| ())
+}
+
+/// Pairs elements by position into two-element lists, stopping at the end of the shorter list
+pub fn eval_zip(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let primitives = |value: ValueEnum| match value {
+        Array(ArrayValue::EmptyUntyped) => Ok(Vec::new()),
+        Array(ArrayValue::PrimitivesArray { values, .. }) => Ok(values),
+        Array(ArrayValue::ObjectsArray { object_type, .. }) => {
+            RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
+        }
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+
+    let left = primitives(left)?;
+    let right = primitives(right)?;
+    if left.is_empty() || right.is_empty() {
+        return Ok(Array(ArrayValue::EmptyUntyped));
     }
+
+    let pairs = left
+        .into_iter()
+        .zip(right)
+        .map(|(first, second)| {
+            let (first_type, second_type) = (first.get_type(), second.get_type());
+            let item_type = if first_type == second_type { first_type } else { ValueType::UndefinedType };
+            Array(ArrayValue::PrimitivesArray { values: vec![first, second], item_type })
+        })
+        .collect();
+
+    // pairs may mix types, so the pair type is left open
+    Ok(Array(ArrayValue::PrimitivesArray { values: pairs, item_type: ListType(None) }))
 }
 
 pub fn eval_partition(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    let size = as_int(&right).ok_or_else(|| RuntimeError::type_not_supported(right.get_type()))?;
+    let size = as_int(&right)?;
 
     match left {
         Array(ArrayValue::EmptyUntyped) => {
@@ -903,7 +1392,8 @@ pub fn eval_partition(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Ru
             } else {
                 let mut idx = 0usize;
                 while idx < values.len() {
-                    let end = (((idx as i64) + size).min(values.len() as i64)) as usize;
+                    let end =
+                        usize::try_from(size).map_or(values.len(), |size| idx.saturating_add(size).min(values.len()));
                     let chunk_values: Vec<ValueEnum> = values[idx..end].to_vec();
                     chunks.push(build_array_from_parts(chunk_values, item_type.clone())?);
                     idx = end;
@@ -922,7 +1412,8 @@ pub fn eval_partition(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Ru
             } else {
                 let mut idx = 0usize;
                 while idx < values.len() {
-                    let end = (((idx as i64) + size).min(values.len() as i64)) as usize;
+                    let end =
+                        usize::try_from(size).map_or(values.len(), |size| idx.saturating_add(size).min(values.len()));
                     let chunk_values: Vec<Rc<RefCell<ExecutionContext>>> = values[idx..end].to_vec();
                     chunks.push(Array(ArrayValue::ObjectsArray {
                         values: chunk_values,
@@ -937,3 +1428,33 @@ pub fn eval_partition(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Ru
         other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(sorted: ValueEnum) -> Vec<String> {
+        let Array(ArrayValue::PrimitivesArray { values, .. }) = sorted else { panic!("sorting must return a list") };
+        values
+            .iter()
+            .map(|value| match value {
+                NumberValue(NumberEnum::Int(number)) => format!("int {}", number),
+                other => format!("real {}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_keeps_order_of_numerically_equal_items() {
+        let values = vec![
+            NumberValue(NumberEnum::Real(Float::from(2))),
+            NumberValue(NumberEnum::Int(1)),
+            NumberValue(NumberEnum::Int(2)),
+            NumberValue(NumberEnum::Real(Float::from(1))),
+        ];
+        let list = Array(ArrayValue::PrimitivesArray { values, item_type: NumberType });
+
+        assert_eq!(describe(eval_sort(list.clone()).unwrap()), ["int 1", "real 1", "real 2", "int 2"]);
+        assert_eq!(describe(eval_sort_desc(list).unwrap()), ["real 2", "int 2", "int 1", "real 1"]);
+    }
+}
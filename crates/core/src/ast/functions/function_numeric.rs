@@ -1,14 +1,17 @@
+use crate::ast::functions::function_list::value_ordering;
+use crate::ast::functions::function_string::eval_length;
 use crate::ast::token::into_valid;
 use crate::ast::Link;
 use crate::typesystem::errors::{LinkingError, RuntimeError};
 use crate::typesystem::types::number::NumberEnum;
 use crate::typesystem::types::number::NumberEnum::{Int, Real, SV};
+use crate::typesystem::types::string::StringEnum;
 use crate::typesystem::types::ValueType::{
-    DateTimeType, DateType, DurationType, ListType, NumberType, RangeType, TimeType, UndefinedType,
+    DateTimeType, DateType, DurationType, ListType, NumberType, RangeType, StringType, TimeType, UndefinedType,
 };
 use crate::typesystem::types::{Float, Integer, SpecialValueEnum, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum::{
-    Array, DateTimeValue, DateValue, DurationValue as DurationVariant, NumberValue, RangeValue, TimeValue,
+    Array, DateTimeValue, DateValue, DurationValue as DurationVariant, NumberValue, RangeValue, StringValue, TimeValue,
 };
 use crate::typesystem::values::{ArrayValue, DurationValue as DurationStruct, ValueEnum, ValueOrSv};
 use rust_decimal::prelude::*;
@@ -24,28 +27,39 @@ fn get_number(v: &ValueEnum) -> Option<NumberEnum> {
     }
 }
 
+/// Natural logarithm guarded against non-positive numbers, `function` names the caller in the special value
+fn positive_ln(function: &str, number: NumberEnum) -> Result<Float, NumberEnum> {
+    match number {
+        Real(n) if n > Float::ZERO => Ok(n.ln()),
+        // ln() on Decimal takes f64? No, MathematicalOps adds ln() to Decimal
+        Int(n) if n > 0 => Ok(Float::from(n).ln()),
+        SV(sv) => Err(SV(sv)),
+        _ => Err(SV(SpecialValueEnum::not_applicable(format!("{} of non-positive number", function)))),
+    }
+}
+
 pub fn eval_ln(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
-        NumberValue(Real(n)) => {
-            if n <= Float::ZERO {
-                Ok(NumberValue(SV(SpecialValueEnum::not_applicable("ln of non-positive number"))))
-            } else {
-                Ok(NumberValue(Real(n.ln())))
-            }
-        }
-        NumberValue(Int(n)) => {
-            if n <= 0 {
-                Ok(NumberValue(SV(SpecialValueEnum::not_applicable("ln of non-positive number"))))
-            } else {
-                // ln() on Decimal takes f64? No, MathematicalOps adds ln() to Decimal
-                Ok(NumberValue(Real(Float::from(n).ln())))
-            }
-        }
-        NumberValue(SV(sv)) => Ok(NumberValue(SV(sv))),
+        NumberValue(n) => Ok(NumberValue(positive_ln("ln", n).map_or_else(|sv| sv, Real))),
         other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
 
+/// Logarithm of `value` in an arbitrary `base`, computed as `ln(value) / ln(base)`
+pub fn eval_log_base(value: ValueEnum, base: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let (Some(number), Some(base_number)) = (get_number(&value), get_number(&base)) else {
+        return RuntimeError::type_not_supported(value.get_type()).into();
+    };
+    let (ln_value, ln_base) = match (positive_ln("logBase", number), positive_ln("logBase", base_number)) {
+        (Ok(ln_value), Ok(ln_base)) => (ln_value, ln_base),
+        (Err(sv), _) | (_, Err(sv)) => return Ok(NumberValue(sv)),
+    };
+    if ln_base.is_zero() {
+        return Ok(NumberValue(SV(SpecialValueEnum::not_applicable("logBase with base 1 is undefined"))));
+    }
+    Ok(NumberValue(ln_value.checked_div(ln_base).map(Real).unwrap_or_else(|| not_finite("logBase overflow"))))
+}
+
 pub fn eval_log10(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
         NumberValue(Real(n)) => {
@@ -67,10 +81,54 @@ pub fn eval_log10(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
+/// Decimal numbers have no NaN or infinity, results that would be non-finite overflow instead.
+/// Such results become `NotApplicable`, so that the following rounding or division propagates it.
+fn not_finite(origin: &str) -> NumberEnum {
+    SV(SpecialValueEnum::not_applicable(origin))
+}
+
+fn real_of(number: &NumberEnum) -> Option<Float> {
+    match number {
+        Int(value) => Some(Float::from(*value)),
+        Real(value) => Some(*value),
+        SV(_) => None,
+    }
+}
+
+/// Accumulates `left + right` without wrapping: an integer overflow promotes the sum to a real.
+pub fn add_promoting(left: NumberEnum, right: NumberEnum) -> NumberEnum {
+    if let (Int(a), Int(b)) = (&left, &right) {
+        if let Some(sum) = a.checked_add(*b) {
+            return Int(sum);
+        }
+    }
+    match (real_of(&left), real_of(&right)) {
+        (Some(a), Some(b)) => a.checked_add(b).map(Real).unwrap_or_else(|| not_finite("sum overflow")),
+        _ => left + right,
+    }
+}
+
+/// Accumulates `left * right` without wrapping: an integer overflow promotes the product to a real.
+pub fn multiply_promoting(left: NumberEnum, right: NumberEnum) -> NumberEnum {
+    if let (Int(a), Int(b)) = (&left, &right) {
+        if let Some(product) = a.checked_mul(*b) {
+            return Int(product);
+        }
+    }
+    match (real_of(&left), real_of(&right)) {
+        (Some(a), Some(b)) => a.checked_mul(b).map(Real).unwrap_or_else(|| not_finite("product overflow")),
+        _ => left * right,
+    }
+}
+
 pub fn eval_exp(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
-        NumberValue(Real(n)) => Ok(NumberValue(Real(n.exp()))),
-        NumberValue(Int(n)) => Ok(NumberValue(Real(Float::from(n).exp()))),
+        NumberValue(Real(n)) => {
+            Ok(NumberValue(n.checked_exp().map(Real).unwrap_or_else(|| not_finite("exp overflow"))))
+        }
+        NumberValue(Int(n)) => {
+            Ok(NumberValue(Float::from(n).checked_exp().map(Real).unwrap_or_else(|| not_finite("exp overflow"))))
+        }
         NumberValue(SV(sv)) => Ok(NumberValue(SV(sv))),
         other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
@@ -180,6 +238,51 @@ pub fn eval_atan(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
+pub fn eval_sinh(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_with_f64(value, "sinh overflow", f64::sinh)
+}
+
+pub fn eval_cosh(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_with_f64(value, "cosh overflow", f64::cosh)
+}
+
+pub fn eval_tanh(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_with_f64(value, "tanh overflow", f64::tanh)
+}
+
+pub fn eval_asinh(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    eval_with_f64(value, "asinh overflow", f64::asinh)
+}
+
+pub fn eval_acosh(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match &value {
+        NumberValue(n @ (Int(_) | Real(_))) if *n < Int(1) => {
+            Ok(NumberValue(SV(SpecialValueEnum::not_applicable("acosh input below 1"))))
+        }
+        _ => eval_with_f64(value, "acosh overflow", f64::acosh),
+    }
+}
+
+pub fn eval_atanh(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match &value {
+        NumberValue(n @ (Int(_) | Real(_))) if *n <= Int(-1) || *n >= Int(1) => {
+            Ok(NumberValue(SV(SpecialValueEnum::not_applicable("atanh input out of range (-1, 1)"))))
+        }
+        _ => eval_with_f64(value, "atanh overflow", f64::atanh),
+    }
+}
+
+/// Decimal has no hyperbolic functions, so they are computed on `f64` and converted back to a real
+fn eval_with_f64(value: ValueEnum, origin: &str, function: fn(f64) -> f64) -> Result<ValueEnum, RuntimeError> {
+    let n = match value {
+        NumberValue(Real(n)) => n.to_f64().unwrap_or(0.0),
+        NumberValue(Int(n)) => n as f64,
+        NumberValue(SV(sv)) => return Ok(NumberValue(SV(sv))),
+        other => return RuntimeError::type_not_supported(other.get_type()).into(),
+    };
+    Ok(NumberValue(Float::from_f64_retain(function(n)).map(Real).unwrap_or_else(|| not_finite(origin))))
+}
+
 pub fn eval_atan2(y_val: ValueEnum, x_val: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let (Some(y), Some(x)) = (get_number(&y_val), get_number(&x_val)) {
         match (y, x) {
@@ -196,97 +299,226 @@ pub fn eval_atan2(y_val: ValueEnum, x_val: ValueEnum) -> Result<ValueEnum, Runti
     }
 }
 
-pub fn eval_abs(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    match value {
-        NumberValue(Real(n)) => Ok(NumberValue(Real(n.abs()))),
-        NumberValue(Int(n)) => Ok(NumberValue(Int(n.abs()))),
-        NumberValue(SV(sv)) => Ok(NumberValue(SV(sv))),
-        other => RuntimeError::type_not_supported(other.get_type()).into(),
+/// Shared by the `^` operator and `pow`, a negative integer exponent promotes the result to a real.
+/// Real results that overflow or are not real numbers give `NotApplicable`.
+pub fn number_power(left: NumberEnum, right: NumberEnum) -> Result<NumberEnum, RuntimeError> {
+    if let (Some(base), Some(exponent)) = (real_of(&left), real_of(&right)) {
+        if base.is_zero() && exponent.is_sign_negative() {
+            return Ok(not_finite("power of zero to a negative exponent"));
+        }
+        if base.is_sign_negative() && !exponent.fract().is_zero() {
+            return Ok(not_finite("fractional power of negative number"));
+        }
     }
-}
 
-pub fn eval_round(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
-    let vals = into_valid(args)?;
-    // validation ensures 1 or 2 arguments
-    let number = get_number(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
-    let digits = if vals.len() == 2 {
-        match get_number(&vals[1]) {
-            Some(Int(d)) => d,
-            Some(Real(d)) => d.to_i64().unwrap_or(0),
-            Some(SV(sv)) => return Ok(NumberValue(SV(sv))),
-            _ => return RuntimeError::type_not_supported(vals[1].get_type()).into(),
+    match (left, right) {
+        (Int(left), Int(right)) => {
+            if right < 0 {
+                Ok(float_power((left as f64).powf(right as f64)))
+            } else {
+                u32::try_from(right)
+                    .ok()
+                    .and_then(|right| left.checked_pow(right))
+                    .map(Int)
+                    .ok_or_else(|| RuntimeError::eval_error("Power result is out of integer range"))
+            }
+        }
+        (Real(left), Int(right)) => {
+            Ok(left.checked_powi(right).map(Real).unwrap_or_else(|| not_finite("power overflow")))
         }
+        (Int(left), Real(right)) => Ok(float_power((left as f64).powf(right.to_f64().unwrap_or(0.0)))),
+        (Real(left), Real(right)) => Ok(left
+            .checked_powf(right.to_f64().unwrap_or(0.0))
+            .map(Real)
+            .unwrap_or_else(|| not_finite("power overflow"))),
+        _ => RuntimeError::internal_integrity_error(100).into(),
+    }
+}
+
+/// Converts a power computed with floats, which may be infinite or out of the real range
+fn float_power(value: f64) -> NumberEnum {
+    if value.is_nan() {
+        not_finite("power is not a real number")
+    } else if value == 0.0 {
+        Int(0)
     } else {
-        0
-    };
+        Float::from_f64_retain(value).map(Real).unwrap_or_else(|| not_finite("power overflow"))
+    }
+}
 
-    match number {
-        Real(n) => {
-            // round_ties_even is default for round_dp? No, use explicit strategy.
-            Ok(NumberValue(Real(n.round_dp_with_strategy(digits as u32, RoundingStrategy::MidpointNearestEven))))
+pub fn eval_pow(base: ValueEnum, exponent: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let (Some(b), Some(e)) = (get_number(&base), get_number(&exponent)) {
+        match (b, e) {
+            (SV(sv), _) | (_, SV(sv)) => Ok(NumberValue(SV(sv))),
+            (b, e) => Ok(NumberValue(number_power(b, e)?)),
         }
-        Int(n) => {
-            if digits >= 0 {
-                Ok(NumberValue(Int(n)))
-            } else {
-                // Rounding integer to negative digits (e.g. 150 round to -2 -> 200)
-                let multiplier = Float::from(10i64.pow((-digits) as u32));
-                let val = Float::from(n) / multiplier;
-                let rounded = val.round_dp_with_strategy(0, RoundingStrategy::MidpointNearestEven);
-                Ok(NumberValue(Real(rounded * multiplier)))
+    } else {
+        RuntimeError::type_not_supported(base.get_type()).into()
+    }
+}
+
+/// Greatest common divisor, always non-negative, `gcd(0, 0)` is `0`
+pub fn eval_gcd(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let (Some(a), Some(b)) = (get_number(&left), get_number(&right)) {
+        match (a, b) {
+            (SV(sv), _) | (_, SV(sv)) => Ok(NumberValue(SV(sv))),
+            (a, b) => {
+                let (a, b) = (integer_arg("gcd", a)?, integer_arg("gcd", b)?);
+                integer_result("gcd", gcd(a.unsigned_abs(), b.unsigned_abs()))
             }
         }
-        SV(sv) => Ok(NumberValue(SV(sv))),
+    } else {
+        RuntimeError::type_not_supported(left.get_type()).into()
     }
 }
 
-pub fn eval_round_up(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
-    let vals = into_valid(args)?;
-    // validation ensures 1 or 2 arguments
-    let number = get_number(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
-    let digits = if vals.len() == 2 {
-        match get_number(&vals[1]) {
-            Some(Int(d)) => d,
-            Some(Real(d)) => d.to_i64().unwrap_or(0),
-            Some(SV(sv)) => return Ok(NumberValue(SV(sv))),
-            _ => return RuntimeError::type_not_supported(vals[1].get_type()).into(),
+/// Least common multiple, always non-negative, `lcm(x, 0)` is `0`
+pub fn eval_lcm(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let (Some(a), Some(b)) = (get_number(&left), get_number(&right)) {
+        match (a, b) {
+            (SV(sv), _) | (_, SV(sv)) => Ok(NumberValue(SV(sv))),
+            (a, b) => {
+                let (a, b) = (integer_arg("lcm", a)?.unsigned_abs(), integer_arg("lcm", b)?.unsigned_abs());
+                if a == 0 || b == 0 {
+                    return Ok(NumberValue(Int(0)));
+                }
+                let multiple = (a / gcd(a, b)).saturating_mul(b);
+                integer_result("lcm", multiple)
+            }
         }
     } else {
-        0
-    };
+        RuntimeError::type_not_supported(left.get_type()).into()
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
 
+/// Integral reals such as `4.0` are accepted, anything else is not applicable for combinatorics
+fn validate_nonnegative_integer(function: &str, number: NumberEnum) -> Result<Integer, NumberEnum> {
     match number {
-        Real(n) => {
-            // Round away from zero: round_dp_with_strategy(RoundingStrategy::AwayFromZero) ?
-            // FEEL spec for round up? Usually it means Ceiling for positive?
-            // "Round up" often means away from zero in business context or Ceiling.
-            // DMN 1.3 spec: "round up(n, scale) = round(n, scale, "UP")" -> away from zero?
-            // Implementation was: sign * ceil(abs).
-            // Decimal::round_dp_with_strategy(digits, RoundingStrategy::AwayFromZero)?
-            Ok(NumberValue(Real(n.round_dp_with_strategy(digits as u32, RoundingStrategy::AwayFromZero))))
+        Int(n) if n >= 0 => Ok(n),
+        Real(n) if n.fract().is_zero() && n >= Float::ZERO => {
+            n.to_i64().ok_or_else(|| not_finite(&format!("{} overflow", function)))
         }
-        Int(n) => {
-            if digits >= 0 {
-                Ok(NumberValue(Int(n)))
-            } else {
-                let multiplier = Float::from(10i64.pow((-digits) as u32));
-                let val = Float::from(n) / multiplier;
-                let rounded = val.round_dp_with_strategy(0, RoundingStrategy::AwayFromZero);
-                Ok(NumberValue(Real(rounded * multiplier)))
-            }
+        SV(sv) => Err(SV(sv)),
+        other => Err(SV(SpecialValueEnum::not_applicable(format!(
+            "{} expects a non-negative integer, but got {}",
+            function, other
+        )))),
+    }
+}
+
+/// `n!` as an integer while it fits, then as a real
+pub fn eval_factorial(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let number = get_number(&value).ok_or_else(|| RuntimeError::type_not_supported(value.get_type()))?;
+    let n = match validate_nonnegative_integer("factorial", number) {
+        Ok(n) => n,
+        Err(sv) => return Ok(NumberValue(sv)),
+    };
+
+    let mut result = Int(1);
+    for i in 2..=n {
+        result = multiply_promoting(result, Int(i));
+        if let SV(_) = result {
+            return Ok(NumberValue(not_finite("factorial overflow")));
         }
-        SV(sv) => Ok(NumberValue(SV(sv))),
     }
+    Ok(NumberValue(result))
+}
+
+/// Number of ways to choose `k` items out of `n`, `0` when `k > n`
+pub fn eval_binomial(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let (Some(n), Some(k)) = (get_number(&left), get_number(&right)) else {
+        return RuntimeError::type_not_supported(left.get_type()).into();
+    };
+    let (n, k) = match (validate_nonnegative_integer("binomial", n), validate_nonnegative_integer("binomial", k)) {
+        (Ok(n), Ok(k)) => (n, k),
+        (Err(sv), _) | (_, Err(sv)) => return Ok(NumberValue(sv)),
+    };
+    if k > n {
+        return Ok(NumberValue(Int(0)));
+    }
+
+    // every partial product C(n - k + i, i) is an integer, so the division stays exact
+    let k = k.min(n - k);
+    let mut result = Float::ONE;
+    for i in 1..=k {
+        match result.checked_mul(Float::from(n - k + i)).and_then(|r| r.checked_div(Float::from(i))) {
+            Some(next) => result = next,
+            None => return Ok(NumberValue(not_finite("binomial overflow"))),
+        }
+    }
+    Ok(NumberValue(result.to_i64().map(Int).unwrap_or(Real(result))))
+}
+
+/// Integral reals such as `4.0` are accepted as integers
+fn integer_arg(function: &str, number: NumberEnum) -> Result<Integer, RuntimeError> {
+    match number {
+        Int(n) => Ok(n),
+        Real(n) if n.fract().is_zero() => {
+            n.to_i64().ok_or_else(|| RuntimeError::eval_error(format!("Number {} is out of integer range", n)))
+        }
+        other => Err(RuntimeError::eval_error(format!("{} expects integers, but got {}", function, other))),
+    }
+}
+
+fn integer_result(function: &str, value: u64) -> Result<ValueEnum, RuntimeError> {
+    Integer::try_from(value)
+        .map(|value| NumberValue(Int(value)))
+        .map_err(|_| RuntimeError::eval_error(format!("{} result is out of integer range", function)))
+}
+
+/// -1, 0 or 1 by the sign of the number, a negative zero such as `-0.0` is 0
+pub fn eval_sign(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        NumberValue(Real(n)) if n.is_zero() => Ok(NumberValue(Int(0))),
+        NumberValue(Real(n)) => Ok(NumberValue(Int(if n.is_sign_negative() { -1 } else { 1 }))),
+        NumberValue(Int(n)) => Ok(NumberValue(Int(n.signum()))),
+        NumberValue(SV(sv)) => Ok(NumberValue(SV(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+pub fn eval_abs(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        NumberValue(Real(n)) => Ok(NumberValue(Real(n.abs()))),
+        NumberValue(Int(n)) => Ok(NumberValue(Int(n.abs()))),
+        NumberValue(SV(sv)) => Ok(NumberValue(SV(sv))),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
+    }
+}
+
+pub fn eval_round(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    // banker's rounding
+    eval_rounding(args, RoundingStrategy::MidpointNearestEven)
+}
+
+pub fn eval_round_up(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    eval_rounding(args, RoundingStrategy::AwayFromZero)
 }
 
 pub fn eval_round_down(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    eval_rounding(args, RoundingStrategy::ToZero)
+}
+
+fn eval_rounding(
+    args: Vec<Result<ValueEnum, RuntimeError>>,
+    strategy: RoundingStrategy,
+) -> Result<ValueEnum, RuntimeError> {
     let vals = into_valid(args)?;
     // validation ensures 1 or 2 arguments
     let number = get_number(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
     let digits = if vals.len() == 2 {
         match get_number(&vals[1]) {
             Some(Int(d)) => d,
-            Some(Real(d)) => d.to_i64().unwrap_or(0),
+            Some(Real(d)) => d
+                .to_i64()
+                .ok_or_else(|| RuntimeError::eval_error(format!("Digits {} are out of integer range", d)))?,
             Some(SV(sv)) => return Ok(NumberValue(SV(sv))),
             _ => return RuntimeError::type_not_supported(vals[1].get_type()).into(),
         }
@@ -294,23 +526,28 @@ pub fn eval_round_down(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueTy
         0
     };
 
-    match number {
-        Real(n) => {
-            // Round toward zero: ToZero
-            Ok(NumberValue(Real(n.round_dp_with_strategy(digits as u32, RoundingStrategy::ToZero))))
-        }
-        Int(n) => {
-            if digits >= 0 {
-                Ok(NumberValue(Int(n)))
-            } else {
-                let multiplier = Float::from(10i64.pow((-digits) as u32));
-                let val = Float::from(n) / multiplier;
-                let rounded = val.round_dp_with_strategy(0, RoundingStrategy::ToZero);
-                Ok(NumberValue(Real(rounded * multiplier)))
-            }
+    Ok(NumberValue(round_number(number, digits, strategy)))
+}
+
+fn round_number(number: NumberEnum, digits: i64, strategy: RoundingStrategy) -> NumberEnum {
+    let value = match number {
+        SV(sv) => return SV(sv),
+        Int(n) if digits >= 0 => return Int(n),
+        Real(n) if digits >= 0 => {
+            return Real(n.round_dp_with_strategy(u32::try_from(digits).unwrap_or(u32::MAX), strategy))
         }
-        SV(sv) => Ok(NumberValue(SV(sv))),
-    }
+        Int(n) => Float::from(n),
+        Real(n) => n,
+    };
+
+    // Negative digits round to tens, hundreds and so on, e.g. round(150, -2) = 200
+    digits
+        .checked_neg()
+        .and_then(|exponent| u64::try_from(exponent).ok())
+        .and_then(|exponent| Float::TEN.checked_powu(exponent))
+        .and_then(|multiplier| (value / multiplier).round_dp_with_strategy(0, strategy).checked_mul(multiplier))
+        .map(Real)
+        .unwrap_or_else(|| not_finite("rounding overflow"))
 }
 
 pub fn eval_floor(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
@@ -340,20 +577,20 @@ pub fn eval_trunc(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     }
 }
 
+// Match f64 behavior: if rem != 0 and rem.signum != right.signum, add right.
+fn floor_mod(left: Float, right: Float) -> NumberEnum {
+    left.checked_rem(right)
+        .and_then(
+            |rem| if rem != Float::ZERO && rem.signum() != right.signum() { rem.checked_add(right) } else { Some(rem) },
+        )
+        .map(Real)
+        .unwrap_or_else(|| not_finite("modulo overflow"))
+}
+
 pub fn eval_modulo(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let (Some(a), Some(b)) = (get_number(&left), get_number(&right)) {
         match (a, b) {
             (SV(sv), _) | (_, SV(sv)) => Ok(NumberValue(SV(sv))),
-            (Real(r1), Real(r2)) => {
-                if r2 == Float::ZERO {
-                    return RuntimeError::division_by_zero().into();
-                }
-                let rem = r1 % r2;
-                // Match f64 behavior: if rem != 0 and rem.signum != r2.signum, add r2.
-                // Decimal has signum() via Signed trait.
-                let res = if rem != Float::ZERO && rem.signum() != r2.signum() { rem + r2 } else { rem };
-                Ok(NumberValue(Real(res)))
-            }
             (Int(i1), Int(i2)) => {
                 if i2 == 0 {
                     return RuntimeError::division_by_zero().into();
@@ -362,23 +599,23 @@ pub fn eval_modulo(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Runti
                 let res = if rem != 0 && rem.signum() != i2.signum() { rem + i2 } else { rem };
                 Ok(NumberValue(Int(res)))
             }
+            (Real(r1), Real(r2)) => {
+                if r2 == Float::ZERO {
+                    return RuntimeError::division_by_zero().into();
+                }
+                Ok(NumberValue(floor_mod(r1, r2)))
+            }
             (Real(r1), Int(i2)) => {
                 if i2 == 0 {
                     return RuntimeError::division_by_zero().into();
                 }
-                let r2 = Float::from(i2);
-                let rem = r1 % r2;
-                let res = if rem != Float::ZERO && rem.signum() != r2.signum() { rem + r2 } else { rem };
-                Ok(NumberValue(Real(res)))
+                Ok(NumberValue(floor_mod(r1, Float::from(i2))))
             }
             (Int(i1), Real(r2)) => {
                 if r2 == Float::ZERO {
                     return RuntimeError::division_by_zero().into();
                 }
-                let r1 = Float::from(i1);
-                let rem = r1 % r2;
-                let res = if rem != Float::ZERO && rem.signum() != r2.signum() { rem + r2 } else { rem };
-                Ok(NumberValue(Real(res)))
+                Ok(NumberValue(floor_mod(Float::from(i1), r2)))
             }
         }
     } else {
@@ -386,6 +623,12 @@ pub fn eval_modulo(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Runti
     }
 }
 
+fn floor_div(left: Float, right: Float) -> NumberEnum {
+    left.checked_div(right)
+        .map(|quotient| Real(quotient.floor()))
+        .unwrap_or_else(|| not_finite("division overflow"))
+}
+
 pub fn eval_idiv(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let (Some(a), Some(b)) = (get_number(&left), get_number(&right)) {
         match (a, b) {
@@ -394,7 +637,7 @@ pub fn eval_idiv(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Runtime
                 if r2 == Float::ZERO {
                     return RuntimeError::division_by_zero().into();
                 }
-                Ok(NumberValue(Real((r1 / r2).floor())))
+                Ok(NumberValue(floor_div(r1, r2)))
             }
             (Int(i1), Int(i2)) => {
                 if i2 == 0 {
@@ -409,13 +652,13 @@ pub fn eval_idiv(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Runtime
                 if i2 == 0 {
                     return RuntimeError::division_by_zero().into();
                 }
-                Ok(NumberValue(Real((r1 / Float::from(i2)).floor())))
+                Ok(NumberValue(floor_div(r1, Float::from(i2))))
             }
             (Int(i1), Real(r2)) => {
                 if r2 == Float::ZERO {
                     return RuntimeError::division_by_zero().into();
                 }
-                Ok(NumberValue(Real((Float::from(i1) / r2).floor())))
+                Ok(NumberValue(floor_div(Float::from(i1), r2)))
             }
         }
     } else {
@@ -469,6 +712,39 @@ pub fn eval_clamp(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -
     Ok(NumberValue(result))
 }
 
+/// Linear interpolation `a + (b - a) * t` with `t` clamped to `[0, 1]`
+pub fn eval_lerp(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    interpolate(args, true)
+}
+
+/// Linear interpolation `a + (b - a) * t` that extrapolates when `t` is outside of `[0, 1]`
+pub fn eval_lerp_unclamped(
+    args: Vec<Result<ValueEnum, RuntimeError>>,
+    _ret: ValueType,
+) -> Result<ValueEnum, RuntimeError> {
+    interpolate(args, false)
+}
+
+fn interpolate(args: Vec<Result<ValueEnum, RuntimeError>>, clamped: bool) -> Result<ValueEnum, RuntimeError> {
+    let vals = into_valid(args)?;
+    // validation ensures 3 arguments
+    let mut reals = [Float::ZERO; 3];
+    for (real, value) in reals.iter_mut().zip(vals.iter()) {
+        match get_number(value) {
+            Some(SV(sv)) => return Ok(NumberValue(SV(sv))),
+            Some(number) => *real = real_of(&number).unwrap_or_default(),
+            None => return RuntimeError::type_not_supported(value.get_type()).into(),
+        }
+    }
+
+    let [a, b, mut t] = reals;
+    if clamped {
+        t = t.clamp(Float::ZERO, Float::ONE);
+    }
+    let result = b.checked_sub(a).and_then(|span| span.checked_mul(t)).and_then(|offset| a.checked_add(offset));
+    Ok(NumberValue(result.map(Real).unwrap_or_else(|| not_finite("lerp overflow"))))
+}
+
 // Validators
 
 pub fn validate_unary_number(arg: ValueType) -> Link<()> {
@@ -482,9 +758,6 @@ pub fn validate_binary_number_number(left: ValueType, right: ValueType) -> Link<
 }
 
 pub fn validate_round_args(args: Vec<ValueType>) -> Link<()> {
-    if args.is_empty() || args.len() > 2 {
-        return LinkingError::other_error("round functions expect 1 or 2 arguments".to_string()).into();
-    }
     LinkingError::expect_type(None, args[0].clone(), &[NumberType])?;
     if args.len() == 2 {
         LinkingError::expect_type(None, args[1].clone(), &[NumberType])?;
@@ -492,23 +765,14 @@ pub fn validate_round_args(args: Vec<ValueType>) -> Link<()> {
     Ok(())
 }
 
-pub fn validate_clamp_args(args: Vec<ValueType>) -> Link<()> {
-    if args.len() != 3 {
-        return LinkingError::other_error("clamp expects 3 arguments".to_string()).into();
-    }
+/// Every argument must be a number, e.g. in `clamp` and `lerp`
+pub fn validate_number_args(args: Vec<ValueType>) -> Link<()> {
     for arg in args {
         LinkingError::expect_type(None, arg, &[NumberType])?;
     }
     Ok(())
 }
 
-pub fn validate_zero_args(args: Vec<ValueType>) -> Link<()> {
-    if !args.is_empty() {
-        return LinkingError::other_error("Expects 0 arguments".to_string()).into();
-    }
-    Ok(())
-}
-
 #[derive(Clone, Copy)]
 enum ExtremaOrder {
     Min,
@@ -532,6 +796,7 @@ enum ExtremaKind {
     Time,
     DateTime,
     Duration,
+    String,
 }
 
 fn kind_from_value_type(value_type: &ValueType) -> Option<ExtremaKind> {
@@ -541,6 +806,7 @@ fn kind_from_value_type(value_type: &ValueType) -> Option<ExtremaKind> {
         TimeType => Some(ExtremaKind::Time),
         DateTimeType => Some(ExtremaKind::DateTime),
         DurationType => Some(ExtremaKind::Duration),
+        StringType => Some(ExtremaKind::String),
         ListType(Some(inner)) => kind_from_value_type(inner),
         _ => None,
     }
@@ -553,6 +819,7 @@ fn kind_from_value(value: &ValueEnum) -> Option<ExtremaKind> {
         TimeValue(_) => Some(ExtremaKind::Time),
         DateTimeValue(_) => Some(ExtremaKind::DateTime),
         DurationVariant(_) => Some(ExtremaKind::Duration),
+        StringValue(_) => Some(ExtremaKind::String),
         _ => None,
     }
 }
@@ -580,6 +847,7 @@ fn missing_extrema_value(kind: ExtremaKind) -> ValueEnum {
         ExtremaKind::Time => TimeValue(ValueOrSv::Sv(SpecialValueEnum::missing_for(None))),
         ExtremaKind::DateTime => DateTimeValue(ValueOrSv::Sv(SpecialValueEnum::missing_for(None))),
         ExtremaKind::Duration => DurationVariant(ValueOrSv::Sv(SpecialValueEnum::missing_for(None))),
+        ExtremaKind::String => StringValue(StringEnum::SV(SpecialValueEnum::missing_for(None))),
     }
 }
 
@@ -612,6 +880,16 @@ fn should_replace_duration(
     }
 }
 
+/// Strings are compared lexicographically, special values are treated like in `should_replace_value_or_sv_ord`
+fn should_replace_string(order: ExtremaOrder, current: &ValueEnum, candidate: &ValueEnum) -> bool {
+    match (current, candidate) {
+        (StringValue(StringEnum::SV(_)), StringValue(StringEnum::SV(_))) => false,
+        (StringValue(StringEnum::SV(_)), _) => matches!(order, ExtremaOrder::Max),
+        (_, StringValue(StringEnum::SV(_))) => matches!(order, ExtremaOrder::Min),
+        _ => order.should_replace(value_ordering(current, candidate)),
+    }
+}
+
 fn eval_extrema_all(
     values: Vec<ValueEnum>,
     list_type: ValueType,
@@ -718,6 +996,24 @@ fn eval_extrema_all(
 
             Ok(best.map(DurationVariant).unwrap_or_else(|| missing_extrema_value(ExtremaKind::Duration)))
         }
+        ExtremaKind::String => {
+            let mut best: Option<ValueEnum> = None;
+            for value in values {
+                match value {
+                    StringValue(_) => match &mut best {
+                        Some(current) => {
+                            if should_replace_string(order, current, &value) {
+                                *current = value;
+                            }
+                        }
+                        None => best = Some(value),
+                    },
+                    other => return RuntimeError::type_not_supported(other.get_type()).into(),
+                }
+            }
+
+            Ok(best.unwrap_or_else(|| missing_extrema_value(ExtremaKind::String)))
+        }
     }
 }
 
@@ -735,7 +1031,9 @@ pub fn eval_max_multi(
 
 pub fn eval_max(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
-        NumberValue(_) | DateValue(_) | TimeValue(_) | DateTimeValue(_) | DurationVariant(_) => Ok(value),
+        NumberValue(_) | DateValue(_) | TimeValue(_) | DateTimeValue(_) | DurationVariant(_) | StringValue(_) => {
+            Ok(value)
+        }
         Array(ArrayValue::ObjectsArray { values: _, object_type }) => {
             RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
         }
@@ -763,7 +1061,9 @@ pub fn eval_min_multi(
 
 pub fn eval_min(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     match value {
-        NumberValue(_) | DateValue(_) | TimeValue(_) | DateTimeValue(_) | DurationVariant(_) => Ok(value),
+        NumberValue(_) | DateValue(_) | TimeValue(_) | DateTimeValue(_) | DurationVariant(_) | StringValue(_) => {
+            Ok(value)
+        }
         Array(ArrayValue::ObjectsArray { values: _, object_type }) => {
             RuntimeError::type_not_supported(ValueType::list_of(ValueType::ObjectType(object_type))).into()
         }
@@ -791,7 +1091,7 @@ pub fn eval_sum_all(values: Vec<ValueEnum>, list_type: ValueType) -> Result<Valu
     for token in values {
         if let NumberValue(number) = token {
             acc = Some(match acc {
-                Some(existing) => existing + number,
+                Some(existing) => add_promoting(existing, number),
                 None => number,
             });
         } else {
@@ -813,7 +1113,9 @@ fn sum_duration_values(values: Vec<ValueEnum>) -> Result<ValueEnum, RuntimeError
     for value in values {
         match value {
             DurationVariant(ValueOrSv::Value(duration)) => {
-                seconds_total += duration.signed_seconds();
+                seconds_total = seconds_total
+                    .checked_add(duration.signed_seconds())
+                    .ok_or_else(|| RuntimeError::eval_error("duration sum overflow".to_string()))?;
                 has_value = true;
             }
             DurationVariant(ValueOrSv::Sv(sv)) => {
@@ -827,7 +1129,9 @@ fn sum_duration_values(values: Vec<ValueEnum>) -> Result<ValueEnum, RuntimeError
     if let Some(sv) = special {
         Ok(DurationVariant(ValueOrSv::Sv(sv)))
     } else if has_value {
-        let result = DurationStruct::from_signed_seconds(seconds_total)?;
+        // the total may exceed the representable duration range even when every item fits
+        let result = DurationStruct::from_signed_seconds(seconds_total)
+            .map_err(|_| RuntimeError::eval_error("duration sum overflow".to_string()))?;
         Ok(DurationVariant(ValueOrSv::Value(result)))
     } else {
         Ok(DurationVariant(ValueOrSv::Sv(SpecialValueEnum::missing_for(None))))
@@ -866,6 +1170,9 @@ pub fn eval_count(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
             Ok(NumberValue(NumberEnum::Int(count as Integer)))
         }
         RangeValue(range) => Ok(ValueEnum::from(range.count() as Integer)),
+        // a string counts its characters, same as `length`
+        StringValue(StringEnum::SV(sv)) => Ok(NumberValue(SV(sv))),
+        StringValue(_) => eval_length(value),
         other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
@@ -898,24 +1205,24 @@ pub fn list_item_as_second_arg(left: ValueType, right: ValueType) -> Link<()> {
     Ok(())
 }
 
-pub fn number_range_or_any_list(value_type: ValueType) -> Link<()> {
+pub fn number_range_string_or_any_list(value_type: ValueType) -> Link<()> {
     match &value_type {
-        NumberType | RangeType | ListType(_) => Ok(()),
+        NumberType | RangeType | StringType | ListType(_) => Ok(()),
         _ => LinkingError::types_not_compatible(
             None,
             value_type,
-            Some(vec![NumberType, RangeType, ListType(Some(Box::new(NumberType)))]),
+            Some(vec![NumberType, RangeType, StringType, ListType(Some(Box::new(NumberType)))]),
         )
         .into(),
     }
 }
 
 fn is_extrema_scalar_type(value_type: &ValueType) -> bool {
-    matches!(value_type, NumberType | DateType | TimeType | DateTimeType | DurationType)
+    matches!(value_type, NumberType | DateType | TimeType | DateTimeType | DurationType | StringType)
 }
 
 pub fn validate_extrema_input(value_type: ValueType) -> Link<()> {
-    if matches!(value_type, NumberType | RangeType | DateType | TimeType | DateTimeType | DurationType) {
+    if matches!(value_type, NumberType | RangeType | DateType | TimeType | DateTimeType | DurationType | StringType) {
         return Ok(());
     }
 
@@ -937,11 +1244,13 @@ pub fn validate_extrema_input(value_type: ValueType) -> Link<()> {
             TimeType,
             DateTimeType,
             DurationType,
+            StringType,
             ListType(Some(Box::new(NumberType))),
             ListType(Some(Box::new(DateType))),
             ListType(Some(Box::new(TimeType))),
             ListType(Some(Box::new(DateTimeType))),
             ListType(Some(Box::new(DurationType))),
+            ListType(Some(Box::new(StringType))),
             ListType(None),
         ]),
     )
@@ -966,7 +1275,7 @@ pub fn validate_multi_extrema_args(args: Vec<ValueType>) -> Link<()> {
             return LinkingError::types_not_compatible(
                 None,
                 arg,
-                Some(vec![NumberType, DateType, TimeType, DateTimeType, DurationType]),
+                Some(vec![NumberType, DateType, TimeType, DateTimeType, DurationType, StringType]),
             )
             .into();
         }
@@ -4,9 +4,9 @@ use crate::ast::Link;
 use crate::link::node_data::ContentHolder;
 use crate::typesystem::errors::{LinkingError, RuntimeError};
 use crate::typesystem::types::number::NumberEnum;
-use crate::typesystem::types::string::StringEnum::{Char as SChar, String as SString};
+use crate::typesystem::types::string::StringEnum::{Char as SChar, String as SString, SV as SSv};
 use crate::typesystem::types::ValueType::{BooleanType, ListType as VTList, NumberType, StringType};
-use crate::typesystem::types::{TypedValue, ValueType};
+use crate::typesystem::types::{SpecialValueEnum, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum::{BooleanValue, NumberValue, StringValue};
 use crate::typesystem::values::{ArrayValue, ValueEnum};
 #[cfg(feature = "base64_functions")]
@@ -24,12 +24,14 @@ fn as_string(v: &ValueEnum) -> Option<String> {
     }
 }
 
-fn as_int(v: &ValueEnum) -> Option<i64> {
+fn as_int(v: &ValueEnum) -> Result<i64, RuntimeError> {
     match v {
-        NumberValue(NumberEnum::Int(i)) => Some(*i),
-        NumberValue(NumberEnum::Real(r)) => r.to_i64(),
-        NumberValue(NumberEnum::SV(_)) => Some(0),
-        _ => None,
+        NumberValue(NumberEnum::Int(i)) => Ok(*i),
+        NumberValue(NumberEnum::Real(r)) => {
+            r.to_i64().ok_or_else(|| RuntimeError::eval_error(format!("Number {} is out of integer range", r)))
+        }
+        NumberValue(NumberEnum::SV(_)) => Ok(0),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
 
@@ -50,10 +52,22 @@ pub fn validate_binary_string_number(left: ValueType, right: ValueType) -> Link<
 pub fn validate_binary_string_any(left: ValueType, _right: ValueType) -> Link<()> {
     LinkingError::expect_type(None, left, &[StringType]).map(|_| ())
 }
-pub fn validate_multi_substring(args: Vec<ValueType>) -> Link<()> {
-    if !(args.len() == 2 || args.len() == 3) {
-        return LinkingError::other_error("substring expects 2 or 3 arguments".to_string()).into();
+pub fn validate_unary_object(arg: ValueType) -> Link<()> {
+    match arg {
+        ValueType::ObjectType(_) => Ok(()),
+        other => LinkingError::types_not_compatible(None, other, None).into(),
     }
+}
+pub fn validate_binary_object_string(left: ValueType, right: ValueType) -> Link<()> {
+    validate_unary_object(left)?;
+    LinkingError::expect_type(None, right, &[StringType]).map(|_| ())
+}
+pub fn validate_multi_extract(args: Vec<ValueType>) -> Link<()> {
+    LinkingError::expect_type(None, args[0].clone(), &[StringType])?;
+    LinkingError::expect_type(Some("pattern".to_string()), args[1].clone(), &[StringType])?;
+    LinkingError::expect_type(Some("group".to_string()), args[2].clone(), &[NumberType]).map(|_| ())
+}
+pub fn validate_multi_substring(args: Vec<ValueType>) -> Link<()> {
     LinkingError::expect_type(None, args[0].clone(), &[StringType])?;
     for (i, t) in args.iter().enumerate().skip(1) {
         LinkingError::expect_type(Some(format!("arg{}", i + 1)), t.clone(), &[NumberType])?;
@@ -61,9 +75,6 @@ pub fn validate_multi_substring(args: Vec<ValueType>) -> Link<()> {
     Ok(())
 }
 pub fn validate_multi_replace(args: Vec<ValueType>) -> Link<()> {
-    if !(args.len() == 3 || args.len() == 4) {
-        return LinkingError::other_error("replace expects 3 or 4 arguments".to_string()).into();
-    }
     for t in args.iter().take(3) {
         LinkingError::expect_type(None, t.clone(), &[StringType])?;
     }
@@ -78,10 +89,10 @@ pub fn validate_multi_from_char_code(args: Vec<ValueType>) -> Link<()> {
     }
     Ok(())
 }
+pub fn validate_multi_format(args: Vec<ValueType>) -> Link<()> {
+    LinkingError::expect_type(None, args[0].clone(), &[StringType]).map(|_| ())
+}
 pub fn validate_multi_pad(args: Vec<ValueType>) -> Link<()> {
-    if args.len() != 3 {
-        return LinkingError::other_error("padStart/padEnd expects 3 arguments".to_string()).into();
-    }
     LinkingError::expect_type(None, args[0].clone(), &[StringType])?;
     LinkingError::expect_type(None, args[1].clone(), &[NumberType])?;
     LinkingError::expect_type(None, args[2].clone(), &[StringType])?;
@@ -99,6 +110,9 @@ pub fn return_string_type_binary(_: ValueType, _: ValueType) -> ValueType {
 pub fn return_string_type_multi(_args: &[ValueType]) -> ValueType {
     StringType
 }
+pub fn return_string_list_type(_: ValueType) -> ValueType {
+    VTList(Some(Box::new(StringType)))
+}
 pub fn return_string_list_type_binary(_: ValueType, _: ValueType) -> ValueType {
     VTList(Some(Box::new(StringType)))
 }
@@ -115,6 +129,19 @@ pub fn eval_to_string(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
         other => Ok(StringValue(SString(other.to_string()))),
     }
 }
+/// Type name reported by `typeOf` and `typeOfField`, such as `'number'`, `'date'`, `'list'` or `'object'`
+fn type_name(value_type: &ValueType) -> String {
+    match value_type {
+        VTList(_) => "list".to_string(),
+        ValueType::ObjectType(_) => "object".to_string(),
+        other => other.to_string(),
+    }
+}
+/// Runtime type name of a value, any special value is `'missing'`
+pub fn eval_type_of(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let name = if value.special_value().is_some() { "missing".to_string() } else { type_name(&value.get_type()) };
+    Ok(StringValue(SString(name)))
+}
 pub fn eval_length(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let Some(s) = as_string(&value) {
         Ok(NumberValue(NumberEnum::from(s.chars().count() as i64)))
@@ -122,26 +149,36 @@ pub fn eval_length(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
         RuntimeError::type_not_supported(value.get_type()).into()
     }
 }
-pub fn eval_to_upper(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let Some(s) = as_string(&value) {
-        Ok(StringValue(SString(s.to_uppercase())))
-    } else {
-        RuntimeError::type_not_supported(value.get_type()).into()
+/// Applies a text conversion, a single character stays a character unless it converts to several
+fn map_text(value: ValueEnum, convert: impl Fn(&str) -> String) -> Result<ValueEnum, RuntimeError> {
+    match value {
+        StringValue(SString(s)) => Ok(StringValue(SString(convert(&s)))),
+        StringValue(SChar(c)) => {
+            let converted = convert(c.encode_utf8(&mut [0; 4]));
+            let mut chars = converted.chars();
+            match (chars.next(), chars.next()) {
+                (Some(single), None) => Ok(StringValue(SChar(single))),
+                _ => Ok(StringValue(SString(converted))),
+            }
+        }
+        StringValue(SSv(_)) => Ok(value),
+        other => RuntimeError::type_not_supported(other.get_type()).into(),
     }
 }
+pub fn eval_to_upper(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    map_text(value, str::to_uppercase)
+}
 pub fn eval_to_lower(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let Some(s) = as_string(&value) {
-        Ok(StringValue(SString(s.to_lowercase())))
-    } else {
-        RuntimeError::type_not_supported(value.get_type()).into()
-    }
+    map_text(value, str::to_lowercase)
 }
 pub fn eval_trim(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let Some(s) = as_string(&value) {
-        Ok(StringValue(SString(s.trim().to_string())))
-    } else {
-        RuntimeError::type_not_supported(value.get_type()).into()
-    }
+    map_text(value, |s| s.trim().to_string())
+}
+pub fn eval_trim_start(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    map_text(value, |s| s.trim_start().to_string())
+}
+pub fn eval_trim_end(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    map_text(value, |s| s.trim_end().to_string())
 }
 pub fn eval_contains(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     if let (Some(h), Some(n)) = (as_string(&left), as_string(&right)) {
@@ -150,7 +187,37 @@ pub fn eval_contains(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, Run
         RuntimeError::type_not_supported(left.get_type()).into()
     }
 }
+/// Special value of the first operand that has one. Booleans cannot hold special values, so predicates return it
+/// as a string special value, the same way omitted booleans are represented.
+fn special_operand(left: &ValueEnum, right: &ValueEnum) -> Option<ValueEnum> {
+    [left, right].into_iter().find(|v| matches!(v, StringValue(SSv(_)))).cloned()
+}
+/// Both strings lowercased for Unicode case-insensitive matching
+fn case_folded_pair(left: &ValueEnum, right: &ValueEnum) -> Result<(String, String), RuntimeError> {
+    match (as_string(left), as_string(right)) {
+        (Some(l), Some(r)) => Ok((l.to_lowercase(), r.to_lowercase())),
+        (None, _) => RuntimeError::type_not_supported(left.get_type()).into(),
+        (_, None) => RuntimeError::type_not_supported(right.get_type()).into(),
+    }
+}
+pub fn eval_contains_ignore_case(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let Some(special) = special_operand(&left, &right) {
+        return Ok(special);
+    }
+    let (haystack, needle) = case_folded_pair(&left, &right)?;
+    Ok(BooleanValue(haystack.contains(&needle)))
+}
+pub fn eval_equals_ignore_case(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let Some(special) = special_operand(&left, &right) {
+        return Ok(special);
+    }
+    let (l, r) = case_folded_pair(&left, &right)?;
+    Ok(BooleanValue(l == r))
+}
 pub fn eval_starts_with(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let Some(special) = special_operand(&left, &right) {
+        return Ok(special);
+    }
     if let (Some(h), Some(p)) = (as_string(&left), as_string(&right)) {
         Ok(BooleanValue(h.starts_with(&p)))
     } else {
@@ -158,44 +225,33 @@ pub fn eval_starts_with(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum,
     }
 }
 pub fn eval_ends_with(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let Some(special) = special_operand(&left, &right) {
+        return Ok(special);
+    }
     if let (Some(h), Some(s)) = (as_string(&left), as_string(&right)) {
         Ok(BooleanValue(h.ends_with(&s)))
     } else {
         RuntimeError::type_not_supported(left.get_type()).into()
     }
 }
+/// Characters from a 1-based `start` (negative counts from the end) for an optional length, clamped to the string
+/// the same way as `sublist`
 pub fn eval_substring(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
-    let vals = into_valid(args)?;
+    let mut vals = into_valid(args)?;
     // validation ensures 2 or 3 arguments
-    let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
-    let start = as_int(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
-    let len_opt = if vals.len() == 3 {
-        Some(as_int(&vals[2]).ok_or_else(|| RuntimeError::type_not_supported(vals[2].get_type()))?)
-    } else {
-        None
-    };
-    let chars: Vec<char> = s.chars().collect();
-    let n = chars.len() as i64;
-    let mut idx = if start > 0 {
-        start - 1
-    } else if start < 0 {
-        n + start
-    } else {
-        0
-    };
-    if idx < 0 {
-        idx = 0;
-    }
-    if idx > n {
-        idx = n;
+    if matches!(vals[0], StringValue(SSv(_))) {
+        return Ok(vals.swap_remove(0));
     }
-    let end = match len_opt {
-        Some(l) if l >= 0 => (idx + l).min(n),
-        Some(l) if l < 0 => (idx + l).max(0),
-        _ => n,
+    let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
+    let start = as_int(&vals[1])?;
+    let len_opt = if vals.len() == 3 { Some(as_int(&vals[2])?) } else { None };
+    let n = s.chars().count() as i64;
+    let i = if start < 0 { n.saturating_add(start) } else { start.saturating_sub(1) }.clamp(0, n);
+    let j = match len_opt {
+        Some(l) => i.saturating_add(l).clamp(i, n),
+        None => n,
     };
-    let (i, j) = (idx as usize, end as usize);
-    let out: String = if j >= i { chars[i..j].iter().collect() } else { chars[j..i].iter().collect() };
+    let out: String = s.chars().take(j as usize).skip(i as usize).collect();
     Ok(StringValue(SString(out)))
 }
 pub fn eval_substring_before(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
@@ -225,24 +281,40 @@ pub fn eval_substring_after(left: ValueEnum, right: ValueEnum) -> Result<ValueEn
 // Basic, non-regex split/replace operations
 // -----------------------------------------
 
+/// An empty delimiter splits into characters and an empty string gives `['']`.
+/// A special value in any argument is kept as the only element.
 pub fn eval_split(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let (Some(h), Some(pat)) = (as_string(&left), as_string(&right)) {
-        let parts: Vec<ValueEnum> = h.split(&pat).map(|s| StringValue(SString(s.to_string()))).collect();
-        Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values: parts, item_type: StringType }))
-    } else {
-        RuntimeError::type_not_supported(left.get_type()).into()
-    }
+    let parts: Vec<ValueEnum> = match (&left, &right) {
+        (StringValue(SSv(_)), _) => vec![left],
+        (_, StringValue(SSv(_))) => vec![right],
+        _ => match (as_string(&left), as_string(&right)) {
+            // an empty string has no characters, but splitting it still gives one empty part
+            (Some(h), Some(pat)) if pat.is_empty() && h.is_empty() => vec![StringValue(SString(h))],
+            (Some(h), Some(pat)) if pat.is_empty() => h.chars().map(|c| StringValue(SString(c.to_string()))).collect(),
+            (Some(h), Some(pat)) => h.split(&pat).map(|s| StringValue(SString(s.to_string()))).collect(),
+            _ => return RuntimeError::type_not_supported(left.get_type()).into(),
+        },
+    };
+    Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values: parts, item_type: StringType }))
 }
 
 pub fn eval_replace(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
-    let vals = into_valid(args)?;
+    let mut vals = into_valid(args)?;
     // validation ensures 3 or 4 arguments
+    if let Some(sv) = vals.iter().position(|v| matches!(v, StringValue(SSv(_)))) {
+        return Ok(vals.swap_remove(sv));
+    }
     let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
     let pattern = as_string(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
     let repl = as_string(&vals[2]).ok_or_else(|| RuntimeError::type_not_supported(vals[2].get_type()))?;
 
-    // Fast path: standard replace or empty pattern
-    if vals.len() == 3 || pattern.is_empty() {
+    // Nothing to find, so nothing is replaced
+    if pattern.is_empty() {
+        return Ok(StringValue(SString(s)));
+    }
+
+    // Fast path: standard replace
+    if vals.len() == 3 {
         return Ok(StringValue(SString(s.replace(&pattern, &repl))));
     }
 
@@ -263,9 +335,6 @@ pub fn eval_replace(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType)
         {
             let s_lower = s.to_ascii_lowercase();
             let pat_lower = pattern.to_ascii_lowercase();
-            if pat_lower.is_empty() {
-                return Ok(StringValue(SString(s.replace(&pattern, &repl))));
-            }
             let mut out = String::with_capacity(s.len());
             let mut i: usize = 0;
             let pat_len = pattern.len();
@@ -472,18 +541,100 @@ pub fn eval_regex_replace(
     RuntimeError::internal_integrity_error(201).into()
 }
 
+#[cfg(feature = "regex_functions")]
+pub fn eval_matches(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let Some(special) = special_operand(&left, &right) {
+        return Ok(special);
+    }
+    match (as_string(&left), as_string(&right)) {
+        (Some(h), Some(pat)) => {
+            let re = RegexBuilder::new(&pat).build().map_err(|e| RuntimeError::eval_error(e.to_string()))?;
+            Ok(BooleanValue(re.is_match(&h)))
+        }
+        _ => RuntimeError::type_not_supported(left.get_type()).into(),
+    }
+}
+
+// WASM (web/node) implementation without Rust regex crate: delegates to host RegExp
+#[cfg(all(not(feature = "regex_functions"), target_arch = "wasm32", feature = "wasm"))]
+pub fn eval_matches(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    if let Some(special) = special_operand(&left, &right) {
+        return Ok(special);
+    }
+    match (as_string(&left), as_string(&right)) {
+        (Some(h), Some(pat)) => match crate::wasm::regex_test_js(&h, &pat) {
+            Ok(found) => Ok(BooleanValue(found)),
+            Err(e) => RuntimeError::eval_error(e).into(),
+        },
+        _ => RuntimeError::type_not_supported(left.get_type()).into(),
+    }
+}
+
+// Fallback for non-WASM builds when regex feature is disabled
+#[cfg(all(not(feature = "regex_functions"), not(all(target_arch = "wasm32", feature = "wasm"))))]
+pub fn eval_matches(_left: ValueEnum, _right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    RuntimeError::internal_integrity_error(204).into()
+}
+
+/// Captured group of the first match, `Missing` when nothing matches or the group did not participate
+#[cfg(feature = "regex_functions")]
+pub fn eval_extract(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    let mut vals = into_valid(args)?;
+    // validation ensures 3 arguments
+    if let Some(sv) = vals.iter().take(2).position(|v| matches!(v, StringValue(SSv(_)))) {
+        return Ok(vals.swap_remove(sv));
+    }
+    let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
+    let pattern = as_string(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
+    let group = as_int(&vals[2])?;
+    let re = RegexBuilder::new(&pattern).build().map_err(|e| RuntimeError::eval_error(e.to_string()))?;
+    let captured = usize::try_from(group).ok().and_then(|group| re.captures(&s)?.get(group));
+    Ok(match captured {
+        Some(found) => StringValue(SString(found.as_str().to_string())),
+        None => StringValue(SSv(SpecialValueEnum::missing_for(None))),
+    })
+}
+
+// WASM (web/node) implementation without Rust regex crate: delegates to host RegExp
+#[cfg(all(not(feature = "regex_functions"), target_arch = "wasm32", feature = "wasm"))]
+pub fn eval_extract(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    let mut vals = into_valid(args)?;
+    // validation ensures 3 arguments
+    if let Some(sv) = vals.iter().take(2).position(|v| matches!(v, StringValue(SSv(_)))) {
+        return Ok(vals.swap_remove(sv));
+    }
+    let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
+    let pattern = as_string(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
+    let group = as_int(&vals[2])?;
+    let Ok(group) = u32::try_from(group) else {
+        return Ok(StringValue(SSv(SpecialValueEnum::missing_for(None))));
+    };
+    match crate::wasm::regex_extract_js(&s, &pattern, group) {
+        Ok(Some(found)) => Ok(StringValue(SString(found))),
+        Ok(None) => Ok(StringValue(SSv(SpecialValueEnum::missing_for(None)))),
+        Err(e) => RuntimeError::eval_error(e).into(),
+    }
+}
+
+// Fallback for non-WASM builds when regex feature is disabled
+#[cfg(all(not(feature = "regex_functions"), not(all(target_arch = "wasm32", feature = "wasm"))))]
+pub fn eval_extract(_args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    RuntimeError::internal_integrity_error(205).into()
+}
+
 pub fn eval_char_at(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let (Some(s), Some(i)) = (as_string(&left), as_int(&right)) {
-        let mut iter = s.chars();
-        let ch = iter.nth(i as usize).unwrap_or('\0');
+    if let Some(s) = as_string(&left) {
+        let i = as_int(&right)?;
+        let ch = usize::try_from(i).ok().and_then(|i| s.chars().nth(i)).unwrap_or('\0');
         Ok(StringValue(SString(if ch == '\0' { String::new() } else { ch.to_string() })))
     } else {
         RuntimeError::type_not_supported(left.get_type()).into()
     }
 }
 pub fn eval_char_code_at(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let (Some(s), Some(i)) = (as_string(&left), as_int(&right)) {
-        let code = s.chars().nth(i as usize).map(|c| c as u32).unwrap_or(0);
+    if let Some(s) = as_string(&left) {
+        let i = as_int(&right)?;
+        let code = usize::try_from(i).ok().and_then(|i| s.chars().nth(i)).map(|c| c as u32).unwrap_or(0);
         Ok(NumberValue(NumberEnum::from(code as i64)))
     } else {
         RuntimeError::type_not_supported(left.get_type()).into()
@@ -518,8 +669,8 @@ pub fn eval_from_char_code(
     let vals = into_valid(args)?;
     let mut s = String::new();
     for v in vals {
-        if let Some(i) = as_int(&v) {
-            if let Some(ch) = char::from_u32(i as u32) {
+        if let Ok(i) = as_int(&v) {
+            if let Some(ch) = u32::try_from(i).ok().and_then(char::from_u32) {
                 s.push(ch);
             }
         }
@@ -529,12 +680,13 @@ pub fn eval_from_char_code(
 pub fn eval_pad_start(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
     let vals = into_valid(args)?;
     let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
-    let target = as_int(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
+    let target = as_int(&vals[1])?;
     let pad = as_string(&vals[2]).unwrap_or(" ".to_string());
     let mut out = s.clone();
-    if target as usize > s.chars().count() {
+    let target = usize::try_from(target).unwrap_or(0);
+    if target > s.chars().count() {
         let pad_ch = pad.chars().next().unwrap_or(' ');
-        let need = target as usize - s.chars().count();
+        let need = target - s.chars().count();
         let prefix: String = std::iter::repeat_n(pad_ch, need).collect();
         out = format!("{}{}", prefix, s);
     }
@@ -543,20 +695,65 @@ pub fn eval_pad_start(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueTyp
 pub fn eval_pad_end(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
     let vals = into_valid(args)?;
     let s = as_string(&vals[0]).ok_or_else(|| RuntimeError::type_not_supported(vals[0].get_type()))?;
-    let target = as_int(&vals[1]).ok_or_else(|| RuntimeError::type_not_supported(vals[1].get_type()))?;
+    let target = as_int(&vals[1])?;
     let pad = as_string(&vals[2]).unwrap_or(" ".to_string());
     let mut out = s.clone();
-    if target as usize > s.chars().count() {
+    let target = usize::try_from(target).unwrap_or(0);
+    if target > s.chars().count() {
         let pad_ch = pad.chars().next().unwrap_or(' ');
-        let need = target as usize - s.chars().count();
+        let need = target - s.chars().count();
         let suffix: String = std::iter::repeat_n(pad_ch, need).collect();
         out = format!("{}{}", s, suffix);
     }
     Ok(StringValue(SString(out)))
 }
+/// Replaces `{}` placeholders in order with the remaining arguments, `{{` and `}}` render literal braces
+pub fn eval_format(args: Vec<Result<ValueEnum, RuntimeError>>, _ret: ValueType) -> Result<ValueEnum, RuntimeError> {
+    let mut vals = into_valid(args)?.into_iter();
+    // validation ensures the template is present
+    let template = match vals.next() {
+        Some(StringValue(SSv(sv))) => return Ok(StringValue(SSv(sv))),
+        Some(value) => as_string(&value).ok_or_else(|| RuntimeError::type_not_supported(value.get_type()))?,
+        None => return RuntimeError::eval_error("format expects a template".to_string()).into(),
+    };
+    let values: Vec<ValueEnum> = vals.collect();
+
+    let mut out = String::with_capacity(template.len());
+    let mut placeholders = 0;
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                out.push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                match values.get(placeholders) {
+                    Some(StringValue(SString(text))) => out.push_str(text),
+                    Some(StringValue(SChar(ch))) => out.push(*ch),
+                    Some(value) => out.push_str(&value.to_string()),
+                    None => {}
+                }
+                placeholders += 1;
+            }
+            _ => out.push(c),
+        }
+    }
+
+    if placeholders != values.len() {
+        return RuntimeError::eval_error(format!(
+            "format expects {} arguments for the template placeholders, got {}",
+            placeholders,
+            values.len()
+        ))
+        .into();
+    }
+    Ok(StringValue(SString(out)))
+}
 pub fn eval_repeat(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
-    if let (Some(s), Some(n)) = (as_string(&left), as_int(&right)) {
-        let times = if n < 0 { 0 } else { n as usize };
+    if let Some(s) = as_string(&left) {
+        let times = usize::try_from(as_int(&right)?).unwrap_or(0);
         Ok(StringValue(SString(s.repeat(times))))
     } else {
         RuntimeError::type_not_supported(left.get_type()).into()
@@ -578,6 +775,38 @@ pub fn eval_sanitize_filename(value: ValueEnum) -> Result<ValueEnum, RuntimeErro
         RuntimeError::type_not_supported(value.get_type()).into()
     }
 }
+pub fn eval_fields_of(value: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let ValueEnum::Reference(ctx) = value else {
+        return RuntimeError::type_not_supported(value.get_type()).into();
+    };
+    let names = ctx
+        .borrow()
+        .get_field_names()
+        .into_iter()
+        .map(|name| StringValue(SString(name.to_string())))
+        .collect();
+    Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values: names, item_type: StringType }))
+}
+/// Type name of an object field, named the same way as by `typeOf`, `NotFound` for unknown fields
+pub fn eval_type_of_field(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    let ValueEnum::Reference(ctx) = left else {
+        return RuntimeError::type_not_supported(left.get_type()).into();
+    };
+    let name = match right {
+        StringValue(SSv(_)) => return Ok(right),
+        other => as_string(&other).ok_or_else(|| RuntimeError::type_not_supported(other.get_type()))?,
+    };
+    let field_type = match ctx.borrow().get(&name) {
+        Ok(EObjectContent::ExpressionRef(entry)) => match entry.borrow().field_type.clone() {
+            Ok(field_type) => field_type,
+            Err(_) => entry.borrow().expression.eval(Rc::clone(&ctx))?.get_type(),
+        },
+        Ok(EObjectContent::ObjectRef(object)) => object.borrow().get_type(),
+        Ok(EObjectContent::ConstantValue(value)) => value.get_type(),
+        _ => return Ok(StringValue(SSv(SpecialValueEnum::not_found(name)))),
+    };
+    Ok(StringValue(SString(type_name(&field_type))))
+}
 pub fn eval_interpolate(left: ValueEnum, right: ValueEnum) -> Result<ValueEnum, RuntimeError> {
     let template = as_string(&left).ok_or_else(|| RuntimeError::type_not_supported(left.get_type()))?;
     if let ValueEnum::Reference(ctx) = right {
@@ -16,6 +16,7 @@ use std::cell::RefCell;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
+use time::OffsetDateTime;
 
 use phf::phf_map;
 
@@ -40,13 +41,20 @@ pub type BinaryFunctionDefinition = FunctionHolder<
     fn(ValueType, ValueType) -> ValueType,
 >;
 
-/// validation method will receive all item types for all arguments
+/// validation method will receive all item types for all arguments.
 pub type MultiFunctionDefinition = FunctionHolder<
     fn(Vec<Result<ValueEnum, RuntimeError>>, ValueType) -> Result<ValueEnum, RuntimeError>,
     fn(Vec<ValueType>) -> Link<()>,
     fn(&[ValueType]) -> ValueType,
 >;
 
+/// Functions that read the runtime clock. They receive the evaluation instant instead of argument values.
+pub type ClockFunctionDefinition = FunctionHolder<
+    fn(OffsetDateTime) -> Result<ValueEnum, RuntimeError>,
+    fn(Vec<ValueType>) -> Link<()>,
+    fn(&[ValueType]) -> ValueType,
+>;
+
 pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinition> = phf_map! {
     // Generic stringification
     "toString" => UnaryFunctionDefinition {
@@ -55,10 +63,29 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: |_| Ok(()),
         return_type: |_| ValueType::StringType,
     },
+    "typeOf" => UnaryFunctionDefinition {
+        name: "typeOf",
+        function: eval_type_of,
+        validation: |_| Ok(()),
+        return_type: |_| ValueType::StringType,
+    },
+    "deepClone" => UnaryFunctionDefinition {
+        name: "deepClone",
+        function: eval_deep_clone,
+        validation: |_| Ok(()),
+        return_type: |value_type| value_type,
+    },
+    // Object introspection
+    "fieldsOf" => UnaryFunctionDefinition {
+        name: "fieldsOf",
+        function: eval_fields_of,
+        validation: validate_unary_object,
+        return_type: return_string_list_type,
+    },
     "count" => UnaryFunctionDefinition {
         name: "count",
         function: eval_count,
-        validation: number_range_or_any_list,
+        validation: number_range_string_or_any_list,
         return_type: return_uni_number,
     },
     "max" => UnaryFunctionDefinition {
@@ -80,6 +107,18 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: validate_unary_number,
         return_type: return_uni_number,
     },
+    "sign" => UnaryFunctionDefinition {
+        name: "sign",
+        function: eval_sign,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
+    "factorial" => UnaryFunctionDefinition {
+        name: "factorial",
+        function: eval_factorial,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
     "floor" => UnaryFunctionDefinition {
         name: "floor",
         function: eval_floor,
@@ -170,6 +209,42 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: validate_unary_number,
         return_type: return_uni_number,
     },
+    "sinh" => UnaryFunctionDefinition {
+        name: "sinh",
+        function: eval_sinh,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
+    "cosh" => UnaryFunctionDefinition {
+        name: "cosh",
+        function: eval_cosh,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
+    "tanh" => UnaryFunctionDefinition {
+        name: "tanh",
+        function: eval_tanh,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
+    "asinh" => UnaryFunctionDefinition {
+        name: "asinh",
+        function: eval_asinh,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
+    "acosh" => UnaryFunctionDefinition {
+        name: "acosh",
+        function: eval_acosh,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
+    "atanh" => UnaryFunctionDefinition {
+        name: "atanh",
+        function: eval_atanh,
+        validation: validate_unary_number,
+        return_type: return_uni_number,
+    },
     // List numerics
     "min" => UnaryFunctionDefinition {
         name: "min",
@@ -183,29 +258,65 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: validate_unary_list_numbers,
         return_type: return_uni_number,
     },
+    "indexOfMax" => UnaryFunctionDefinition {
+        name: "indexOfMax",
+        function: eval_index_of_max,
+        validation: validate_unary_list_numbers,
+        return_type: |_| ValueType::NumberType,
+    },
+    "indexOfMin" => UnaryFunctionDefinition {
+        name: "indexOfMin",
+        function: eval_index_of_min,
+        validation: validate_unary_list_numbers,
+        return_type: |_| ValueType::NumberType,
+    },
+    "cumulativeSum" => UnaryFunctionDefinition {
+        name: "cumulativeSum",
+        function: eval_cumulative_sum,
+        validation: validate_unary_list_numbers,
+        return_type: |_| ValueType::list_of(ValueType::NumberType),
+    },
     "mean" => UnaryFunctionDefinition {
         name: "mean",
         function: eval_mean,
-        validation: validate_unary_list_numbers,
-        return_type: return_uni_number,
+        validation: validate_unary_list_numbers_or_durations,
+        return_type: return_number_or_duration_type,
     },
     "median" => UnaryFunctionDefinition {
         name: "median",
         function: eval_median,
-        validation: validate_unary_list_numbers,
-        return_type: return_uni_number,
+        validation: validate_unary_list_numbers_or_durations,
+        return_type: return_number_or_duration_type,
     },
     "stddev" => UnaryFunctionDefinition {
         name: "stddev",
         function: eval_stddev,
-        validation: validate_unary_list_numbers,
+        validation: validate_unary_list_numbers_or_durations,
+        return_type: return_uni_number,
+    },
+    "stddevSample" => UnaryFunctionDefinition {
+        name: "stddevSample",
+        function: eval_stddev_sample,
+        validation: validate_unary_list_numbers_or_durations,
+        return_type: return_uni_number,
+    },
+    "variance" => UnaryFunctionDefinition {
+        name: "variance",
+        function: eval_variance,
+        validation: validate_unary_list_numbers_or_durations,
+        return_type: return_uni_number,
+    },
+    "varianceSample" => UnaryFunctionDefinition {
+        name: "varianceSample",
+        function: eval_variance_sample,
+        validation: validate_unary_list_numbers_or_durations,
         return_type: return_uni_number,
     },
     "mode" => UnaryFunctionDefinition {
         name: "mode",
         function: eval_mode,
         validation: validate_unary_list,
-        return_type: |_| ValueType::ListType(Some(Box::new(ValueType::NumberType))),
+        return_type: return_same_list_type,
     },
     // Booleans
     "all" => UnaryFunctionDefinition {
@@ -239,6 +350,12 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: expect_string_arg,
         return_type: |_| ValueType::DateTimeType,
     },
+    "toUtc" => UnaryFunctionDefinition {
+        name: "toUtc",
+        function: eval_to_utc,
+        validation: expect_datetime_arg,
+        return_type: |_| ValueType::DateTimeType,
+    },
     "duration" => UnaryFunctionDefinition {
         name: "duration",
         function: eval_duration,
@@ -251,6 +368,18 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: expect_string_arg,
         return_type: |_| ValueType::PeriodType,
     },
+    "parseDuration" => UnaryFunctionDefinition {
+        name: "parseDuration",
+        function: eval_parse_duration,
+        validation: expect_string_arg,
+        return_type: |_| ValueType::DurationType,
+    },
+    "parsePeriod" => UnaryFunctionDefinition {
+        name: "parsePeriod",
+        function: eval_parse_period,
+        validation: expect_string_arg,
+        return_type: |_| ValueType::PeriodType,
+    },
     // Additional helpers
     "dayOfWeek" => UnaryFunctionDefinition {
         name: "dayOfWeek",
@@ -270,6 +399,54 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: expect_date_arg,
         return_type: |_| ValueType::NumberType,
     },
+    "absDuration" => UnaryFunctionDefinition {
+        name: "absDuration",
+        function: eval_abs_duration,
+        validation: expect_duration_arg,
+        return_type: |_| ValueType::DurationType,
+    },
+    "durationSign" => UnaryFunctionDefinition {
+        name: "durationSign",
+        function: eval_duration_sign,
+        validation: expect_duration_arg,
+        return_type: |_| ValueType::NumberType,
+    },
+    "isWeekend" => UnaryFunctionDefinition {
+        name: "isWeekend",
+        function: eval_is_weekend,
+        validation: expect_date_or_datetime_arg,
+        return_type: |_| ValueType::BooleanType,
+    },
+    "isLeapYear" => UnaryFunctionDefinition {
+        name: "isLeapYear",
+        function: eval_is_leap_year,
+        validation: expect_date_datetime_or_number_arg,
+        return_type: |_| ValueType::BooleanType,
+    },
+    "startOfMonth" => UnaryFunctionDefinition {
+        name: "startOfMonth",
+        function: eval_start_of_month,
+        validation: expect_date_or_datetime_arg,
+        return_type: |arg| arg,
+    },
+    "endOfMonth" => UnaryFunctionDefinition {
+        name: "endOfMonth",
+        function: eval_end_of_month,
+        validation: expect_date_or_datetime_arg,
+        return_type: |arg| arg,
+    },
+    "startOfYear" => UnaryFunctionDefinition {
+        name: "startOfYear",
+        function: eval_start_of_year,
+        validation: expect_date_or_datetime_arg,
+        return_type: |arg| arg,
+    },
+    "endOfYear" => UnaryFunctionDefinition {
+        name: "endOfYear",
+        function: eval_end_of_year,
+        validation: expect_date_or_datetime_arg,
+        return_type: |arg| arg,
+    },
     // String unary
     "length" => UnaryFunctionDefinition {
         name: "length",
@@ -295,6 +472,18 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
         validation: validate_unary_string,
         return_type: return_string_type_unary,
     },
+    "trimStart" => UnaryFunctionDefinition {
+        name: "trimStart",
+        function: eval_trim_start,
+        validation: validate_unary_string,
+        return_type: return_string_type_unary,
+    },
+    "trimEnd" => UnaryFunctionDefinition {
+        name: "trimEnd",
+        function: eval_trim_end,
+        validation: validate_unary_string,
+        return_type: return_string_type_unary,
+    },
     // base64 group (available; implementation depends on features/target)
     "toBase64" => UnaryFunctionDefinition {
         name: "toBase64",
@@ -312,7 +501,7 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
     "reverse" => UnaryFunctionDefinition {
         name: "reverse",
         function: eval_reverse_mixed,
-        validation: validate_unary_reverse_mixed,
+        validation: validate_unary_list_or_string,
         return_type: return_same_list_type,
     },
     "sort" => UnaryFunctionDefinition {
@@ -355,7 +544,7 @@ pub static UNARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, UnaryFunctionDefinit
     "isEmpty" => UnaryFunctionDefinition {
         name: "isEmpty",
         function: eval_is_empty,
-        validation: validate_unary_list,
+        validation: validate_unary_list_or_string,
         return_type: |_| ValueType::BooleanType,
     },
 };
@@ -367,6 +556,42 @@ pub static BINARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, BinaryFunctionDefin
         validation: validate_binary_date_date,
         return_type: return_period_type_binary,
     },
+    "datetimeTz" => BinaryFunctionDefinition {
+        name: "datetimeTz",
+        function: eval_datetime_tz,
+        validation: validate_binary_string_string,
+        return_type: return_datetime_type_binary,
+    },
+    "toOffset" => BinaryFunctionDefinition {
+        name: "toOffset",
+        function: eval_to_offset,
+        validation: validate_binary_datetime_string,
+        return_type: return_datetime_type_binary,
+    },
+    "daysBetween" => BinaryFunctionDefinition {
+        name: "daysBetween",
+        function: eval_days_between,
+        validation: validate_binary_date_date,
+        return_type: return_number_type_binary,
+    },
+    "monthsBetween" => BinaryFunctionDefinition {
+        name: "monthsBetween",
+        function: eval_months_between,
+        validation: validate_binary_date_date,
+        return_type: return_number_type_binary,
+    },
+    "addMonths" => BinaryFunctionDefinition {
+        name: "addMonths",
+        function: eval_add_months,
+        validation: validate_binary_date_number,
+        return_type: return_binary_same_as_left_arg,
+    },
+    "addYears" => BinaryFunctionDefinition {
+        name: "addYears",
+        function: eval_add_years,
+        validation: validate_binary_date_number,
+        return_type: return_binary_same_as_left_arg,
+    },
     "find" => BinaryFunctionDefinition {
         name: "find",
         function: eval_find,
@@ -385,6 +610,36 @@ pub static BINARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, BinaryFunctionDefin
         validation: validate_binary_number_number,
         return_type: return_number_type_binary,
     },
+    "pow" => BinaryFunctionDefinition {
+        name: "pow",
+        function: eval_pow,
+        validation: validate_binary_number_number,
+        return_type: return_number_type_binary,
+    },
+    "gcd" => BinaryFunctionDefinition {
+        name: "gcd",
+        function: eval_gcd,
+        validation: validate_binary_number_number,
+        return_type: return_number_type_binary,
+    },
+    "lcm" => BinaryFunctionDefinition {
+        name: "lcm",
+        function: eval_lcm,
+        validation: validate_binary_number_number,
+        return_type: return_number_type_binary,
+    },
+    "binomial" => BinaryFunctionDefinition {
+        name: "binomial",
+        function: eval_binomial,
+        validation: validate_binary_number_number,
+        return_type: return_number_type_binary,
+    },
+    "logBase" => BinaryFunctionDefinition {
+        name: "logBase",
+        function: eval_log_base,
+        validation: validate_binary_number_number,
+        return_type: return_number_type_binary,
+    },
     "atan2" => BinaryFunctionDefinition {
         name: "atan2",
         function: eval_atan2,
@@ -404,6 +659,18 @@ pub static BINARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, BinaryFunctionDefin
         validation: validate_binary_string_string,
         return_type: return_boolean_type_binary,
     },
+    "containsIgnoreCase" => BinaryFunctionDefinition {
+        name: "containsIgnoreCase",
+        function: eval_contains_ignore_case,
+        validation: validate_binary_string_string,
+        return_type: return_boolean_type_binary,
+    },
+    "equalsIgnoreCase" => BinaryFunctionDefinition {
+        name: "equalsIgnoreCase",
+        function: eval_equals_ignore_case,
+        validation: validate_binary_string_string,
+        return_type: return_boolean_type_binary,
+    },
     "endsWith" => BinaryFunctionDefinition {
         name: "endsWith",
         function: eval_ends_with,
@@ -423,6 +690,12 @@ pub static BINARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, BinaryFunctionDefin
         validation: validate_binary_string_string,
         return_type: return_string_list_type_binary,
     },
+    "matches" => BinaryFunctionDefinition {
+        name: "matches",
+        function: eval_matches,
+        validation: validate_binary_string_string,
+        return_type: return_boolean_type_binary,
+    },
     "substringBefore" => BinaryFunctionDefinition {
         name: "substringBefore",
         function: eval_substring_before,
@@ -472,6 +745,12 @@ pub static BINARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, BinaryFunctionDefin
         validation: validate_binary_string_any,
         return_type: return_string_type_binary,
     },
+    "typeOfField" => BinaryFunctionDefinition {
+        name: "typeOfField",
+        function: eval_type_of_field,
+        validation: validate_binary_object_string,
+        return_type: return_string_type_binary,
+    },
     // List-specific
     "remove" => BinaryFunctionDefinition {
         name: "remove",
@@ -479,6 +758,24 @@ pub static BINARY_BUILT_IN_FUNCTIONS: phf::Map<&'static str, BinaryFunctionDefin
         validation: validate_binary_list_number,
         return_type: return_binary_same_as_left_arg,
     },
+    "histogram" => BinaryFunctionDefinition {
+        name: "histogram",
+        function: eval_histogram,
+        validation: validate_binary_list_numbers_number,
+        return_type: return_histogram_type,
+    },
+    "percentile" => BinaryFunctionDefinition {
+        name: "percentile",
+        function: eval_percentile,
+        validation: validate_binary_list_numbers_number,
+        return_type: return_number_type_binary,
+    },
+    "zip" => BinaryFunctionDefinition {
+        name: "zip",
+        function: eval_zip,
+        validation: validate_binary_list_list,
+        return_type: |_, _| ValueType::list_of(ValueType::ListType(None)),
+    },
     "partition" => BinaryFunctionDefinition {
         name: "partition",
         function: eval_partition,
@@ -527,7 +824,19 @@ pub static MULTI_BUILT_IN_FUNCTIONS: phf::Map<&'static str, MultiFunctionDefinit
     "clamp" => MultiFunctionDefinition {
         name: "clamp",
         function: eval_clamp,
-        validation: validate_clamp_args,
+        validation: validate_number_args,
+        return_type: |_| ValueType::NumberType,
+    },
+    "lerp" => MultiFunctionDefinition {
+        name: "lerp",
+        function: eval_lerp,
+        validation: validate_number_args,
+        return_type: |_| ValueType::NumberType,
+    },
+    "lerpUnclamped" => MultiFunctionDefinition {
+        name: "lerpUnclamped",
+        function: eval_lerp_unclamped,
+        validation: validate_number_args,
         return_type: |_| ValueType::NumberType,
     },
     "pi" => MultiFunctionDefinition {
@@ -541,19 +850,19 @@ pub static MULTI_BUILT_IN_FUNCTIONS: phf::Map<&'static str, MultiFunctionDefinit
         name: "sublist",
         function: eval_sublist,
         validation: validate_multi_sublist,
-        return_type: return_list_undefined,
+        return_type: return_multi_same_list_type,
     },
     "append" => MultiFunctionDefinition {
         name: "append",
         function: eval_append,
         validation: validate_multi_append,
-        return_type: return_list_undefined,
+        return_type: return_multi_same_list_type,
     },
     "concatenate" => MultiFunctionDefinition {
         name: "concatenate",
         function: eval_concatenate,
         validation: validate_multi_concatenate,
-        return_type: return_list_undefined,
+        return_type: return_multi_same_list_type,
     },
     "insertBefore" => MultiFunctionDefinition {
         name: "insertBefore",
@@ -567,6 +876,18 @@ pub static MULTI_BUILT_IN_FUNCTIONS: phf::Map<&'static str, MultiFunctionDefinit
         validation: validate_multi_union,
         return_type: return_list_undefined,
     },
+    "intersection" => MultiFunctionDefinition {
+        name: "intersection",
+        function: eval_intersection,
+        validation: validate_multi_intersection,
+        return_type: return_multi_same_list_type,
+    },
+    "difference" => MultiFunctionDefinition {
+        name: "difference",
+        function: eval_difference,
+        validation: validate_multi_difference,
+        return_type: return_multi_same_list_type,
+    },
     // String multi-arity
     "join" => MultiFunctionDefinition {
         name: "join",
@@ -574,6 +895,18 @@ pub static MULTI_BUILT_IN_FUNCTIONS: phf::Map<&'static str, MultiFunctionDefinit
         validation: validate_multi_join,
         return_type: return_string_type_multi,
     },
+    "joinBy" => MultiFunctionDefinition {
+        name: "joinBy",
+        function: eval_join_by,
+        validation: validate_multi_join_by,
+        return_type: return_string_type_multi,
+    },
+    "format" => MultiFunctionDefinition {
+        name: "format",
+        function: eval_format,
+        validation: validate_multi_format,
+        return_type: return_string_type_multi,
+    },
     "substring" => MultiFunctionDefinition {
         name: "substring",
         function: eval_substring,
@@ -586,6 +919,12 @@ pub static MULTI_BUILT_IN_FUNCTIONS: phf::Map<&'static str, MultiFunctionDefinit
         validation: validate_multi_replace,
         return_type: return_string_type_multi,
     },
+    "extract" => MultiFunctionDefinition {
+        name: "extract",
+        function: eval_extract,
+        validation: validate_multi_extract,
+        return_type: return_string_type_multi,
+    },
     "regexReplace" => MultiFunctionDefinition {
         name: "regexReplace",
         function: eval_regex_replace,
@@ -622,8 +961,107 @@ pub static MULTI_BUILT_IN_FUNCTIONS: phf::Map<&'static str, MultiFunctionDefinit
         validation: validate_multi_pad,
         return_type: return_string_type_multi,
     },
+    "isBusinessDay" => MultiFunctionDefinition {
+        name: "isBusinessDay",
+        function: eval_is_business_day,
+        validation: validate_multi_business_day,
+        return_type: |_| ValueType::BooleanType,
+    },
+    "addBusinessDays" => MultiFunctionDefinition {
+        name: "addBusinessDays",
+        function: eval_add_business_days,
+        validation: validate_multi_add_business_days,
+        return_type: |_| ValueType::DateType,
+    },
+};
+
+pub static CLOCK_BUILT_IN_FUNCTIONS: phf::Map<&'static str, ClockFunctionDefinition> = phf_map! {
+    "today" => ClockFunctionDefinition {
+        name: "today",
+        function: eval_today,
+        validation: validate_zero_args,
+        return_type: |_| ValueType::DateType,
+    },
+    "now" => ClockFunctionDefinition {
+        name: "now",
+        function: eval_now,
+        validation: validate_zero_args,
+        return_type: |_| ValueType::DateTimeType,
+    },
 };
 
+/// Accepted argument count range `(min, max)` of multi and clock built-ins, checked during linking before the function validator
+/// runs. Variadic functions have `usize::MAX` as `max`.
+pub static MULTI_BUILT_IN_ARITIES: phf::Map<&'static str, (usize, usize)> = phf_map! {
+    "max" => (1, usize::MAX),
+    "min" => (1, usize::MAX),
+    "sum" => (1, usize::MAX),
+    "round" => (1, 2),
+    "roundUp" => (1, 2),
+    "roundDown" => (1, 2),
+    "clamp" => (3, 3),
+    "lerp" => (3, 3),
+    "lerpUnclamped" => (3, 3),
+    "today" => (0, 0),
+    "now" => (0, 0),
+    "pi" => (0, 0),
+    "sublist" => (2, 3),
+    "insertBefore" => (3, 3),
+    "substring" => (2, 3),
+    "joinBy" => (3, 3),
+    "replace" => (3, 4),
+    "regexReplace" => (3, 4),
+    "extract" => (3, 3),
+    "replaceFirst" => (3, 4),
+    "replaceLast" => (3, 4),
+    "padStart" => (3, 3),
+    "padEnd" => (3, 3),
+    "format" => (1, usize::MAX),
+    "isBusinessDay" => (1, 2),
+    "addBusinessDays" => (2, 3),
+};
+
+/// Unary list functions that also take a predicate as a second argument, e.g. `all(loans, overdue)`
+pub const PREDICATE_FORM_FUNCTIONS: [&str; 2] = ["all", "any"];
+
+/// Describes accepted argument counts for error messages, e.g. "1 or 2 arguments"
+pub fn describe_arity(min: usize, max: usize) -> String {
+    match (min, max) {
+        (0, 0) => "no arguments".to_string(),
+        (1, 1) => "1 argument".to_string(),
+        (1, usize::MAX) => "at least 1 argument".to_string(),
+        (min, usize::MAX) => format!("at least {} arguments", min),
+        (min, max) if min == max => format!("{} arguments", min),
+        (min, max) if max == min + 1 => format!("{} or {} arguments", min, max),
+        (min, max) => format!("{} to {} arguments", min, max),
+    }
+}
+
+/// Checks the argument count against `MULTI_BUILT_IN_ARITIES`, functions without an entry accept any count
+fn check_arity(name: &str, count: usize) -> Link<()> {
+    if let Some((min, max)) = MULTI_BUILT_IN_ARITIES.get(name) {
+        if !(*min..=*max).contains(&count) {
+            return LinkingError::other_error(format!(
+                "{} accepts {}, got {}",
+                name,
+                describe_arity(*min, *max),
+                count
+            ))
+            .into();
+        }
+    }
+    Ok(())
+}
+
+/// Validation of functions without arguments, e.g. `pi()` or `today()`
+pub fn validate_zero_args(args: Vec<ValueType>) -> Link<()> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        LinkingError::other_error(format!("Expected no arguments, got {}", args.len())).into()
+    }
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 #[derive(PartialEq, Clone)]
 pub enum EFunctionType {
@@ -635,10 +1073,13 @@ pub enum EFunctionType {
 
 pub static BUILT_IN_ALL_FUNCTIONS: phf::Map<&'static str, EFunctionType> = phf_map! {
     "toString" => EFunctionType::Unary,
+    "typeOf" => EFunctionType::Unary,
     "max" => EFunctionType::Multi,
     "sum" => EFunctionType::Multi,
     "min" => EFunctionType::Multi,
     "abs" => EFunctionType::Unary,
+    "sign" => EFunctionType::Unary,
+    "factorial" => EFunctionType::Unary,
     "floor" => EFunctionType::Unary,
     "ceiling" => EFunctionType::Unary,
     "trunc" => EFunctionType::Unary,
@@ -654,20 +1095,49 @@ pub static BUILT_IN_ALL_FUNCTIONS: phf::Map<&'static str, EFunctionType> = phf_m
     "asin" => EFunctionType::Unary,
     "acos" => EFunctionType::Unary,
     "atan" => EFunctionType::Unary,
+    "sinh" => EFunctionType::Unary,
+    "cosh" => EFunctionType::Unary,
+    "tanh" => EFunctionType::Unary,
+    "asinh" => EFunctionType::Unary,
+    "acosh" => EFunctionType::Unary,
+    "atanh" => EFunctionType::Unary,
+    "logBase" => EFunctionType::Binary,
     "atan2" => EFunctionType::Binary,
+    "today" => EFunctionType::Multi,
+    "now" => EFunctionType::Multi,
     "pi" => EFunctionType::Multi,
     "modulo" => EFunctionType::Binary,
     "idiv" => EFunctionType::Binary,
+    "pow" => EFunctionType::Binary,
+    "gcd" => EFunctionType::Binary,
+    "lcm" => EFunctionType::Binary,
+    "binomial" => EFunctionType::Binary,
     "round" => EFunctionType::Multi,
     "roundUp" => EFunctionType::Multi,
     "roundDown" => EFunctionType::Multi,
     "clamp" => EFunctionType::Multi,
+    "lerp" => EFunctionType::Multi,
+    "lerpUnclamped" => EFunctionType::Multi,
     "count" => EFunctionType::Unary,
     "find" => EFunctionType::Binary,
+    "map" => EFunctionType::Binary,
+    "filter" => EFunctionType::Binary,
+    "groupBy" => EFunctionType::Binary,
+    "sortBy" => EFunctionType::Binary,
+    "sortByDesc" => EFunctionType::Binary,
+    "takeWhile" => EFunctionType::Binary,
+    "dropWhile" => EFunctionType::Binary,
+    "flatMap" => EFunctionType::Binary,
     "product" => EFunctionType::Unary,
+    "indexOfMax" => EFunctionType::Unary,
+    "indexOfMin" => EFunctionType::Unary,
+    "cumulativeSum" => EFunctionType::Unary,
     "mean" => EFunctionType::Unary,
     "median" => EFunctionType::Unary,
     "stddev" => EFunctionType::Unary,
+    "stddevSample" => EFunctionType::Unary,
+    "variance" => EFunctionType::Unary,
+    "varianceSample" => EFunctionType::Unary,
     "mode" => EFunctionType::Unary,
     "all" => EFunctionType::Unary,
     "any" => EFunctionType::Unary,
@@ -679,29 +1149,60 @@ pub static BUILT_IN_ALL_FUNCTIONS: phf::Map<&'static str, EFunctionType> = phf_m
     "reverse" => EFunctionType::Unary,
     "indexOf" => EFunctionType::Binary,
     "union" => EFunctionType::Multi,
+    "intersection" => EFunctionType::Multi,
+    "difference" => EFunctionType::Multi,
     "distinctValues" => EFunctionType::Unary,
     "duplicateValues" => EFunctionType::Unary,
     "flatten" => EFunctionType::Unary,
     "sort" => EFunctionType::Unary,
     "sortDescending" => EFunctionType::Unary,
     "join" => EFunctionType::Multi,
+    "joinBy" => EFunctionType::Multi,
+    "format" => EFunctionType::Multi,
     "isEmpty" => EFunctionType::Unary,
     "partition" => EFunctionType::Binary,
+    "zip" => EFunctionType::Binary,
+    "lookup" => EFunctionType::Custom(4),
+    "reduce" => EFunctionType::Custom(3),
+    "decisionTable" => EFunctionType::Multi,
+    "percentile" => EFunctionType::Binary,
+    "histogram" => EFunctionType::Binary,
     "calendarDiff" => EFunctionType::Binary,
     // Date/Time/Duration parsing and helpers
     "date" => EFunctionType::Unary,
     "time" => EFunctionType::Unary,
     "datetime" => EFunctionType::Unary,
+    "datetimeTz" => EFunctionType::Binary,
+    "toUtc" => EFunctionType::Unary,
+    "isLeapYear" => EFunctionType::Unary,
+    "startOfMonth" => EFunctionType::Unary,
+    "endOfMonth" => EFunctionType::Unary,
+    "startOfYear" => EFunctionType::Unary,
+    "endOfYear" => EFunctionType::Unary,
+    "parseDuration" => EFunctionType::Unary,
+    "parsePeriod" => EFunctionType::Unary,
+    "toOffset" => EFunctionType::Binary,
     "duration" => EFunctionType::Unary,
     "period" => EFunctionType::Unary,
     "dayOfWeek" => EFunctionType::Unary,
     "monthOfYear" => EFunctionType::Unary,
     "lastDayOfMonth" => EFunctionType::Unary,
+    "absDuration" => EFunctionType::Unary,
+    "durationSign" => EFunctionType::Unary,
+    "isWeekend" => EFunctionType::Unary,
+    "isBusinessDay" => EFunctionType::Multi,
+    "addBusinessDays" => EFunctionType::Multi,
+    "daysBetween" => EFunctionType::Binary,
+    "monthsBetween" => EFunctionType::Binary,
+    "addMonths" => EFunctionType::Binary,
+    "addYears" => EFunctionType::Binary,
     // String
     "length" => EFunctionType::Unary,
     "toUpperCase" => EFunctionType::Unary,
     "toLowerCase" => EFunctionType::Unary,
     "trim" => EFunctionType::Unary,
+    "trimStart" => EFunctionType::Unary,
+    "trimEnd" => EFunctionType::Unary,
     "toBase64" => EFunctionType::Unary,
     "fromBase64" => EFunctionType::Unary,
     // reverse accounted above
@@ -709,8 +1210,11 @@ pub static BUILT_IN_ALL_FUNCTIONS: phf::Map<&'static str, EFunctionType> = phf_m
     // contains accounted above
     "startsWith" => EFunctionType::Binary,
     "endsWith" => EFunctionType::Binary,
+    "containsIgnoreCase" => EFunctionType::Binary,
+    "equalsIgnoreCase" => EFunctionType::Binary,
     "split" => EFunctionType::Binary,
     "regexSplit" => EFunctionType::Binary,
+    "matches" => EFunctionType::Binary,
     "substringBefore" => EFunctionType::Binary,
     "substringAfter" => EFunctionType::Binary,
     "charAt" => EFunctionType::Binary,
@@ -719,9 +1223,13 @@ pub static BUILT_IN_ALL_FUNCTIONS: phf::Map<&'static str, EFunctionType> = phf_m
     "lastIndexOf" => EFunctionType::Binary,
     "repeat" => EFunctionType::Binary,
     "interpolate" => EFunctionType::Binary,
+    "fieldsOf" => EFunctionType::Unary,
+    "deepClone" => EFunctionType::Unary,
+    "typeOfField" => EFunctionType::Binary,
     "substring" => EFunctionType::Multi,
     "replace" => EFunctionType::Multi,
     "regexReplace" => EFunctionType::Multi,
+    "extract" => EFunctionType::Multi,
     // Basic variants always available
     "replaceFirst" => EFunctionType::Multi,
     "replaceLast" => EFunctionType::Multi,
@@ -846,6 +1354,8 @@ impl StaticLink for MultiFunction {
                 arg_types.push(arg.link(Rc::clone(&ctx))?);
             }
 
+            check_arity(self.definition.name, arg_types.len())?;
+
             (self.definition.validation)(arg_types.clone())?;
 
             self.return_type = Ok((self.definition.return_type)(&arg_types));
@@ -865,3 +1375,52 @@ impl EvaluatableExpression for MultiFunction {
         (self.definition.function)(values, self.return_type.clone()?)
     }
 }
+
+//--------------------------------------------------------------------------------------------------
+
+/// **Clock function**, e.g. `today()` or `now()`.
+/// Reads the evaluation instant from the runtime clock, so every call within one evaluation sees the same time.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct ClockFunction {
+    pub args: Vec<ExpressionEnum>,
+    pub definition: ClockFunctionDefinition,
+    pub return_type: Link<ValueType>,
+}
+
+impl ClockFunction {
+    pub fn build(definition: ClockFunctionDefinition, args: Vec<ExpressionEnum>) -> Self {
+        ClockFunction { args, definition, return_type: LinkingError::not_linked().into() }
+    }
+}
+
+impl Display for ClockFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.definition.name, array_to_code_sep(self.args.iter(), ", "))
+    }
+}
+
+impl StaticLink for ClockFunction {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let mut arg_types = Vec::new();
+            for arg in self.args.iter_mut() {
+                arg_types.push(arg.link(Rc::clone(&ctx))?);
+            }
+
+            check_arity(self.definition.name, arg_types.len())?;
+
+            (self.definition.validation)(arg_types.clone())?;
+
+            self.return_type = Ok((self.definition.return_type)(&arg_types));
+        }
+
+        self.return_type.clone()
+    }
+}
+
+impl EvaluatableExpression for ClockFunction {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let instant = context.borrow().clock.now();
+        (self.definition.function)(instant)
+    }
+}
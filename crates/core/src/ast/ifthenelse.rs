@@ -16,19 +16,20 @@ use std::rc::Rc;
 pub struct IfThenElseFunction {
     pub condition: ExpressionEnum,
     pub then_expression: ExpressionEnum,
-    pub else_expression: ExpressionEnum,
+    /// `if ... then ...` without `else` evaluates to the omitted value when the condition is false
+    pub else_expression: Option<ExpressionEnum>,
     pub result_type: Link<ValueType>,
 }
 
 impl Display for IfThenElseFunction {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "if {} then {} else {}",
-            self.condition,
-            bracket_unwrap(format!("{}", self.then_expression)),
-            bracket_unwrap(format!("{}", self.else_expression))
-        )
+        write!(f, "if {} then {}", self.condition, bracket_unwrap(format!("{}", self.then_expression)))?;
+
+        if let Some(else_expression) = &self.else_expression {
+            write!(f, " else {}", bracket_unwrap(format!("{}", else_expression)))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -36,7 +37,7 @@ impl IfThenElseFunction {
     pub fn build(
         condition: ExpressionEnum,
         then_expression: ExpressionEnum,
-        else_expression: ExpressionEnum,
+        else_expression: Option<ExpressionEnum>,
     ) -> Result<Self, ParseErrorEnum> {
         Ok(IfThenElseFunction {
             condition,
@@ -52,10 +53,24 @@ impl StaticLink for IfThenElseFunction {
         if !is_linked(&self.result_type) {
             let condition_type = self.condition.link(Rc::clone(&ctx))?;
             let then_expression = self.then_expression.link(Rc::clone(&ctx))?;
-            self.result_type = self.else_expression.link(Rc::clone(&ctx));
 
             LinkingError::expect_single_type("if condition", condition_type, &ValueType::BooleanType)?;
 
+            let Some(else_expression) = &mut self.else_expression else {
+                if ValueEnum::omitted_for_type(&then_expression).is_none() {
+                    return LinkingError::other_error(format!(
+                        "`{}` has no `else`, so it cannot produce '{}' value",
+                        self, then_expression
+                    ))
+                    .into();
+                }
+
+                self.result_type = Ok(then_expression);
+                return self.result_type.clone();
+            };
+
+            self.result_type = else_expression.link(Rc::clone(&ctx));
+
             if let Ok(else_expression) = &self.result_type {
                 LinkingError::expect_same_types(
                     "`then` and `else` expressions",
@@ -75,7 +90,14 @@ impl EvaluatableExpression for IfThenElseFunction {
 
         match result {
             ValueEnum::BooleanValue(true) => Ok(self.then_expression.eval(context)?),
-            ValueEnum::BooleanValue(false) => Ok(self.else_expression.eval(context)?),
+            ValueEnum::BooleanValue(false) => match &self.else_expression {
+                Some(else_expression) => Ok(else_expression.eval(context)?),
+                None => {
+                    let result_type = self.result_type.clone()?;
+                    ValueEnum::omitted_for_type(&result_type)
+                        .ok_or_else(|| RuntimeError::type_not_supported(result_type))
+                }
+            },
             _ => RuntimeError::type_not_supported(result.get_type().clone()).into(),
         }
     }
@@ -0,0 +1,85 @@
+use crate::ast::context::context_object::{ContextObject, MethodEntry};
+use crate::ast::context::context_object_type::FormalParameter;
+use crate::ast::expression::{EvaluatableExpression, StaticLink};
+use crate::ast::metaphors::functions::{InlineFunctionDefinition, UserFunctionDefinition};
+use crate::ast::token::{ComplexTypeRef, ExpressionEnum};
+use crate::ast::utils::array_to_code_sep;
+use crate::ast::Link;
+use crate::runtime::execution_context::ExecutionContext;
+use crate::typesystem::errors::{LinkingError, ParseErrorEnum, RuntimeError};
+use crate::typesystem::types::ValueType;
+use crate::typesystem::values::ValueEnum;
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+pub const LAMBDA_FUNCTION_NAME: &str = "lambda";
+
+/// Anonymous function `(x, y) => x + y`.
+/// It is not a value itself: a lambda is passed as an argument and bound to the receiving function parameter
+/// the same way as a named user function.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct LambdaFunction {
+    pub method: Rc<RefCell<MethodEntry>>,
+}
+
+impl LambdaFunction {
+    pub fn build(parameters: Vec<String>, body: ExpressionEnum) -> Result<Self, ParseErrorEnum> {
+        let arguments = parameters
+            .into_iter()
+            .map(|name| FormalParameter::with_type_ref(name, ComplexTypeRef::undefined()))
+            .collect();
+        let definition = InlineFunctionDefinition::build(LAMBDA_FUNCTION_NAME.to_string(), arguments, body)?;
+
+        Ok(LambdaFunction { method: MethodEntry::from(UserFunctionDefinition::Inline(definition)).into() })
+    }
+
+    /// Consumes the lambda into parameter names and body, used when a lambda is inlined into another construct
+    pub fn into_parts(self) -> (Vec<String>, ExpressionEnum) {
+        let method = self.method.borrow();
+        match &method.function_definition {
+            UserFunctionDefinition::Inline(definition) => {
+                let parameters = definition.arguments.iter().map(|argument| argument.name.clone()).collect();
+                let body = std::mem::replace(
+                    &mut definition.get_body_entry().borrow_mut().expression,
+                    ExpressionEnum::Value(ValueEnum::BooleanValue(false)),
+                );
+                (parameters, body)
+            }
+            other => unreachable!("lambda is always built as inline function, got {}", other),
+        }
+    }
+
+    /// Binds the lambda body to the scope where the lambda is written, so it can reference outer fields
+    pub fn bind_to(&self, ctx: &Rc<RefCell<ContextObject>>) -> Rc<RefCell<MethodEntry>> {
+        self.method.borrow().function_definition.set_parent_with_alias(ctx, LAMBDA_FUNCTION_NAME);
+        Rc::clone(&self.method)
+    }
+}
+
+impl Display for LambdaFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.method.borrow().function_definition {
+            UserFunctionDefinition::Inline(definition) => write!(
+                f,
+                "({}) => {}",
+                array_to_code_sep(definition.arguments.iter(), ", "),
+                definition.get_body_entry().borrow().expression
+            ),
+            other => write!(f, "{}", other),
+        }
+    }
+}
+
+impl StaticLink for LambdaFunction {
+    fn link(&mut self, _ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        LinkingError::other_error(format!("Lambda `{}` can only be passed as a function argument", self)).into()
+    }
+}
+
+impl EvaluatableExpression for LambdaFunction {
+    fn eval(&self, _context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        RuntimeError::eval_error(format!("Lambda `{}` cannot be used as a value", self)).into()
+    }
+}
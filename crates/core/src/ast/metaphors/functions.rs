@@ -6,9 +6,12 @@ use crate::ast::context::context_object_type::FormalParameter;
 use crate::ast::context::function_context::{FunctionContext, RETURN_EXPRESSION};
 use crate::ast::metaphors::metaphor::UserFunction;
 use crate::ast::token::ExpressionEnum;
+use crate::ast::token::{DefinitionEnum, EToken};
 use crate::ast::utils::{array_to_code_sep, trim};
 use crate::ast::Link;
 use crate::link::linker;
+use crate::link::node_data::NodeDataEnum;
+use crate::tokenizer::parser::tokenize;
 use crate::tokenizer::C_ASSIGN;
 use crate::typesystem::errors::{LinkingError, ParseErrorEnum};
 use crate::utils::intern_field_name;
@@ -243,6 +246,27 @@ impl UserFunctionDefinition {
         }
     }
 
+    /// Unlinked copy of the function in the same scope, parsed from its source code
+    pub fn parse_copy(&self) -> Link<UserFunctionDefinition> {
+        let copy = match tokenize(&format!("func {}", self)).pop_front() {
+            Some(EToken::Definition(DefinitionEnum::UserFunction(definition))) => {
+                UserFunctionDefinition::Function(definition)
+            }
+            Some(EToken::Definition(DefinitionEnum::InlineUserFunction(definition))) => {
+                UserFunctionDefinition::Inline(definition)
+            }
+            _ => return LinkingError::other_error(format!("Cannot copy function `{}`", self.get_name())).into(),
+        };
+
+        if let NodeDataEnum::Internal(parent, alias) = &self.get_body()?.borrow().node.node_type {
+            if let Some(parent) = parent.upgrade() {
+                copy.set_parent_with_alias(&parent, alias.unwrap_or_else(|| intern_field_name(&self.get_name())));
+            }
+        }
+
+        Ok(copy)
+    }
+
     pub fn get_name(&self) -> String {
         match self {
             UserFunctionDefinition::Function(function_def) => function_def.name.clone(),
@@ -1,10 +1,12 @@
 use crate::typesystem::errors::{ErrorStack, LinkingError, LinkingErrorEnum};
 
 pub mod context;
+pub mod decision_table;
 pub mod expression;
 pub mod foreach;
 pub mod functions;
 pub mod ifthenelse;
+pub mod lambda;
 pub mod metaphors;
 pub mod operators;
 pub mod selections;
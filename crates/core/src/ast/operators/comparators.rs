@@ -1,13 +1,15 @@
 use crate::ast::context::context_object::ContextObject;
 use crate::ast::expression::{EvaluatableExpression, StaticLink};
 use crate::ast::operators::comparators::ComparatorEnum::*;
-use crate::ast::operators::math_operators::{Operator, OperatorData};
-use crate::ast::token::ExpressionEnum;
+use crate::ast::operators::math_operators::{write_operands, Operator, OperatorData};
+use crate::ast::token::{EPriorities, ExpressionEnum};
 use crate::ast::Link;
-use crate::runtime::execution_context::ExecutionContext;
+use crate::runtime::execution_context::{ExecutionContext, RealTolerance};
 use crate::tokenizer::utils::CharStream;
 use crate::typesystem::errors::{LinkingError, ParseErrorEnum, RuntimeError};
-use crate::typesystem::types::{TypedValue, ValueType};
+use crate::typesystem::types::number::NumberEnum;
+use crate::typesystem::types::number::NumberEnum::{Int, Real};
+use crate::typesystem::types::{Float, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum;
 use crate::typesystem::values::ValueEnum::{
     BooleanValue, DateTimeValue, DateValue, DurationValue as DurationVariant, NumberValue,
@@ -200,8 +202,42 @@ impl ComparatorOperator {
         Self::date_datetime_ordering(date, datetime).reverse()
     }
 
-    fn eval_operator(&self, left: &ValueEnum, right: &ValueEnum) -> Result<ValueEnum, RuntimeError> {
+    /// Orders two numbers where at least one is a real, treating them as equal when they differ by at most `tolerance`
+    fn tolerant_number_ordering(left: &NumberEnum, right: &NumberEnum, tolerance: Float) -> Option<Ordering> {
+        let (left, right) = match (left, right) {
+            (Real(left), Real(right)) => (*left, *right),
+            (Real(left), Int(right)) => (*left, Float::from(*right)),
+            (Int(left), Real(right)) => (Float::from(*left), *right),
+            _ => return None,
+        };
+
+        let difference = left.checked_sub(right)?;
+        Some(if difference.abs() <= tolerance { Ordering::Equal } else { left.cmp(&right) })
+    }
+
+    fn eval_operator(
+        &self,
+        left: &ValueEnum,
+        right: &ValueEnum,
+        real_tolerance: Option<&RealTolerance>,
+    ) -> Result<ValueEnum, RuntimeError> {
         use crate::typesystem::values::ValueOrSv::Value;
+
+        if let (Some(tolerance), NumberValue(left), NumberValue(right)) = (real_tolerance, left, right) {
+            if let Some(ordering) = Self::tolerant_number_ordering(left, right, tolerance.tolerance) {
+                tolerance.mark_applied();
+                let result = match self.data.operator {
+                    Equals => ordering == Ordering::Equal,
+                    NotEquals => ordering != Ordering::Equal,
+                    Less => ordering == Ordering::Less,
+                    Greater => ordering == Ordering::Greater,
+                    LessEquals => ordering != Ordering::Greater,
+                    GreaterEquals => ordering != Ordering::Less,
+                };
+                return Ok(BooleanValue(result));
+            }
+        }
+
         match (left, &self.data.operator, right) {
             (NumberValue(left), Equals, NumberValue(right)) => Ok(BooleanValue(left == right)),
             (BooleanValue(left), Equals, BooleanValue(right)) => Ok(BooleanValue(left == right)),
@@ -320,9 +356,10 @@ impl ComparatorOperator {
 impl EvaluatableExpression for ComparatorOperator {
     fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
         let left_token = &self.data.left.eval(Rc::clone(&context))?;
-        let right_token = &self.data.right.eval(context)?;
+        let right_token = &self.data.right.eval(Rc::clone(&context))?;
+        let real_tolerance = context.borrow().real_tolerance.clone();
 
-        self.eval_operator(left_token, right_token)
+        self.eval_operator(left_token, right_token, real_tolerance.as_deref())
     }
 }
 
@@ -338,7 +375,7 @@ impl Display for ComparatorOperator {
 
 impl Display for OperatorData<ComparatorEnum> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.left, self.operator, self.right)
+        write_operands(f, self, EPriorities::ComparatorPriority as u32)
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::ast::context::context_object::ContextObject;
 use crate::ast::expression::{EvaluatableExpression, StaticLink};
 use crate::ast::operators::logical_operators::LogicalOperatorEnum::*;
-use crate::ast::operators::math_operators::{Operator, OperatorData};
+use crate::ast::operators::math_operators::{write_operands, Operator, OperatorData};
 use crate::ast::token::{EPriorities, ExpressionEnum};
 use crate::ast::Link;
 use crate::runtime::execution_context::ExecutionContext;
@@ -121,7 +121,14 @@ impl Display for LogicalOperator {
 
 impl Display for OperatorData<LogicalOperatorEnum> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.left, self.operator, self.right)
+        match self.operator {
+            // right side of `not` is a placeholder
+            Not => match &self.left {
+                ExpressionEnum::Operator(_) => write!(f, "{} ({})", self.operator, self.left),
+                operand => write!(f, "{} {}", self.operator, operand),
+            },
+            _ => write_operands(f, self, self.operator.clone() as u32),
+        }
     }
 }
 
@@ -1,14 +1,16 @@
 use crate::ast::context::context_object::ContextObject;
 use crate::ast::expression::{EvaluatableExpression, StaticLink};
+use crate::ast::functions::function_numeric::number_power;
+use crate::ast::operators::comparators::ComparatorOperator;
+use crate::ast::operators::logical_operators::LogicalOperator;
 use crate::ast::operators::math_operators::MathOperatorEnum::*;
 use crate::ast::token::EToken::Unparsed;
 use crate::ast::token::ExpressionEnum::{Value, Variable};
-use crate::ast::token::{EToken, EUnparsedToken, ExpressionEnum};
+use crate::ast::token::{EPriorities, EToken, EUnparsedToken, ExpressionEnum};
 use crate::ast::Link;
 use crate::runtime::execution_context::*;
 use crate::typesystem::errors::{LinkingError, ParseErrorEnum, RuntimeError};
 use crate::typesystem::types::number::NumberEnum;
-use crate::typesystem::types::number::NumberEnum::{Int, Real};
 use crate::typesystem::types::string::StringEnum as TStringEnum;
 use crate::typesystem::types::ValueType::StringType;
 use crate::typesystem::types::ValueType::{DateTimeType, DateType, DurationType, NumberType, PeriodType, TimeType};
@@ -19,7 +21,7 @@ use crate::typesystem::values::ValueEnum::{
     TimeValue,
 };
 use crate::typesystem::values::{DurationValue as ErDurationValue, PeriodValue as ErPeriodValue, ValueOrSv};
-use rust_decimal::prelude::*;
+use std::any::Any;
 use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::fmt;
@@ -50,13 +52,39 @@ pub struct OperatorData<T: Display> {
 
 impl Display for OperatorData<MathOperatorEnum> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.operator {
-            Multiplication | Division | Power => {
-                write!(f, "{} {} {}", self.left, self.operator, self.right)
-            }
-            _ => write!(f, "({} {} {})", self.left, self.operator, self.right),
+        write_operands(f, self, self.operator.priority() as u32)
+    }
+}
+
+/// Writes `left operator right` wrapping operands in parentheses when they bind looser than the operator.
+/// Operators are merged from left to right, so the right operand of the same priority must be wrapped as well.
+pub(crate) fn write_operands<T: Display>(f: &mut Formatter<'_>, data: &OperatorData<T>, priority: u32) -> fmt::Result {
+    let wrap = |operand: &ExpressionEnum, is_right: bool| match operator_priority(operand) {
+        Some(operand_priority) if operand_priority < priority || (is_right && operand_priority == priority) => {
+            format!("({})", operand)
         }
+        _ => operand.to_string(),
+    };
+
+    write!(f, "{} {} {}", wrap(&data.left, false), data.operator, wrap(&data.right, true))
+}
+
+/// Priority of the operator at the top of the expression, `None` for anything that never needs parentheses
+fn operator_priority(expression: &ExpressionEnum) -> Option<u32> {
+    let ExpressionEnum::Operator(operator) = expression else {
+        return None;
+    };
+    let operator = operator.as_ref() as &dyn Any;
+
+    if let Some(math) = operator.downcast_ref::<MathOperator>() {
+        return Some(math.data.operator.priority() as u32);
     }
+
+    if let Some(logical) = operator.downcast_ref::<LogicalOperator>() {
+        return Some(logical.data.operator.clone() as u32);
+    }
+
+    operator.downcast_ref::<ComparatorOperator>().map(|_| EPriorities::ComparatorPriority as u32)
 }
 
 impl<T: Display> OperatorData<T> {
@@ -108,6 +136,16 @@ impl MathOperatorEnum {
         }
     }
 
+    /// Same priority the parser assigns to the operator token
+    pub fn priority(&self) -> EPriorities {
+        match self {
+            Addition => EPriorities::Plus,
+            Subtraction => EPriorities::Minus,
+            Multiplication | Division | Modulus => EPriorities::DivideMultiply,
+            Power => EPriorities::PowerPriority,
+        }
+    }
+
     pub fn build(operator: &str) -> EToken {
         match MathOperatorEnum::try_from(operator) {
             Ok(operator) => operator.into(),
@@ -285,21 +323,7 @@ impl MathOperator {
                 }
                 Ok(left / right)
             },
-            Power => |left: NumberEnum, right: NumberEnum| -> Result<NumberEnum, RuntimeError> {
-                match (left, right) {
-                    (Int(left), Int(right)) => {
-                        if right < 0 {
-                            Ok(NumberEnum::from((left as f64).powf(right as f64)))
-                        } else {
-                            Ok(NumberEnum::from(left.pow(right as u32)))
-                        }
-                    }
-                    (Real(left), Int(right)) => Ok(NumberEnum::from(left.powi(right))),
-                    (Int(left), Real(right)) => Ok(NumberEnum::from((left as f64).powf(right.to_f64().unwrap_or(0.0)))),
-                    (Real(left), Real(right)) => Ok(NumberEnum::from(left.powf(right.to_f64().unwrap_or(0.0)))),
-                    _ => RuntimeError::internal_integrity_error(100).into(),
-                }
-            },
+            Power => number_power,
             Modulus => |left: NumberEnum, right: NumberEnum| -> Result<NumberEnum, RuntimeError> {
                 match right {
                     NumberEnum::Int(0) => return Err(RuntimeError::division_by_zero()),
@@ -360,7 +384,7 @@ fn operate_period_values(
     ErPeriodValue::from_signed_parts(months_total, days_total)
 }
 
-fn shift_date_by_months(date: time::Date, months_delta: i128) -> Result<time::Date, RuntimeError> {
+pub(crate) fn shift_date_by_months(date: time::Date, months_delta: i128) -> Result<time::Date, RuntimeError> {
     if months_delta == 0 {
         return Ok(date);
     }
@@ -369,8 +393,9 @@ fn shift_date_by_months(date: time::Date, months_delta: i128) -> Result<time::Da
         .map_err(|_| RuntimeError::eval_error("Month offset is out of range for date adjustment".to_string()))?;
 
     let year = date.year();
-    let mut month = date.month() as i32;
-    month += delta_i32;
+    let month = (date.month() as i32)
+        .checked_add(delta_i32)
+        .ok_or_else(|| RuntimeError::eval_error("Month offset is out of range for date adjustment".to_string()))?;
     let mut new_year = year + (month - 1) / 12;
     let mut new_month = (month - 1) % 12 + 1;
     if new_month <= 0 {
@@ -1,20 +1,23 @@
 use crate::ast::context::context_object::ContextObject;
 use crate::ast::context::context_object_builder::ContextObjectBuilder;
 use crate::ast::expression::{missing_for_type, EvaluatableExpression, StaticLink};
-use crate::ast::token::ExpressionEnum;
+use crate::ast::functions::function_list::value_ordering;
 use crate::ast::token::ExpressionEnum::Variable;
+use crate::ast::token::{ComplexTypeRef, ExpressionEnum};
 use crate::ast::variable::VariableLink;
 use crate::ast::{is_linked, Link};
-use crate::link::node_data::Node;
+use crate::link::linker;
+use crate::link::node_data::{ContentHolder, Node};
 use crate::runtime::execution_context::*;
 use crate::typesystem::errors::ParseErrorEnum::WrongFormat;
 use crate::typesystem::errors::{ErrorStack, LinkingError, ParseErrorEnum, RuntimeError, RuntimeErrorEnum};
 use crate::typesystem::types::number::NumberEnum as Num;
 use crate::typesystem::types::number::NumberEnum::Int;
+use crate::typesystem::types::string::StringEnum;
 use crate::typesystem::types::{TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum::{
     BooleanValue, DateTimeValue, DateValue, DurationValue as DurationVariant, NumberValue,
-    PeriodValue as PeriodVariant, Reference, TimeValue,
+    PeriodValue as PeriodVariant, Reference, StringValue, TimeValue,
 };
 use crate::typesystem::values::{number_value_from_i128, ArrayValue, ValueEnum, ValueOrSv};
 use std::cell::RefCell;
@@ -94,11 +97,11 @@ impl ExpressionFilter {
             let method = self.method.eval(Rc::clone(&context))?;
 
             if let NumberValue(Int(number)) = method {
-                if number < 0 {
+                // a negative index counts from the end, an index outside the list is missing
+                let Some(idx) = resolve_index(number, array.len()) else {
                     let element_type = list_type.get_list_type().unwrap_or(ValueType::UndefinedType);
                     return missing_for_type(&element_type, None, &context);
-                }
-                let idx = number as usize;
+                };
                 match array {
                     ArrayValue::EmptyUntyped => {
                         let element_type = list_type.get_list_type().unwrap_or(ValueType::UndefinedType);
@@ -130,119 +133,568 @@ impl ExpressionFilter {
         candidate: ValueEnum,
         context: Rc<RefCell<ExecutionContext>>,
     ) -> Result<bool, RuntimeError> {
-        let interpret_result = |result: Result<ValueEnum, RuntimeError>| match result {
-            Ok(BooleanValue(true)) => Ok(true),
-            Ok(BooleanValue(false)) => Ok(false),
-            Ok(_) => Ok(false),
-            Err(err) => match err.kind() {
-                RuntimeErrorEnum::RuntimeFieldNotFound(_, field) => {
-                    if field == "it" {
-                        return Ok(false);
-                    }
-                    Err(err)
-                }
-                _ => Err(err),
-            },
-        };
+        eval_predicate_for_element(&self.method, candidate, context)
+    }
+}
 
-        match &candidate {
-            Reference(reference_ctx) => {
-                let element_ctx = Rc::clone(reference_ctx);
-                let previous_context_variable = element_ctx.borrow().context_variable.clone();
+/// Position of `index` in a list of `len` elements, where `-1` is the last element
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)
+    } else {
+        usize::try_from(index).ok()
+    }
+}
 
-                element_ctx.borrow_mut().context_variable = Some(candidate.clone());
-                let evaluation = self.method.eval(Rc::clone(&element_ctx));
-                element_ctx.borrow_mut().context_variable = previous_context_variable;
+/// Evaluates a per-element predicate: anything other than `true` does not hold
+fn eval_predicate_for_element(
+    predicate: &ExpressionEnum,
+    candidate: ValueEnum,
+    context: Rc<RefCell<ExecutionContext>>,
+) -> Result<bool, RuntimeError> {
+    match eval_for_element(predicate, candidate, context) {
+        Ok(BooleanValue(true)) => Ok(true),
+        Ok(BooleanValue(false)) => Ok(false),
+        Ok(_) => Ok(false),
+        Err(err) => match err.kind() {
+            RuntimeErrorEnum::RuntimeFieldNotFound(_, field) => {
+                if field == "it" {
+                    return Ok(false);
+                }
+                Err(err)
+            }
+            _ => Err(err),
+        },
+    }
+}
 
-                interpret_result(evaluation)
+/// Evaluates `method` with `candidate` bound as the context variable (`it` or `...`)
+fn eval_for_element(
+    method: &ExpressionEnum,
+    candidate: ValueEnum,
+    context: Rc<RefCell<ExecutionContext>>,
+) -> Result<ValueEnum, RuntimeError> {
+    match &candidate {
+        Reference(reference_ctx) => {
+            let element_ctx = Rc::clone(reference_ctx);
+            let previous_context_variable = element_ctx.borrow().context_variable.clone();
+
+            element_ctx.borrow_mut().context_variable = Some(candidate.clone());
+            let evaluation = method.eval(Rc::clone(&element_ctx));
+            element_ctx.borrow_mut().context_variable = previous_context_variable;
+
+            evaluation
+        }
+        _ => {
+            let tmp_ctx =
+                ExecutionContext::create_temp_child_context(Rc::clone(&context), ContextObjectBuilder::new().build());
+            {
+                tmp_ctx.borrow_mut().context_variable = Some(candidate.clone());
             }
-            _ => {
-                let tmp_ctx = ExecutionContext::create_temp_child_context(
-                    Rc::clone(&context),
-                    ContextObjectBuilder::new().build(),
-                );
-                {
-                    tmp_ctx.borrow_mut().context_variable = Some(candidate.clone());
+            method.eval(Rc::clone(&tmp_ctx))
+        }
+    }
+}
+
+/// Context in which a per-element method is linked: the fields of object elements are reachable directly and
+/// the element itself is the context variable. Returns the context and the element type.
+fn link_element_context(
+    ctx: Rc<RefCell<ContextObject>>,
+    source: &ExpressionEnum,
+    inner: &Option<Box<ValueType>>,
+) -> Link<(Rc<RefCell<ContextObject>>, ValueType)> {
+    let mut builder = ContextObjectBuilder::new_internal(Rc::clone(&ctx));
+
+    if let Some(inner_type) = inner.as_ref() {
+        if let ValueType::ObjectType(object_type) = inner_type.as_ref() {
+            builder
+                .append_if_missing(Rc::clone(object_type))
+                .map_err(|err| LinkingError::other_error(err.to_string()))?;
+
+            if let ExpressionEnum::Collection(collection) = source {
+                for element in &collection.elements {
+                    if let ExpressionEnum::StaticObject(obj) = element {
+                        builder
+                            .append_if_missing(Rc::clone(obj))
+                            .map_err(|err| LinkingError::other_error(err.to_string()))?;
+                    }
                 }
-                interpret_result(self.method.eval(Rc::clone(&tmp_ctx)))
             }
         }
     }
+
+    let element_type = inner.as_ref().map(|boxed| (**boxed).clone()).unwrap_or(ValueType::UndefinedType);
+    let element_type = flatten_list_type(element_type);
+
+    builder.set_context_type(element_type.clone());
+    builder.set_allow_it(true);
+
+    Ok((builder.build(), element_type))
+}
+
+/// Links the list source of a per-element construct such as a filter or `sortBy`, `name` describes the construct in
+/// the error for a non-list source. Returns the source type, the per-element context and the element type.
+fn link_list_source(
+    ctx: Rc<RefCell<ContextObject>>,
+    name: &str,
+    source: &mut ExpressionEnum,
+) -> Link<(ValueType, Rc<RefCell<ContextObject>>, ValueType)> {
+    let source_type = source.link(Rc::clone(&ctx))?;
+    let ValueType::ListType(inner) = &source_type else {
+        let subject = Some(format!("{} subject `{}`", name, source));
+        return LinkingError::types_not_compatible(subject, source_type, Some(vec![ValueType::ListType(None)])).into();
+    };
+
+    let (element_context, element_type) = link_element_context(ctx, source, inner)?;
+    Ok((source_type, element_context, element_type))
+}
+
+/// Evaluates the list source of a per-element construct, `action` describes the construct in the error for a
+/// non-list value
+fn eval_list_source(
+    source: &ExpressionEnum,
+    action: &str,
+    context: &Rc<RefCell<ExecutionContext>>,
+) -> Result<ArrayValue, RuntimeError> {
+    match source.eval(Rc::clone(context))? {
+        ValueEnum::Array(array) => Ok(array),
+        other => RuntimeError::eval_error(format!(
+            "Cannot {} '{}' because data type is {} and not an array",
+            action,
+            source,
+            other.get_type()
+        ))
+        .into(),
+    }
+}
+
+/// Elements of the evaluated list source, object elements are given as references
+fn eval_list_elements(
+    source: &ExpressionEnum,
+    action: &str,
+    context: &Rc<RefCell<ExecutionContext>>,
+) -> Result<Vec<ValueEnum>, RuntimeError> {
+    Ok(match eval_list_source(source, action, context)? {
+        ArrayValue::EmptyUntyped => Vec::new(),
+        ArrayValue::PrimitivesArray { values, .. } => values,
+        ArrayValue::ObjectsArray { values, .. } => values.into_iter().map(Reference).collect(),
+    })
 }
 
 impl StaticLink for ExpressionFilter {
     fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
         if !is_linked(&self.return_type) {
-            let source_type = self.source.link(Rc::clone(&ctx))?;
-
-            if let ValueType::ListType(inner) = source_type {
-                let mut builder = ContextObjectBuilder::new_internal(Rc::clone(&ctx));
-
-                if let Some(inner_type) = inner.as_ref() {
-                    if let ValueType::ObjectType(object_type) = inner_type.as_ref() {
-                        if let Err(err) = builder.append_if_missing(Rc::clone(object_type)) {
-                            let linking_error = LinkingError::other_error(err.to_string());
-                            self.method_type = Err(linking_error.clone());
-                            self.return_type = Err(linking_error.clone());
-                            return Err(linking_error);
-                        }
-
-                        if let ExpressionEnum::Collection(collection) = &self.source {
-                            for element in &collection.elements {
-                                if let ExpressionEnum::StaticObject(obj) = element {
-                                    if let Err(err) = builder.append_if_missing(Rc::clone(obj)) {
-                                        let linking_error = LinkingError::other_error(err.to_string());
-                                        self.method_type = Err(linking_error.clone());
-                                        self.return_type = Err(linking_error.clone());
-                                        return Err(linking_error);
-                                    }
-                                }
-                            }
-                        }
-                    }
+            let (source_type, method_context, element_type) = match link_list_source(ctx, "Filter", &mut self.source) {
+                Ok(linked) => linked,
+                Err(linking_error) => {
+                    self.method_type = Err(linking_error.clone());
+                    self.return_type = Err(linking_error.clone());
+                    return Err(linking_error);
                 }
+            };
 
-                let element_type = inner.as_ref().map(|boxed| (**boxed).clone()).unwrap_or(ValueType::UndefinedType);
-                let element_type = flatten_list_type(element_type);
+            self.method_type = self.method.link(method_context);
+            let static_type = match &self.method_type.clone()? {
+                ValueType::BooleanType | ValueType::RangeType => source_type,
+                _ => element_type,
+            };
 
-                builder.set_context_type(element_type.clone());
-                builder.set_allow_it(true);
+            self.return_type = Ok(static_type);
+        }
 
-                let method_context = builder.build();
+        self.return_type.clone()
+    }
+}
 
-                self.method_type = self.method.link(Rc::clone(&method_context));
-                let static_type = match &self.method_type.clone()? {
-                    ValueType::BooleanType | ValueType::RangeType => ValueType::ListType(inner.clone()),
-                    _ => element_type,
-                };
+impl EvaluatableExpression for ExpressionFilter {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let array = eval_list_source(&self.source, "filter", &context)?;
+        let list_type = array.list_type();
+        self.select_from_list(array, list_type, context)
+    }
+}
 
-                self.return_type = Ok(static_type);
-            } else {
-                self.return_type =
-                    LinkingError::expect_array_type(Some(format!("Filter subject `{}`", self.source)), source_type);
+//--------------------------------------------------------------------------------------------------
+
+/// `groupBy(list, key)` evaluates `key` for every element the same way filters do, so the element is `it` and
+/// fields of object elements are reachable directly. Elements with equal keys are collected into
+/// `{ key: <key>; items: <elements> }` objects, ordered by the first appearance of each key.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct GroupBy {
+    pub source: ExpressionEnum,
+    pub key: ExpressionEnum,
+    pub group_type: Link<Rc<RefCell<ContextObject>>>,
+}
+
+impl GroupBy {
+    const KEY_FIELD: &'static str = "key";
+    const ITEMS_FIELD: &'static str = "items";
+
+    pub fn build(source: ExpressionEnum, key: ExpressionEnum) -> Result<Self, ParseErrorEnum> {
+        Ok(GroupBy { source, key, group_type: LinkingError::not_linked().into() })
+    }
+
+    fn link_group_type(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<Rc<RefCell<ContextObject>>> {
+        let (source_type, key_context, _) = link_list_source(ctx, "groupBy", &mut self.source)?;
+        let key_type = self.key.link(key_context)?;
+
+        let mut builder = ContextObjectBuilder::new();
+        builder
+            .add_expression(Self::KEY_FIELD, ExpressionEnum::TypePlaceholder(ComplexTypeRef::from_value_type(key_type)))
+            .and_then(|builder| {
+                builder.add_expression(
+                    Self::ITEMS_FIELD,
+                    ExpressionEnum::TypePlaceholder(ComplexTypeRef::from_value_type(source_type)),
+                )
+            })
+            .map_err(|err| LinkingError::other_error(err.to_string()))?;
+        let group_type = builder.build();
+        linker::link_parts(Rc::clone(&group_type))?;
+
+        Ok(group_type)
+    }
+}
+
+impl Display for GroupBy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "groupBy({}, {})", self.source, self.key)
+    }
+}
+
+impl StaticLink for GroupBy {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.group_type) {
+            self.group_type = self.link_group_type(ctx);
+        }
+
+        Ok(ValueType::list_of(ValueType::ObjectType(self.group_type.clone()?)))
+    }
+}
+
+impl EvaluatableExpression for GroupBy {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let group_type = self.group_type.clone()?;
+
+        let elements = eval_list_elements(&self.source, "group", &context)?;
+
+        let mut groups: Vec<(ValueEnum, Vec<ValueEnum>)> = Vec::new();
+        for element in elements {
+            let key = eval_for_element(&self.key, element.clone(), Rc::clone(&context))?;
+            match groups.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, items)) => items.push(element),
+                None => groups.push((key, vec![element])),
             }
         }
 
+        let mut values = Vec::with_capacity(groups.len());
+        for (key, items) in groups {
+            let group = ExecutionContext::create_isolated_context(Rc::clone(&group_type));
+            group.borrow().stack_insert(Self::KEY_FIELD, Ok(key));
+            group
+                .borrow()
+                .stack_insert(Self::ITEMS_FIELD, Ok(ValueEnum::Array(ArrayValue::from_values(items)?)));
+            values.push(group);
+        }
+
+        Ok(ValueEnum::Array(ArrayValue::ObjectsArray { values, object_type: group_type }))
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+
+/// `sortBy(list, key)` and `sortByDesc(list, key)` evaluate `key` for every element the same way filters do and
+/// order the elements by it using the same ordering as `sort`. The sort is stable: elements with equal keys keep
+/// their original order in both directions.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct SortBy {
+    pub source: ExpressionEnum,
+    pub key: ExpressionEnum,
+    pub descending: bool,
+    pub return_type: Link<ValueType>,
+}
+
+impl SortBy {
+    pub fn build(source: ExpressionEnum, key: ExpressionEnum, descending: bool) -> Result<Self, ParseErrorEnum> {
+        Ok(SortBy { source, key, descending, return_type: LinkingError::not_linked().into() })
+    }
+
+    fn name(&self) -> &'static str {
+        if self.descending {
+            "sortByDesc"
+        } else {
+            "sortBy"
+        }
+    }
+
+    fn sorted<T>(
+        &self,
+        elements: Vec<T>,
+        as_value: impl Fn(&T) -> ValueEnum,
+        context: &Rc<RefCell<ExecutionContext>>,
+    ) -> Result<Vec<T>, RuntimeError> {
+        let mut keyed = Vec::with_capacity(elements.len());
+        for element in elements {
+            let key = eval_for_element(&self.key, as_value(&element), Rc::clone(context))?;
+            keyed.push((key, element));
+        }
+
+        if self.descending {
+            keyed.sort_by(|(left, _), (right, _)| value_ordering(right, left));
+        } else {
+            keyed.sort_by(|(left, _), (right, _)| value_ordering(left, right));
+        }
+
+        Ok(keyed.into_iter().map(|(_, element)| element).collect())
+    }
+}
+
+impl Display for SortBy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({}, {})", self.name(), self.source, self.key)
+    }
+}
+
+impl StaticLink for SortBy {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let (source_type, key_context, _) = link_list_source(ctx, self.name(), &mut self.source)?;
+            self.key.link(key_context)?;
+            self.return_type = Ok(source_type);
+        }
+
         self.return_type.clone()
     }
 }
 
-impl EvaluatableExpression for ExpressionFilter {
+impl EvaluatableExpression for SortBy {
     fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
-        let source_value = self.source.eval(Rc::clone(&context))?;
+        match eval_list_source(&self.source, "sort", &context)? {
+            ArrayValue::EmptyUntyped => Ok(ValueEnum::Array(ArrayValue::EmptyUntyped)),
+            ArrayValue::PrimitivesArray { values, item_type } => {
+                let values = self.sorted(values, ValueEnum::clone, &context)?;
+                Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values, item_type }))
+            }
+            ArrayValue::ObjectsArray { values, object_type } => {
+                let values = self.sorted(values, |reference| Reference(Rc::clone(reference)), &context)?;
+                Ok(ValueEnum::Array(ArrayValue::ObjectsArray { values, object_type }))
+            }
+        }
+    }
+}
 
-        match source_value {
-            ValueEnum::Array(array) => {
-                let list_type = array.list_type();
-                self.select_from_list(array, list_type, context)
+//--------------------------------------------------------------------------------------------------
+
+/// `takeWhile(list, predicate)` keeps the elements before the first one for which the predicate does not hold and
+/// `dropWhile(list, predicate)` keeps that element and everything after it. The predicate is evaluated per element
+/// the same way filters do.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct WhileSelection {
+    pub source: ExpressionEnum,
+    pub predicate: ExpressionEnum,
+    pub take: bool,
+    pub return_type: Link<ValueType>,
+}
+
+impl WhileSelection {
+    pub fn build(source: ExpressionEnum, predicate: ExpressionEnum, take: bool) -> Result<Self, ParseErrorEnum> {
+        Ok(WhileSelection { source, predicate, take, return_type: LinkingError::not_linked().into() })
+    }
+
+    fn name(&self) -> &'static str {
+        if self.take {
+            "takeWhile"
+        } else {
+            "dropWhile"
+        }
+    }
+
+    fn select<T>(
+        &self,
+        mut elements: Vec<T>,
+        as_value: impl Fn(&T) -> ValueEnum,
+        context: &Rc<RefCell<ExecutionContext>>,
+    ) -> Result<Vec<T>, RuntimeError> {
+        let mut split = elements.len();
+        for (index, element) in elements.iter().enumerate() {
+            if !eval_predicate_for_element(&self.predicate, as_value(element), Rc::clone(context))? {
+                split = index;
+                break;
             }
-            _ => RuntimeError::eval_error(format!(
-                "Cannot filter '{}' because data type is {} and not an array",
-                self.source,
-                source_value.get_type()
-            ))
-            .into(),
         }
+
+        if self.take {
+            elements.truncate(split);
+            Ok(elements)
+        } else {
+            Ok(elements.split_off(split))
+        }
+    }
+}
+
+impl Display for WhileSelection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({}, {})", self.name(), self.source, self.predicate)
+    }
+}
+
+impl StaticLink for WhileSelection {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let (source_type, predicate_context, _) = link_list_source(ctx, self.name(), &mut self.source)?;
+            let predicate_type = self.predicate.link(predicate_context)?;
+            LinkingError::expect_single_type(
+                &format!("{} predicate `{}`", self.name(), self.predicate),
+                predicate_type,
+                &ValueType::BooleanType,
+            )?;
+            self.return_type = Ok(source_type);
+        }
+
+        self.return_type.clone()
+    }
+}
+
+impl EvaluatableExpression for WhileSelection {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        match eval_list_source(&self.source, "select from", &context)? {
+            ArrayValue::EmptyUntyped => Ok(ValueEnum::Array(ArrayValue::EmptyUntyped)),
+            ArrayValue::PrimitivesArray { values, item_type } => {
+                let values = self.select(values, ValueEnum::clone, &context)?;
+                Ok(ValueEnum::Array(ArrayValue::PrimitivesArray { values, item_type }))
+            }
+            ArrayValue::ObjectsArray { values, object_type } => {
+                let values = self.select(values, |reference| Reference(Rc::clone(reference)), &context)?;
+                Ok(ValueEnum::Array(ArrayValue::ObjectsArray { values, object_type }))
+            }
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+
+/// `all(list, predicate)` holds if the predicate holds for every element and `any(list, predicate)` holds if it
+/// holds for at least one element. The predicate is evaluated per element the same way filters do and evaluation
+/// stops at the first element that decides the result, so an empty list gives `true` for `all` and `false` for `any`.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct Quantifier {
+    pub source: ExpressionEnum,
+    pub predicate: ExpressionEnum,
+    pub all: bool,
+    pub return_type: Link<ValueType>,
+}
+
+impl Quantifier {
+    pub fn build(source: ExpressionEnum, predicate: ExpressionEnum, all: bool) -> Result<Self, ParseErrorEnum> {
+        Ok(Quantifier { source, predicate, all, return_type: LinkingError::not_linked().into() })
+    }
+
+    fn name(&self) -> &'static str {
+        if self.all {
+            "all"
+        } else {
+            "any"
+        }
+    }
+
+    fn decide(
+        &self,
+        elements: impl Iterator<Item = ValueEnum>,
+        context: &Rc<RefCell<ExecutionContext>>,
+    ) -> Result<ValueEnum, RuntimeError> {
+        for element in elements {
+            if eval_predicate_for_element(&self.predicate, element, Rc::clone(context))? != self.all {
+                return Ok(BooleanValue(!self.all));
+            }
+        }
+        Ok(BooleanValue(self.all))
+    }
+}
+
+impl Display for Quantifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({}, {})", self.name(), self.source, self.predicate)
+    }
+}
+
+impl StaticLink for Quantifier {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let (_, predicate_context, _) = link_list_source(ctx, self.name(), &mut self.source)?;
+            let predicate_type = self.predicate.link(predicate_context)?;
+            LinkingError::expect_single_type(
+                &format!("{} predicate `{}`", self.name(), self.predicate),
+                predicate_type,
+                &ValueType::BooleanType,
+            )?;
+            self.return_type = Ok(ValueType::BooleanType);
+        }
+
+        self.return_type.clone()
+    }
+}
+
+impl EvaluatableExpression for Quantifier {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let elements = eval_list_elements(&self.source, "check", &context)?;
+        self.decide(elements.into_iter(), &context)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+
+/// `flatMap(list, expression)` evaluates `expression` for every element the same way filters do and concatenates
+/// the resulting lists one level deep. A scalar result is taken as a single-element list.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct FlatMap {
+    pub source: ExpressionEnum,
+    pub projection: ExpressionEnum,
+    pub return_type: Link<ValueType>,
+}
+
+impl FlatMap {
+    pub fn build(source: ExpressionEnum, projection: ExpressionEnum) -> Result<Self, ParseErrorEnum> {
+        Ok(FlatMap { source, projection, return_type: LinkingError::not_linked().into() })
+    }
+}
+
+impl Display for FlatMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "flatMap({}, {})", self.source, self.projection)
+    }
+}
+
+impl StaticLink for FlatMap {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let (_, projection_context, _) = link_list_source(ctx, "flatMap", &mut self.source)?;
+            self.return_type = match self.projection.link(projection_context)? {
+                list_type @ ValueType::ListType(_) => Ok(list_type),
+                item_type => Ok(ValueType::list_of(item_type)),
+            };
+        }
+
+        self.return_type.clone()
+    }
+}
+
+impl EvaluatableExpression for FlatMap {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let elements = eval_list_elements(&self.source, "flatMap", &context)?;
+
+        let mut items = Vec::new();
+        for element in elements {
+            match eval_for_element(&self.projection, element, Rc::clone(&context))? {
+                ValueEnum::Array(ArrayValue::EmptyUntyped) => {}
+                ValueEnum::Array(ArrayValue::PrimitivesArray { values, .. }) => items.extend(values),
+                ValueEnum::Array(ArrayValue::ObjectsArray { values, .. }) => {
+                    items.extend(values.into_iter().map(Reference))
+                }
+                scalar => items.push(scalar),
+            }
+        }
+
+        // item types are merged the same way `flatten` does, an empty result stays untyped
+        Ok(ValueEnum::Array(ArrayValue::from_values(items)?))
     }
 }
 
@@ -453,3 +905,99 @@ impl Display for FieldSelection {
 }
 
 //--------------------------------------------------------------------------------------------------
+
+/// `lookup(table, 'keyField', keyValue, 'valueField')` returns `valueField` of the first object whose `keyField`
+/// equals `keyValue`, or `Missing` when there is no such object. Field names must be string literals, so the
+/// result is typed by the value field.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct LookupFunction {
+    pub table: ExpressionEnum,
+    pub key_field: VariableLink,
+    pub key_value: ExpressionEnum,
+    pub value_field: VariableLink,
+    pub return_type: Link<ValueType>,
+}
+
+impl LookupFunction {
+    pub fn build(mut args: Vec<ExpressionEnum>) -> Result<Self, ParseErrorEnum> {
+        let field_name = |arg: ExpressionEnum| match arg {
+            ExpressionEnum::Value(StringValue(StringEnum::String(name))) => Ok(VariableLink::new_unlinked(name)),
+            other => Err(WrongFormat(format!("lookup expects a field name as a string literal, got {}", other))),
+        };
+
+        // the builder guarantees exactly 4 arguments
+        let value_field = field_name(args.pop().unwrap())?;
+        let key_value = args.pop().unwrap();
+        let key_field = field_name(args.pop().unwrap())?;
+        let table = args.pop().unwrap();
+
+        Ok(LookupFunction { table, key_field, key_value, value_field, return_type: LinkingError::not_linked().into() })
+    }
+
+    /// The field must belong to the row object itself, not to any of its parents
+    fn link_field(field: &mut VariableLink, object_type: &Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        object_type.borrow().get(field.get_name().as_str())?;
+        field.link(Rc::clone(object_type))
+    }
+}
+
+impl StaticLink for LookupFunction {
+    fn link(&mut self, ctx: Rc<RefCell<ContextObject>>) -> Link<ValueType> {
+        if !is_linked(&self.return_type) {
+            let object_type = match self.table.link(Rc::clone(&ctx))? {
+                ValueType::ListType(Some(item_type)) => match *item_type {
+                    ValueType::ObjectType(object_type) => object_type,
+                    other => {
+                        return LinkingError::other_error(format!(
+                            "lookup expects a list of objects, got list of {}",
+                            other
+                        ))
+                        .into()
+                    }
+                },
+                other => {
+                    return LinkingError::other_error(format!("lookup expects a list of objects, got {}", other)).into()
+                }
+            };
+
+            let key_type = Self::link_field(&mut self.key_field, &object_type)?;
+            let key_value_type = self.key_value.link(Rc::clone(&ctx))?;
+            LinkingError::expect_type(Some(self.key_field.get_name()), key_value_type, &[key_type])?;
+
+            self.return_type = Self::link_field(&mut self.value_field, &object_type);
+        }
+
+        self.return_type.clone()
+    }
+}
+
+impl EvaluatableExpression for LookupFunction {
+    fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
+        let key_value = self.key_value.eval(Rc::clone(&context))?;
+
+        match self.table.eval(Rc::clone(&context))? {
+            ValueEnum::Array(ArrayValue::ObjectsArray { values, .. }) => {
+                for row in values {
+                    if self.key_field.eval(Rc::clone(&row))? == key_value {
+                        let value = self.value_field.eval(row)?;
+                        if let Reference(child_ctx) = &value {
+                            ExecutionContext::eval_all_fields(child_ctx)?;
+                        }
+                        return Ok(value);
+                    }
+                }
+            }
+            ValueEnum::Array(_) => {}
+            other => return RuntimeError::type_not_supported(other.get_type()).into(),
+        }
+
+        let field_name = self.value_field.get_name();
+        missing_for_type(&self.return_type.clone()?, Some(field_name.as_str()), &context)
+    }
+}
+
+impl Display for LookupFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "lookup({}, '{}', {}, '{}')", self.table, self.key_field, self.key_value, self.value_field)
+    }
+}
@@ -57,7 +57,7 @@ impl StaticLink for CollectionExpression {
     }
 }
 
-fn merge_collection_types(existing: ValueType, new_type: ValueType) -> Link<ValueType> {
+pub(crate) fn merge_collection_types(existing: ValueType, new_type: ValueType) -> Link<ValueType> {
     use ValueType::*;
 
     if existing == new_type {
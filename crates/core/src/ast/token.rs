@@ -36,6 +36,7 @@ pub enum EPriorities {
     ContextPriority = 1,
     Assign = 2,
     RangePriority = 3,
+    LambdaPriority = 4,
     ReservedWords = 5,
 
     // a = b or a = c
@@ -78,6 +79,7 @@ pub enum EUnparsedToken {
     FunctionNameToken(VariableLink),
     FunctionDefinitionLiteralToken(String, Vec<FormalParameter>),
     TypeReferenceLiteralToken(ComplexTypeRef),
+    LambdaToken(Vec<String>),
     MathOperatorToken(MathOperatorEnum),
     LogicalOperatorToken(LogicalOperatorEnum),
     ComparatorToken(ComparatorEnum),
@@ -176,6 +178,8 @@ pub enum DefinitionEnum {
     UserFunction(FunctionDefinition),
     InlineUserFunction(InlineFunctionDefinition),
     UserType(UserTypeDefinition),
+    /// `import "path"` or `import "path" as alias`, the source is loaded by the model import resolver
+    Import(ImportDefinition),
 }
 
 impl Display for DefinitionEnum {
@@ -184,6 +188,7 @@ impl Display for DefinitionEnum {
             UserFunction(m) => write!(f, "{}", m),
             DefinitionEnum::InlineUserFunction(m) => write!(f, "{}", m),
             DefinitionEnum::UserType(t) => write!(f, "type {}: {}", t.name, t.body),
+            DefinitionEnum::Import(import) => write!(f, "{}", import),
         }
     }
 }
@@ -251,6 +256,10 @@ impl StaticLink for ExpressionEnum {
             }
             StaticObject(object) => {
                 // @Todo: it is unknown when this must be linked separately or it is callers responsibility
+                // Spread fields are part of the object type, so they are expanded here, e.g. for `{...base}.a`
+                if !object.borrow().spreads.is_empty() {
+                    ContextObject::expand_spreads(object, &ctx)?;
+                }
                 Ok(ObjectType(Rc::clone(object)))
             }
             TypePlaceholder(tref) => ctx.borrow().resolve_type_ref(tref),
@@ -330,6 +339,7 @@ impl Display for EUnparsedToken {
                 write!(f, "{}({})", text, array_to_code_sep(args.iter(), ", "))
             }
             TypeReferenceLiteralToken(r) => write!(f, "<{}>", r),
+            LambdaToken(params) => write!(f, "({}) =>", params.join(", ")),
             LiteralToken(value) => write!(f, "{}", value),
             AssignToken => write!(f, ":"),
             RangeToken => write!(f, ".."),
@@ -398,6 +408,23 @@ pub struct UserTypeDefinition {
     pub body: UserTypeBody,
 }
 
+/// Imported definitions are inlined into the importing object, or placed under the `alias` object when it is set
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[derive(Clone, PartialEq)]
+pub struct ImportDefinition {
+    pub path: String,
+    pub alias: Option<String>,
+}
+
+impl Display for ImportDefinition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.alias {
+            Some(alias) => write!(f, "import \"{}\" as {}", self.path, alias),
+            None => write!(f, "import \"{}\"", self.path),
+        }
+    }
+}
+
 impl Display for EToken {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,16 +1,18 @@
-use crate::ast::context::context_object::ContextObject;
+use crate::ast::context::context_object::{ContextObject, MethodEntry};
 use crate::ast::context::context_object_type::EObjectContent;
+use crate::ast::context::context_object_type::FormalParameter;
 use crate::ast::context::function_context::FunctionContext;
 use crate::ast::context::function_context::RETURN_EXPRESSION;
 use crate::ast::expression::{CastCall, EvaluatableExpression, StaticLink};
 use crate::ast::metaphors::metaphor::UserFunction;
+use crate::ast::sequence::CollectionExpression;
 use crate::ast::token::{ComplexTypeRef, ExpressionEnum};
 use crate::ast::utils::array_to_code_sep;
 use crate::ast::{is_linked, Link};
 use crate::link::linker;
 use crate::link::node_data::ContentHolder;
 use crate::runtime::execution_context::*;
-use crate::typesystem::errors::{LinkingError, RuntimeError};
+use crate::typesystem::errors::{LinkingError, ParseErrorEnum, RuntimeError};
 use crate::typesystem::types::ValueType;
 use crate::typesystem::values::ValueEnum;
 use crate::typesystem::values::ValueEnum::Reference;
@@ -25,6 +27,14 @@ use std::rc::Rc;
 pub struct UserFunctionCall {
     pub name: String,
     pub args: Vec<ExpressionEnum>,
+    /// Default values of omitted trailing parameters, resolved during linking
+    pub default_args: Vec<ExpressionEnum>,
+    /// Index of the argument that collects rest arguments into a list, set during linking
+    pub variadic_from: Option<usize>,
+    /// `name: value` arguments that follow positional ones, moved into `args` in parameter order during linking
+    pub named_args: Vec<(String, ExpressionEnum)>,
+    /// Indexes of arguments that pass a user function instead of a value, e.g. `inc` in `applyTwice(inc, 1)`
+    pub function_args: Vec<usize>,
     pub definition: Link<FunctionContext>,
     pub return_type: Link<ValueType>,
 }
@@ -34,16 +44,124 @@ impl UserFunctionCall {
         UserFunctionCall {
             name,
             args,
+            default_args: Vec::new(),
+            variadic_from: None,
+            named_args: Vec::new(),
+            function_args: Vec::new(),
             definition: LinkingError::not_linked().into(),
             return_type: LinkingError::not_linked().into(),
         }
     }
+
+    /// Builds a call where `name: value` arguments may follow positional ones, e.g. `f(1, rate: 2)`
+    pub fn with_named_arguments(name: String, args: Vec<ExpressionEnum>) -> Result<UserFunctionCall, ParseErrorEnum> {
+        let mut positional = Vec::new();
+        let mut named: Vec<(String, ExpressionEnum)> = Vec::new();
+
+        for arg in args {
+            match arg {
+                ExpressionEnum::ObjectField(arg_name, value) => {
+                    if named.iter().any(|(existing, _)| existing == &arg_name) {
+                        return Err(ParseErrorEnum::WrongFormat(format!(
+                            "Argument `{}` of function `{}` is passed more than once",
+                            arg_name, name
+                        )));
+                    }
+                    named.push((arg_name, *value));
+                }
+                other => {
+                    if !named.is_empty() {
+                        return Err(ParseErrorEnum::WrongFormat(format!(
+                            "Positional argument `{}` of function `{}` cannot follow named arguments",
+                            other, name
+                        )));
+                    }
+                    positional.push(other);
+                }
+            }
+        }
+
+        let mut call = UserFunctionCall::new(name, positional);
+        call.named_args = named;
+        Ok(call)
+    }
+
+    /// Moves named arguments into `args` in parameter order. Skipped parameters before the last named one
+    /// take their default values.
+    fn resolve_named_arguments(&mut self, parameters: &[FormalParameter]) -> Link<()> {
+        let mut indexes = Vec::with_capacity(self.named_args.len());
+        for (arg_name, _) in &self.named_args {
+            let Some(index) = parameters.iter().position(|param| &param.name == arg_name) else {
+                return LinkingError::other_error(format!(
+                    "Function {} has no parameter named `{}`",
+                    self.name, arg_name
+                ))
+                .into();
+            };
+
+            if index < self.args.len() {
+                return LinkingError::other_error(format!(
+                    "Argument `{}` of function `{}` is already passed positionally",
+                    arg_name, self.name
+                ))
+                .into();
+            }
+
+            if parameters[index].variadic {
+                return LinkingError::other_error(format!(
+                    "Rest parameter `{}` of function `{}` cannot be passed by name",
+                    arg_name, self.name
+                ))
+                .into();
+            }
+
+            indexes.push(index);
+        }
+
+        let last_named = indexes.iter().copied().max().unwrap_or(0);
+        let first_named = self.args.len();
+        if let Some(missing) = (first_named..=last_named)
+            .find(|index| !indexes.contains(index) && parameters[*index].default_value.is_none())
+        {
+            return LinkingError::other_error(format!(
+                "Function {} is missing argument `{}`",
+                self.name, parameters[missing].name
+            ))
+            .into();
+        }
+
+        let mut slots: Vec<Option<ExpressionEnum>> = parameters.iter().map(|_| None).collect();
+        for (index, (_, value)) in indexes.into_iter().zip(std::mem::take(&mut self.named_args)) {
+            slots[index] = Some(value);
+        }
+
+        for index in first_named..=last_named {
+            let value =
+                slots[index].take().or_else(|| parameters[index].default_value.clone().map(ExpressionEnum::Value));
+            self.args.extend(value);
+        }
+
+        Ok(())
+    }
 }
 
 // eval context is not immediately evaluated for output values, but passed to the caller
 impl EvaluatableExpression for UserFunctionCall {
     fn eval(&self, context: Rc<RefCell<ExecutionContext>>) -> Result<ValueEnum, RuntimeError> {
-        let values = self.args.iter().map(|expr| expr.eval(Rc::clone(&context))).collect();
+        let values = self
+            .args
+            .iter()
+            .chain(self.default_args.iter())
+            .enumerate()
+            .map(|(index, expr)| {
+                if self.function_args.contains(&index) {
+                    // bound functions are resolved during linking, the parameter itself holds no value
+                    RuntimeError::eval_error(format!("Function `{}` cannot be used as a value", expr)).into()
+                } else {
+                    expr.eval(Rc::clone(&context))
+                }
+            })
+            .collect();
 
         match &self.definition {
             Ok(definition) => {
@@ -83,12 +201,37 @@ impl StaticLink for UserFunctionCall {
             // Step 1: resolve the function definition in the current scope.
             let definition = linker::find_implementation(Rc::clone(&ctx), self.name.clone())?;
 
-            // Step 2: validate that we have the correct number of arguments.
-            let param_len = {
+            if !self.named_args.is_empty() {
+                let parameters = definition.borrow().function_definition.get_parameters().clone();
+                self.resolve_named_arguments(&parameters)?;
+            }
+
+            // Step 2: validate that we have the correct number of arguments, omitted trailing ones must have defaults.
+            // A rest parameter collects all remaining arguments into a single list argument.
+            let rest_index = {
                 let method = definition.borrow();
-                method.function_definition.get_parameters().len()
+                let params = method.function_definition.get_parameters();
+                params.last().filter(|param| param.variadic).map(|_| params.len() - 1)
             };
-            if self.args.len() != param_len {
+            if let Some(index) = rest_index {
+                if self.variadic_from.is_none() && self.args.len() >= index {
+                    let rest = self.args.split_off(index);
+                    self.args.push(ExpressionEnum::Collection(CollectionExpression::build(rest)));
+                    self.variadic_from = Some(index);
+                }
+            }
+
+            let (param_len, omitted_defaults) = {
+                let method = definition.borrow();
+                let params = method.function_definition.get_parameters();
+                let omitted: Option<Vec<ExpressionEnum>> = params
+                    .iter()
+                    .skip(self.args.len())
+                    .map(|param| param.default_value.clone().map(ExpressionEnum::Value))
+                    .collect();
+                (params.len(), omitted)
+            };
+            let Some(omitted_defaults) = omitted_defaults.filter(|_| self.args.len() <= param_len) else {
                 return LinkingError::other_error(format!(
                     "Function {} expects {} arguments, but {} were provided",
                     self.name,
@@ -96,22 +239,41 @@ impl StaticLink for UserFunctionCall {
                     self.args.len()
                 ))
                 .into();
-            }
+            };
+            self.default_args = omitted_defaults;
 
-            // Step 3: link each argument expression and ensure it matches the declared parameter type.
+            // Step 3: untyped parameters can receive a user function. It is bound to the parameter name in a copy of
+            // the called function, so the body is linked with the functions passed by this call.
+            let declared_parameters = definition.borrow().function_definition.get_parameters().clone();
+            let passed_functions: Vec<Option<Rc<RefCell<MethodEntry>>>> = declared_parameters
+                .iter()
+                .zip(self.args.iter().chain(self.default_args.iter()))
+                .map(|(parameter, argument)| resolve_function_argument(parameter, argument, &ctx))
+                .collect();
+            let definition = if passed_functions.iter().any(Option::is_some) {
+                bind_passed_functions(&definition, &passed_functions)?
+            } else {
+                definition
+            };
+
+            // Step 4: link each argument expression and ensure it matches the declared parameter type.
             let ctx_name = ctx.borrow().node.node_type.to_code();
             let function_name = self.name.clone();
-
-            let (declared_parameters, function_body_ctx) = {
-                let borrowed = definition.borrow();
-                let params = borrowed.function_definition.get_parameters().clone();
-                let body = borrowed.function_definition.get_body()?;
-                (params, body)
-            };
+            let function_body_ctx = definition.borrow().function_definition.get_body()?;
 
             let mut parameters = Vec::new();
 
-            for (parameter, input_argument) in declared_parameters.iter().zip(self.args.iter_mut()) {
+            for (index, (parameter, input_argument)) in
+                declared_parameters.iter().zip(self.args.iter_mut().chain(self.default_args.iter_mut())).enumerate()
+            {
+                if passed_functions[index].is_some() {
+                    if !self.function_args.contains(&index) {
+                        self.function_args.push(index);
+                    }
+                    parameters.push(parameter.with_runtime_type(ValueType::UndefinedType));
+                    continue;
+                }
+
                 // Link the argument within the current call context. Passing the function's own context is disallowed to
                 // prevent accidental self-references before the function body is evaluated.
                 let arg_link_result = if let ExpressionEnum::Variable(var) = input_argument {
@@ -144,10 +306,11 @@ impl StaticLink for UserFunctionCall {
                 }
 
                 if let Some(tref) = parameter.declared_type() {
-                    // Step 4: resolve the parameter's declared type (including aliases) and coerce when safe.
+                    // Step 5: resolve the parameter's declared type (including aliases) and coerce when safe.
                     let expected_type = resolve_declared_type(tref, Some(&function_body_ctx), &ctx)?;
 
                     // Alias parameters may need an explicit cast to resolve the correct runtime type.
+                    // This is how an empty object literal `{}` takes the expected alias with all fields `Missing`.
                     if resolved_type != expected_type
                         && complex_type_ref_contains_alias(tref)
                         && can_cast_alias(&resolved_type, &expected_type)
@@ -168,9 +331,11 @@ impl StaticLink for UserFunctionCall {
                 }
             }
 
-            // Step 5: build and cache the callable function context with all resolved parameter types.
-            self.definition =
-                Ok(definition.borrow().function_definition.create_context(parameters, Some(Rc::clone(&ctx)))?);
+            // Step 6: build and cache the callable function context with all resolved parameter types.
+            // The body is evaluated in the scope where the function is defined, that differs from the call site
+            // when the function is passed as an argument or called from a loop.
+            let scope = function_body_ctx.borrow().node.node_type.get_parent().unwrap_or_else(|| Rc::clone(&ctx));
+            self.definition = Ok(definition.borrow().function_definition.create_context(parameters, Some(scope))?);
 
             // Determine return type respecting explicit return field when present
             let return_key = intern_field_name("return");
@@ -188,6 +353,7 @@ impl StaticLink for UserFunctionCall {
                     }
                 }
             }
+
             self.return_type = Ok(rt.unwrap_or(ValueType::ObjectType(function_body_ctx)));
         }
 
@@ -195,6 +361,61 @@ impl StaticLink for UserFunctionCall {
     }
 }
 
+/// Returns the user function passed as an argument, e.g. `inc` in `applyTwice(inc, 1)`
+fn resolve_function_argument(
+    parameter: &FormalParameter,
+    argument: &ExpressionEnum,
+    ctx: &Rc<RefCell<ContextObject>>,
+) -> Option<Rc<RefCell<MethodEntry>>> {
+    if let Some(lambda) = argument.as_lambda() {
+        return Some(lambda.bind_to(ctx));
+    }
+
+    let ExpressionEnum::Variable(variable) = argument else {
+        return None;
+    };
+
+    if parameter.declared_type().is_some() || variable.path.len() != 1 {
+        return None;
+    }
+
+    linker::find_implementation(Rc::clone(ctx), variable.get_name()).ok()
+}
+
+/// Copy of the called function with the passed functions bound to its parameters. Function bodies are linked once,
+/// so each distinct set of passed functions gets its own copy, shared by all calls that pass the same functions.
+fn bind_passed_functions(
+    method: &Rc<RefCell<MethodEntry>>,
+    passed: &[Option<Rc<RefCell<MethodEntry>>>],
+) -> Link<Rc<RefCell<MethodEntry>>> {
+    let same_functions = |bound: &[Option<Rc<RefCell<MethodEntry>>>]| {
+        bound.len() == passed.len()
+            && bound.iter().zip(passed).all(|pair| match pair {
+                (Some(bound), Some(passed)) => Rc::ptr_eq(bound, passed),
+                (None, None) => true,
+                _ => false,
+            })
+    };
+    if let Some((_, copy)) = method.borrow().bound_copies.iter().find(|(bound, _)| same_functions(bound)) {
+        return Ok(Rc::clone(copy));
+    }
+
+    let definition = method.borrow().function_definition.parse_copy()?;
+    {
+        let body = definition.get_body()?;
+        let mut body = body.borrow_mut();
+        for (parameter, function) in definition.get_parameters().iter().zip(passed) {
+            if let Some(function) = function {
+                body.bound_functions.insert(intern_field_name(parameter.name.as_str()), Rc::clone(function));
+            }
+        }
+    }
+
+    let copy: Rc<RefCell<MethodEntry>> = MethodEntry::from(definition).into();
+    method.borrow_mut().bound_copies.push((passed.to_vec(), Rc::clone(&copy)));
+    Ok(copy)
+}
+
 fn resolve_declared_type(
     tref: &ComplexTypeRef,
     function_ctx: Option<&Rc<RefCell<ContextObject>>>,
@@ -242,6 +463,14 @@ fn can_cast_alias(actual: &ValueType, expected: &ValueType) -> bool {
 
 impl Display for UserFunctionCall {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}({})", self.name, array_to_code_sep(self.args.iter(), ", "))
+        // rest arguments are printed as they were passed, not as the collected list
+        let mut args: Vec<String> = self.args.iter().map(|arg| arg.to_string()).collect();
+        if let (Some(index), Some(ExpressionEnum::Collection(rest))) = (self.variadic_from, self.args.last()) {
+            args.truncate(index);
+            args.extend(rest.elements.iter().map(|arg| arg.to_string()));
+        }
+        args.extend(self.named_args.iter().map(|(name, value)| format!("{}: {}", name, value)));
+
+        write!(f, "{}({})", self.name, array_to_code_sep(args.iter(), ", "))
     }
 }
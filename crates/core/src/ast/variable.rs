@@ -3,8 +3,8 @@ use crate::ast::context::context_object_type::EObjectContent;
 use crate::ast::expression::{missing_for_type, EvaluatableExpression, StaticLink};
 use crate::ast::token::{EToken, ExpressionEnum};
 use crate::ast::{is_linked, Link};
-use crate::link::linker::{browse, build_location_from_context};
-use crate::runtime::execution_context::ExecutionContext;
+use crate::link::linker::{browse, build_location_from_context, get_till_root};
+use crate::runtime::execution_context::{build_location_from_execution_context, ExecutionContext};
 use crate::typesystem::errors::{ErrorStage, LinkingError, LinkingErrorEnum, RuntimeError, RuntimeErrorEnum};
 use crate::typesystem::types::ValueType;
 use crate::typesystem::values::ValueEnum;
@@ -110,6 +110,20 @@ impl EvaluatableExpression for VariableLink {
             }
         };
 
+        let dependencies = context.borrow().dependencies.clone();
+        if let (Some(dependencies), Some(&first)) = (dependencies, path_vec.first()) {
+            let owner = if find_root {
+                get_till_root(Rc::clone(&start_ctx), first).ok().map(|found| found.context)
+            } else {
+                Some(Rc::clone(&start_ctx))
+            };
+            if let Some(owner) = owner {
+                let mut location = build_location_from_execution_context(&owner, first);
+                location.extend(path_vec[1..].iter().map(|segment| segment.to_string()));
+                dependencies.record_read(location.join("."));
+            }
+        }
+
         let browse_result = match browse(start_ctx, path_vec, find_root) {
             Ok(res) => res,
             Err(link_err) => {
@@ -263,8 +277,11 @@ impl StaticLink for VariableLink {
                         crate::link::node_data::NodeDataEnum::Root() | crate::link::node_data::NodeDataEnum::Isolated()
                     );
 
-                    // @Todo: must return LinkingErrorEnum::FieldNotFound or the other even better linking error
-                    if self.path.len() > 1 && is_unattached_root {
+                    // the path root may appear only after the object is attached, but if the root is already
+                    // in this context, the missing nested field is a real linking error
+                    let root_is_known = context.borrow().field_name_set.contains(self.path[0]);
+
+                    if self.path.len() > 1 && is_unattached_root && !root_is_known {
                         self.variable_type = LinkingError::not_linked().into();
                         return Ok(ValueType::UndefinedType);
                     }
@@ -26,6 +26,18 @@ use std::rc::Rc;
 pub fn link_parts(context: Rc<RefCell<ContextObject>>) -> Link<Rc<RefCell<ContextObject>>> {
     //trace!("link_parts: {}(..)", context.borrow().node().node_type);
 
+    if let Some(import) = context.borrow().imports.first() {
+        return LinkingError::other_error(format!(
+            "Cannot import '{}': imports are resolved only at the top level of a model",
+            import.path
+        ))
+        .into();
+    }
+
+    if !context.borrow().spreads.is_empty() {
+        ContextObject::expand_spreads(&context, &context)?;
+    }
+
     let field_names = context.borrow().get_field_names();
     let mut references = Vec::new();
 
@@ -144,6 +156,9 @@ pub fn find_implementation(
             //     ctx.borrow().node().node_type
             // );
             return Ok(Rc::clone(&definition));
+        } else if (*ctx).borrow().parameters.iter().any(|p| p.name == function_name && p.declared_type().is_none()) {
+            // An untyped parameter is called as a function: it gets bound when the enclosing function is called
+            return LinkingError::not_linked().into();
         } else {
             let maybe_parent = (*ctx).borrow().node().node_type.get_parent();
 
@@ -263,8 +278,18 @@ impl BrowseResultFound<ExecutionContext> {
         match &self.content {
             ConstantValue(value) => Ok(value.clone()),
             ExpressionRef(value) => {
+                // a field evaluated on first read records its own reads as well, not only the reads of its reader
+                let dependencies = self.context.borrow().dependencies.clone();
+                if let Some(dependencies) = &dependencies {
+                    dependencies
+                        .enter_field(build_location_from_execution_context(&self.context, self.field_name).join("."));
+                }
                 // since linking did it's work, no need to lock again
-                let result = match value.borrow().expression.eval(Rc::clone(&self.context)) {
+                let evaluated = value.borrow().expression.eval(Rc::clone(&self.context));
+                if let Some(dependencies) = &dependencies {
+                    dependencies.exit_field();
+                }
+                let result = match evaluated {
                     Ok(v) => Ok(v),
                     Err(mut err) => {
                         if err.location().is_empty() {
@@ -419,7 +444,8 @@ fn continue_browse<'a, T: Node<T>>(
     mut starting: (Rc<RefCell<T>>, EObjectContent<T>),
 ) -> Result<BrowseResult<'a, T>, LinkingError> {
     //trace!("continue_browse(path[{}..], {:?})", index, starting);
-    let mut current_search_end: Option<&str> = path.first().copied();
+    // the name of the item being browsed, unknown when browsing continues from a resolved expression
+    let mut current_search_end: Option<&str> = index.checked_sub(1).and_then(|previous| path.get(previous)).copied();
 
     #[allow(irrefutable_let_patterns)]
     while let (ref context, ref item) = starting {
@@ -100,16 +100,27 @@ pub trait ContentHolder<T: Node<T>> {
 
     fn get_field_names(&self) -> Vec<&'static str>;
 
+    /// Lines printed before the fields, such as `...base` object spreads
+    fn get_leading_lines(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Optional `field?: value` is dropped from the output when its value is omitted
+    fn is_optional_field(&self, _name: &str) -> bool {
+        false
+    }
+
     fn print_object(&self, f: &mut Formatter) -> fmt::Result {
         trace!("print_object: {:?}", self.get_field_names());
 
-        let mut lines: Vec<String> = Vec::new();
+        let mut lines: Vec<String> = self.get_leading_lines();
 
         for field_name in self.get_field_names().iter() {
             match self.get(field_name) {
                 Ok(ExpressionRef(field)) => {
                     let value = bracket_unwrap(format!("{}", field.borrow().expression));
-                    lines.push(format!("{}: {}", field_name, value));
+                    let marker = if self.is_optional_field(field_name) { "?" } else { "" };
+                    lines.push(format!("{}{}: {}", field_name, marker, value));
                 }
                 Ok(UserFunctionRef(definition)) => {
                     lines.push(format!("{}", definition.borrow().function_definition));
@@ -118,6 +129,9 @@ pub trait ContentHolder<T: Node<T>> {
                     lines.push(format!("{}: {}", field_name, obj.borrow()));
                 }
                 Ok(ConstantValue(value)) => {
+                    if value.is_omitted() && self.is_optional_field(field_name) {
+                        continue;
+                    }
                     lines.push(format!("{}: {}", field_name, value));
                 }
                 _ => {}
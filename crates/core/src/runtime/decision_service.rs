@@ -1,21 +1,41 @@
 use crate::ast::context::context_object::ContextObject;
-use crate::ast::expression::cast_value_to_type;
+use crate::ast::context::context_object_builder::ContextObjectBuilder;
+use crate::ast::context::context_object_type::{EObjectContent, FormalParameter};
+use crate::ast::expression::{cast_value_to_type, missing_for_type};
 use crate::ast::functions::function_types::EFunctionType;
 use crate::ast::metaphors::metaphor::UserFunction;
 use crate::ast::token::ExpressionEnum;
 use crate::link::linker::link_parts;
+use crate::link::node_data::ContentHolder;
 use crate::runtime::edge_rules::{ContextQueryErrorEnum, EdgeRulesModel, EdgeRulesRuntime, EvalError, MethodEntry};
-use crate::typesystem::errors::{ParseErrorEnum, RuntimeError};
-use crate::typesystem::types::ValueType;
-use crate::typesystem::values::ValueEnum;
+use crate::runtime::execution_context::{Clock, DependencyGraph, EvaluationObserver, ExecutionContext};
+use crate::typesystem::errors::{LinkingErrorEnum, ParseErrorEnum, RuntimeError};
+use crate::typesystem::types::number::NumberEnum;
+use crate::typesystem::types::string::StringEnum;
+use crate::typesystem::types::{Float, Integer, TypedValue, ValueType};
+use crate::typesystem::values::{ArrayValue, ValueEnum};
 use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
+use std::str::FromStr;
 
 /// Maintains a reusable rules model and linked runtime tree for decision-service style execution.
 pub struct DecisionService {
     model: Rc<RefCell<EdgeRulesModel>>,
     static_context: Rc<RefCell<ContextObject>>,
     runtime_dirty: bool,
+    result_cache: ResultCache,
+    /// Clock for `today()` and `now()` of every runtime, the system clock when not set
+    clock: Option<Rc<dyn Clock>>,
+    /// Evaluated fields kept by `update_input` until the model changes
+    incremental: Option<IncrementalState>,
+}
+
+struct IncrementalState {
+    context: Rc<RefCell<ExecutionContext>>,
+    dependencies: Rc<DependencyGraph>,
+    /// Paths of updated inputs, which are never evaluated again
+    inputs: HashSet<String>,
 }
 
 impl DecisionService {
@@ -26,7 +46,14 @@ impl DecisionService {
         let mut model = EdgeRulesModel::new();
         model.merge_context_object(Rc::clone(&context))?;
 
-        Ok(Self { model: Rc::new(RefCell::new(model)), static_context: context, runtime_dirty: false })
+        Ok(Self {
+            model: Rc::new(RefCell::new(model)),
+            static_context: context,
+            runtime_dirty: false,
+            result_cache: ResultCache::default(),
+            clock: None,
+            incremental: None,
+        })
     }
 
     /// Parses EdgeRules DSL source and links it into a reusable decision service.
@@ -60,28 +87,37 @@ impl DecisionService {
         let runtime_method_name = Self::runtime_method_name(&method_path);
 
         let method_entry = self.resolve_method_entry(&method_path)?;
-        let (parameter_count, param_types) = {
+        let (parameter_count, required_count, variadic, param_types) = {
             let borrowed = method_entry.borrow();
             let params = borrowed.function_definition.get_parameters();
             let count = params.len();
+            let required = params.iter().filter(|p| p.default_value.is_none() && !p.variadic).count();
+            let variadic = params.last().is_some_and(|p| p.variadic);
+            // rest arguments are collected into a list by the call itself, so they are not cast one by one
             let types: Vec<Option<crate::ast::token::ComplexTypeRef>> =
-                params.iter().map(|p| p.declared_type().cloned()).collect();
-            (count, types)
+                params.iter().map(|p| if p.variadic { None } else { p.declared_type().cloned() }).collect();
+            (count, required, variadic, types)
         };
 
-        if parameter_count != args.len() {
+        if args.len() < required_count || (!variadic && args.len() > parameter_count) {
             return Err(EvalError::from(RuntimeError::from(ParseErrorEnum::FunctionWrongNumberOfArguments(
                 runtime_method_name.to_string(),
                 EFunctionType::Custom(parameter_count as u8),
                 args.len(),
+                None,
             ))));
         }
 
         let runtime = self.ensure_runtime()?;
 
+        let cache_key = self.result_cache.is_enabled().then(|| ResultKey::new(&method_path, &args)).flatten();
+        if let Some(cached) = cache_key.as_ref().and_then(|key| self.result_cache.get(key)) {
+            return Ok(cached);
+        }
+
         let mut final_args = Vec::with_capacity(args.len());
         for (i, arg) in args.into_iter().enumerate() {
-            let casted_arg = if let Some(tref) = &param_types[i] {
+            let casted_arg = if let Some(Some(tref)) = param_types.get(i) {
                 let expected_type =
                     runtime.context.borrow().object.borrow().resolve_type_ref(tref).unwrap_or(ValueType::UndefinedType);
                 cast_value_to_type(arg, expected_type, Rc::clone(&runtime.context), Some("Argument"))
@@ -92,7 +128,188 @@ impl DecisionService {
             final_args.push(ExpressionEnum::from(casted_arg));
         }
 
-        runtime.call_method(runtime_method_name, final_args).map_err(EvalError::from)
+        let result = runtime.call_method(runtime_method_name, final_args).map_err(EvalError::from)?;
+        // responses that read the clock would be replayed with a stale `today()` or `now()`
+        if let Some(key) = cache_key.filter(|_| !runtime.context.borrow().clock.was_read()) {
+            self.result_cache.put(key, &result);
+        }
+        Ok(result)
+    }
+
+    /// Caches up to `size` method responses keyed by the method and its request, so identical requests are answered
+    /// without evaluation. The least recently used response is dropped first. Size `0` disables the cache.
+    /// Only successful responses are cached, and the cache is cleared whenever the model changes.
+    pub fn set_cache_size(&mut self, size: usize) {
+        self.result_cache.resize(size);
+    }
+
+    /// Drops all cached method responses.
+    pub fn clear_cache(&mut self) {
+        self.result_cache.clear();
+    }
+
+    /// Number of method calls answered from the cache so far.
+    pub fn cache_hits(&self) -> u64 {
+        self.result_cache.hits
+    }
+
+    /// Replaces the clock read by `today()` and `now()`, e.g. with a fixed instant in tests.
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.clock = Some(clock);
+        self.incremental = None;
+    }
+
+    /// Builds a request template for a single-argument method, with every field set to `Missing`
+    /// or to the default value declared in the argument type.
+    pub fn sample_request(&mut self, service_method: &str) -> Result<ValueEnum, EvalError> {
+        let (parameter, request_type, runtime) = self.request_type(service_method, "Sample request")?;
+        let origin = match request_type {
+            ValueType::ObjectType(_) => None,
+            _ => Some(parameter.name.as_str()),
+        };
+
+        missing_for_type(&request_type, origin, &runtime.context).map_err(EvalError::from)
+    }
+
+    /// Converts loosely typed request values of a single-argument method to the types declared in the argument
+    /// type, for example `'30'` to `30` for a `<number>` field, and reports every converted field.
+    /// Strings are converted to numbers, booleans and temporal values, numbers and booleans to strings.
+    /// Values that cannot be converted are kept as they are, so `execute_method` reports them as usual.
+    pub fn coerce_request(&mut self, service_method: &str, request: ValueEnum) -> Result<CoercedRequest, EvalError> {
+        let (parameter, request_type, runtime) = self.request_type(service_method, "Request coercion")?;
+        let path = match request_type {
+            ValueType::ObjectType(_) => String::new(),
+            _ => parameter.name.clone(),
+        };
+
+        let mut coercions = Vec::new();
+        let request = coerce_value(request, &request_type, &path, &runtime.context, &mut coercions)?;
+        Ok(CoercedRequest { request, coercions })
+    }
+
+    fn request_type(
+        &mut self,
+        service_method: &str,
+        purpose: &str,
+    ) -> Result<(FormalParameter, ValueType, EdgeRulesRuntime), EvalError> {
+        let method_path = Self::clean_method_name(service_method)?;
+        let method_entry = self.resolve_method_entry(&method_path)?;
+        let parameter = {
+            let borrowed = method_entry.borrow();
+            match borrowed.function_definition.get_parameters().as_slice() {
+                [parameter] => parameter.clone(),
+                parameters => {
+                    return Err(Self::config_error(format!(
+                        "{} requires a single argument method, but `{}` has {} arguments",
+                        purpose,
+                        method_path,
+                        parameters.len()
+                    )));
+                }
+            }
+        };
+
+        let runtime = self.ensure_runtime()?;
+        let request_type = match parameter.declared_type() {
+            Some(tref) => runtime.context.borrow().object.borrow().resolve_type_ref(tref).map_err(EvalError::from)?,
+            None => ValueType::UndefinedType,
+        };
+
+        Ok((parameter, request_type, runtime))
+    }
+
+    /// Sets the input field at `path` to `value` and evaluates again only the fields that read it, directly or through
+    /// other fields. The first call evaluates all fields, later calls reuse the values of unaffected fields.
+    /// Returns the evaluated fields whose value changed, keyed by path. Updated inputs keep their value until the
+    /// model changes.
+    pub fn update_input(&mut self, path: &str, value: ValueEnum) -> Result<BTreeMap<String, ValueEnum>, EvalError> {
+        self.update_input_with(path, value, None)
+    }
+
+    /// Same as `update_input`, reporting each evaluated field to the observer
+    pub fn update_input_observed(
+        &mut self,
+        path: &str,
+        value: ValueEnum,
+        observer: &mut dyn EvaluationObserver,
+    ) -> Result<BTreeMap<String, ValueEnum>, EvalError> {
+        self.update_input_with(path, value, Some(observer))
+    }
+
+    fn update_input_with(
+        &mut self,
+        path: &str,
+        value: ValueEnum,
+        mut observer: Option<&mut dyn EvaluationObserver>,
+    ) -> Result<BTreeMap<String, ValueEnum>, EvalError> {
+        let runtime = self.ensure_runtime()?;
+        let state = match &mut self.incremental {
+            Some(state) => state,
+            incremental => {
+                let dependencies = DependencyGraph::new();
+                runtime.context.borrow_mut().dependencies = Some(Rc::clone(&dependencies));
+                runtime.eval_all()?;
+                incremental.insert(IncrementalState { context: runtime.context, dependencies, inputs: HashSet::new() })
+            }
+        };
+
+        let path = path.trim();
+        let (ctx, name) = resolve_field(&state.context, path)
+            .ok_or_else(|| EvalError::from(ContextQueryErrorEnum::EntryNotFoundError(path.to_string())))?;
+        let value = match declared_field_type(&ctx.borrow().object, name) {
+            Some(field_type) => input_of_type(value, &field_type, path, &ctx)?,
+            None => value,
+        };
+        let unchanged = field_value(&ctx, name).as_ref() == Some(&value);
+        ctx.borrow().stack_insert(name, Ok(value));
+        state.inputs.insert(path.to_string());
+        if unchanged {
+            return Ok(BTreeMap::new());
+        }
+
+        // all dependents are cleared first, so a dependent read by another one is never taken from the stack
+        let mut dependents = Vec::new();
+        for field in state.dependencies.dependents(path) {
+            if state.inputs.contains(&field) {
+                continue;
+            }
+            if let Some((ctx, name)) = resolve_field(&state.context, &field) {
+                let expression = match ctx.borrow().object.borrow().get(name) {
+                    Ok(EObjectContent::ExpressionRef(expression)) => expression,
+                    _ => continue,
+                };
+                let previous = ctx.borrow().stack_remove(name).and_then(Result::ok);
+                dependents.push((field, ctx, name, expression, previous));
+            }
+        }
+
+        // every dependent is evaluated before an error is reported, so no field is left without a value
+        let mut changed = BTreeMap::new();
+        let mut first_error = None;
+        for (field, ctx, name, expression, previous) in dependents {
+            // a dependent read by an earlier one is already evaluated
+            if ctx.borrow().stack_get(name).is_none() {
+                if let Err(err) = ExecutionContext::eval_field_observed(&ctx, name, expression, observer.as_deref_mut())
+                {
+                    first_error.get_or_insert(err);
+                    continue;
+                }
+            }
+            match ctx.borrow().stack_get(name) {
+                Some(Ok(value)) if previous.as_ref() != Some(&value) => {
+                    changed.insert(field, value);
+                }
+                Some(Err(err)) => {
+                    first_error.get_or_insert(err);
+                }
+                _ => {}
+            }
+        }
+
+        match first_error {
+            Some(err) => Err(EvalError::from(err)),
+            None => Ok(changed),
+        }
     }
 
     /// Evaluates a field by path in the decision service.
@@ -102,6 +319,14 @@ impl DecisionService {
         runtime.evaluate_field(path).map_err(EvalError::from)
     }
 
+    /// Prepares the service for low-latency requests: relinks a modified model and links every function body,
+    /// including functions nested in objects and in other functions, so the first `execute` only evaluates.
+    /// Constant expressions are not folded, they are still evaluated by every request.
+    pub fn warm_up(&mut self) -> Result<(), EvalError> {
+        self.ensure_runtime()?;
+        Self::link_function_bodies(&self.static_context)
+    }
+
     #[cfg(feature = "mutable_decision_service")]
     pub fn get_model(&mut self) -> Rc<RefCell<EdgeRulesModel>> {
         self.runtime_dirty = true;
@@ -118,7 +343,13 @@ impl DecisionService {
             for entry in ctx.metaphors.values() {
                 let borrowed_entry = entry.borrow();
                 if let Ok(body) = borrowed_entry.function_definition.get_body() {
-                    link_parts(Rc::clone(&body)).map_err(EvalError::from)?;
+                    match link_parts(Rc::clone(&body)) {
+                        // functions taking function arguments are linked at their call sites
+                        Err(err) if err.kind() == &LinkingErrorEnum::NotLinkedYet => {}
+                        other => {
+                            other.map_err(EvalError::from)?;
+                        }
+                    }
                 }
             }
         }
@@ -127,18 +358,59 @@ impl DecisionService {
             model: Rc::new(RefCell::new(model)),
             static_context: Rc::clone(&runtime.static_tree),
             runtime_dirty: false,
+            result_cache: ResultCache::default(),
+            clock: None,
+            incremental: None,
         })
     }
 
+    fn link_function_bodies(context: &Rc<RefCell<ContextObject>>) -> Result<(), EvalError> {
+        let (bodies, childs) = {
+            let borrowed = context.borrow();
+            let bodies: Vec<Rc<RefCell<ContextObject>>> = borrowed
+                .metaphors
+                .values()
+                .filter_map(|entry| entry.borrow().function_definition.get_body().ok())
+                .collect();
+            let childs: Vec<Rc<RefCell<ContextObject>>> =
+                borrowed.node.get_childs().borrow().values().cloned().collect();
+            (bodies, childs)
+        };
+
+        for body in bodies {
+            match link_parts(Rc::clone(&body)) {
+                // functions taking function arguments are linked at their call sites
+                Err(err) if err.kind() == &LinkingErrorEnum::NotLinkedYet => continue,
+                other => {
+                    other.map_err(EvalError::from)?;
+                }
+            }
+            Self::link_function_bodies(&body)?;
+        }
+
+        for child in childs {
+            Self::link_function_bodies(&child)?;
+        }
+
+        Ok(())
+    }
+
     fn ensure_runtime(&mut self) -> Result<EdgeRulesRuntime, EvalError> {
-        if self.runtime_dirty {
+        let mut runtime = if self.runtime_dirty {
             let runtime = self.model.borrow_mut().to_runtime_snapshot()?;
             self.static_context = Rc::clone(&runtime.static_tree);
             self.runtime_dirty = false;
-            return Ok(runtime);
-        }
+            self.result_cache.clear();
+            self.incremental = None;
+            runtime
+        } else {
+            EdgeRulesRuntime::new(Rc::clone(&self.static_context))
+        };
 
-        Ok(EdgeRulesRuntime::new(Rc::clone(&self.static_context)))
+        if let Some(clock) = &self.clock {
+            runtime.set_clock(Rc::clone(clock));
+        }
+        Ok(runtime)
     }
 
     pub fn get_linked_type(&mut self, path: &str) -> Result<ValueType, ContextQueryErrorEnum> {
@@ -176,3 +448,290 @@ impl DecisionService {
         EvalError::from(RuntimeError::eval_error(message.into()))
     }
 }
+
+/// Execution context and name of the field at a dotted path, if the field exists
+fn resolve_field(
+    root: &Rc<RefCell<ExecutionContext>>,
+    path: &str,
+) -> Option<(Rc<RefCell<ExecutionContext>>, &'static str)> {
+    let segments: Vec<&str> = path.split('.').map(str::trim).collect();
+    let (leaf, parents) = segments.split_last()?;
+    let mut ctx = Rc::clone(root);
+    for segment in parents {
+        let child = match ctx.borrow().get(segment) {
+            Ok(EObjectContent::ObjectRef(child)) => child,
+            _ => return None,
+        };
+        ctx = child;
+    }
+    let name = ctx.borrow().get_field_names().into_iter().find(|name| name == leaf)?;
+    Some((ctx, name))
+}
+
+/// Input value converted to the linked field type like `coerce_request` does, an error when it has another type
+fn input_of_type(
+    value: ValueEnum,
+    field_type: &ValueType,
+    path: &str,
+    ctx: &Rc<RefCell<ExecutionContext>>,
+) -> Result<ValueEnum, RuntimeError> {
+    let value = coerce_value(value, field_type, path, ctx, &mut Vec::new())?;
+    let matches = match (field_type, &value) {
+        (ValueType::UndefinedType | ValueType::RangeType, _) => true,
+        (ValueType::ObjectType(_), ValueEnum::Reference(_)) | (ValueType::ListType(_), ValueEnum::Array(_)) => true,
+        (ValueType::ObjectType(_) | ValueType::ListType(_), _) => false,
+        (field_type, value) => value.get_type() == *field_type,
+    };
+    if matches {
+        return Ok(value);
+    }
+
+    let mut err = RuntimeError::parsing(value.get_type(), field_type.clone());
+    *err.location_mut() = path.split('.').map(str::to_string).collect();
+    Err(err)
+}
+
+fn field_value(ctx: &Rc<RefCell<ExecutionContext>>, name: &str) -> Option<ValueEnum> {
+    match ctx.borrow().get(name) {
+        Ok(EObjectContent::ConstantValue(value)) => Some(value),
+        Ok(EObjectContent::ObjectRef(child)) => Some(ValueEnum::Reference(child)),
+        _ => None,
+    }
+}
+
+/// Request value converted to the declared type by `DecisionService::coerce_request`.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[derive(Clone, PartialEq)]
+pub struct Coercion {
+    /// Field path in the request, such as `applicant.age` or `items[0].quantity`
+    pub path: String,
+    pub from: ValueType,
+    pub to: ValueType,
+}
+
+/// Coerced request together with the log of converted fields.
+pub struct CoercedRequest {
+    pub request: ValueEnum,
+    pub coercions: Vec<Coercion>,
+}
+
+fn coerce_value(
+    value: ValueEnum,
+    target: &ValueType,
+    path: &str,
+    ctx: &Rc<RefCell<ExecutionContext>>,
+    coercions: &mut Vec<Coercion>,
+) -> Result<ValueEnum, RuntimeError> {
+    match (target, value) {
+        (ValueType::ObjectType(schema), ValueEnum::Reference(object)) => {
+            coerce_object(&object, schema, path, ctx, coercions)
+        }
+        (ValueType::ListType(Some(item_type)), ValueEnum::Array(array)) => {
+            let values: Vec<ValueEnum> = match array {
+                ArrayValue::EmptyUntyped => return Ok(ValueEnum::Array(ArrayValue::EmptyUntyped)),
+                ArrayValue::PrimitivesArray { values, .. } => values,
+                ArrayValue::ObjectsArray { values, .. } => values.into_iter().map(ValueEnum::Reference).collect(),
+            };
+            let mut coerced = Vec::with_capacity(values.len());
+            for (index, item) in values.into_iter().enumerate() {
+                let item_path = format!("{}[{}]", path, index);
+                coerced.push(coerce_value(item, item_type, &item_path, ctx, coercions)?);
+            }
+            Ok(ValueEnum::Array(ArrayValue::from_values(coerced)?))
+        }
+        (target, value) => {
+            let Some(converted) = convert_scalar(&value, target, ctx) else {
+                return Ok(value);
+            };
+            coercions.push(Coercion { path: path.to_string(), from: value.get_type(), to: converted.get_type() });
+            Ok(converted)
+        }
+    }
+}
+
+fn coerce_object(
+    object: &Rc<RefCell<ExecutionContext>>,
+    schema: &Rc<RefCell<ContextObject>>,
+    path: &str,
+    ctx: &Rc<RefCell<ExecutionContext>>,
+    coercions: &mut Vec<Coercion>,
+) -> Result<ValueEnum, RuntimeError> {
+    let mut builder = ContextObjectBuilder::new();
+    for name in object.borrow().get_field_names() {
+        let value = match object.borrow().get(name) {
+            Ok(EObjectContent::ObjectRef(child)) => ValueEnum::Reference(child),
+            Ok(EObjectContent::ConstantValue(value)) => value,
+            Ok(EObjectContent::ExpressionRef(entry)) => entry.borrow().expression.eval(Rc::clone(object))?,
+            _ => continue,
+        };
+
+        let field_path = if path.is_empty() { name.to_string() } else { format!("{}.{}", path, name) };
+        let value = match declared_field_type(schema, name) {
+            Some(field_type) => coerce_value(value, &field_type, &field_path, ctx, coercions)?,
+            None => value,
+        };
+        builder
+            .add_expression(name, ExpressionEnum::from(value))
+            .map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+    }
+
+    let static_context = builder.build();
+    link_parts(Rc::clone(&static_context)).map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+    let coerced = ExecutionContext::create_isolated_context(static_context);
+    ExecutionContext::eval_all_fields(&coerced)?;
+    Ok(ValueEnum::Reference(coerced))
+}
+
+fn declared_field_type(schema: &Rc<RefCell<ContextObject>>, name: &str) -> Option<ValueType> {
+    match schema.borrow().get(name).ok()? {
+        EObjectContent::ExpressionRef(entry) => match &entry.borrow().expression {
+            ExpressionEnum::TypePlaceholder(tref) => schema.borrow().resolve_type_ref(tref).ok(),
+            _ => entry.borrow().field_type.clone().ok(),
+        },
+        EObjectContent::ObjectRef(object) => Some(ValueType::ObjectType(object)),
+        _ => None,
+    }
+}
+
+/// Converted scalar, `None` when the value already has the target type or cannot be converted
+fn convert_scalar(value: &ValueEnum, target: &ValueType, ctx: &Rc<RefCell<ExecutionContext>>) -> Option<ValueEnum> {
+    match (target, value) {
+        (ValueType::NumberType, ValueEnum::StringValue(StringEnum::String(text))) => {
+            let text = text.trim();
+            match text.parse::<Integer>() {
+                Ok(integer) => Some(ValueEnum::NumberValue(NumberEnum::Int(integer))),
+                Err(_) => Float::from_str(text).ok().map(|real| ValueEnum::NumberValue(NumberEnum::Real(real))),
+            }
+        }
+        (ValueType::BooleanType, ValueEnum::StringValue(StringEnum::String(text))) => {
+            match text.trim().to_lowercase().as_str() {
+                "true" => Some(ValueEnum::BooleanValue(true)),
+                "false" => Some(ValueEnum::BooleanValue(false)),
+                _ => None,
+            }
+        }
+        (ValueType::StringType, ValueEnum::NumberValue(NumberEnum::Int(_) | NumberEnum::Real(_)))
+        | (ValueType::StringType, ValueEnum::BooleanValue(_)) => {
+            Some(ValueEnum::StringValue(StringEnum::from(value.to_string())))
+        }
+        (
+            ValueType::DateType
+            | ValueType::TimeType
+            | ValueType::DateTimeType
+            | ValueType::DurationType
+            | ValueType::PeriodType,
+            ValueEnum::StringValue(StringEnum::String(_)),
+        ) => cast_value_to_type(value.clone(), target.clone(), Rc::clone(ctx), None).ok(),
+        _ => None,
+    }
+}
+
+/// A method call compared by value, so `'1'` and `1` are different requests
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ResultKey {
+    method_path: String,
+    args: Vec<KeyValue>,
+}
+
+/// Structural copy of a request value: objects are compared by their field values, not by their evaluation state
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum KeyValue {
+    Primitive(ValueEnum),
+    Object(Vec<(&'static str, KeyValue)>),
+    Objects(Vec<KeyValue>),
+}
+
+impl ResultKey {
+    /// `None` when a request object cannot be fully evaluated, such requests are not cached
+    fn new(method_path: &str, args: &[ValueEnum]) -> Option<Self> {
+        let args = args.iter().map(KeyValue::from_value).collect::<Option<Vec<_>>>()?;
+        Some(ResultKey { method_path: method_path.to_string(), args })
+    }
+}
+
+impl KeyValue {
+    fn from_value(value: &ValueEnum) -> Option<Self> {
+        match value {
+            ValueEnum::Reference(ctx) => Self::from_object(ctx),
+            ValueEnum::Array(ArrayValue::ObjectsArray { values, .. }) => {
+                values.iter().map(Self::from_object).collect::<Option<Vec<_>>>().map(KeyValue::Objects)
+            }
+            primitive => Some(KeyValue::Primitive(primitive.clone())),
+        }
+    }
+
+    fn from_object(ctx: &Rc<RefCell<ExecutionContext>>) -> Option<Self> {
+        ExecutionContext::eval_all_fields(ctx).ok()?;
+        let field_names = ctx.borrow().object.borrow().get_field_names();
+        let mut fields = Vec::with_capacity(field_names.len());
+        for field_name in field_names {
+            let content = ctx.borrow().get(field_name).ok()?;
+            let field = match content {
+                EObjectContent::ConstantValue(value) => Self::from_value(&value)?,
+                EObjectContent::ObjectRef(child) => Self::from_object(&child)?,
+                EObjectContent::UserFunctionRef(_) | EObjectContent::Definition(_) => continue,
+                EObjectContent::ExpressionRef(_) => return None,
+            };
+            fields.push((field_name, field));
+        }
+        Some(KeyValue::Object(fields))
+    }
+}
+
+/// Bounded least recently used cache of method responses.
+/// Responses are stored and handed out as deep copies, so callers never share evaluation state through the cache.
+#[derive(Default)]
+struct ResultCache {
+    capacity: usize,
+    tick: u64,
+    hits: u64,
+    entries: HashMap<ResultKey, (u64, ValueEnum)>,
+    recency: BTreeMap<u64, ResultKey>,
+}
+
+impl ResultCache {
+    fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    fn get(&mut self, key: &ResultKey) -> Option<ValueEnum> {
+        let tick = self.next_tick();
+        let (used, value) = self.entries.get_mut(key)?;
+        self.recency.remove(used);
+        self.recency.insert(tick, key.clone());
+        *used = tick;
+        self.hits += 1;
+        Some(value.deep_clone())
+    }
+
+    fn put(&mut self, key: ResultKey, value: &ValueEnum) {
+        let tick = self.next_tick();
+        if let Some((used, _)) = self.entries.insert(key.clone(), (tick, value.deep_clone())) {
+            self.recency.remove(&used);
+        }
+        self.recency.insert(tick, key);
+        self.evict();
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some((_, key)) = self.recency.pop_first() else { break };
+            self.entries.remove(&key);
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
@@ -9,20 +9,22 @@ use crate::ast::token::ExpressionEnum::ObjectField;
 use crate::ast::user_function_call::UserFunctionCall;
 use crate::ast::utils::array_to_code_sep;
 use crate::link::node_data::ContentHolder;
-use crate::runtime::execution_context::ExecutionContext;
+pub use crate::runtime::execution_context::EvaluationObserver;
+use crate::runtime::execution_context::{Clock, EvaluationClock, ExecutionContext, RealTolerance};
 use crate::tokenizer::parser::tokenize;
 use crate::typesystem::errors::ParseErrorEnum::{OtherError, UnexpectedEnd, UnexpectedToken, WrongFormat};
 use crate::typesystem::errors::{LinkingError, ParseErrorEnum, RuntimeError};
-use crate::typesystem::types::{TypedValue, ValueType};
+use crate::typesystem::types::{Float, SpecialValueEnum, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 
 pub use crate::ast::context::context_object::{ExpressionEntry, MethodEntry};
-pub use crate::ast::context::context_object_builder::ContextObjectBuilder;
+pub use crate::ast::context::context_object_builder::{ContextObjectBuilder, MergePolicy};
 pub use crate::ast::metaphors::functions::{FunctionDefinition, UserFunctionDefinition};
-pub use crate::ast::token::{DefinitionEnum, ExpressionEnum, UserTypeBody};
+pub use crate::ast::token::{DefinitionEnum, ExpressionEnum, ImportDefinition, UserTypeBody};
 pub use crate::link::linker::link_parts;
 //--------------------------------------------------------------------------------------------------
 // Errors
@@ -38,9 +40,34 @@ pub enum EvalError {
     FailedExecution(RuntimeError),
 }
 
+/// Outcome of `EdgeRulesRuntime::eval_all_with_diagnostics`, giving observability into partial results
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 #[derive(PartialEq, Clone)]
-pub struct EvalResult(Rc<RefCell<ExecutionContext>>, ValueEnum);
+pub struct EvalResult {
+    /// Reference to the evaluated root context
+    pub value: ValueEnum,
+    /// Non-fatal notes about the evaluation, e.g. that reals were compared with a tolerance
+    pub warnings: Vec<String>,
+    /// Dot-separated paths of the fields that evaluated to `Missing`, in declaration order
+    pub missing_fields: Vec<String>,
+}
+
+/// Appends the dot-separated paths of the evaluated fields that hold `Missing`
+fn collect_missing_fields(ctx: &Rc<RefCell<ExecutionContext>>, prefix: &str, missing_fields: &mut Vec<String>) {
+    let field_names = ctx.borrow().object.borrow().get_field_names();
+    for name in field_names {
+        let path = if prefix.is_empty() { name.to_string() } else { format!("{}.{}", prefix, name) };
+        match ctx.borrow().get(name) {
+            Ok(EObjectContent::ConstantValue(value)) => {
+                if matches!(value.special_value(), Some(SpecialValueEnum::Missing(_))) {
+                    missing_fields.push(path);
+                }
+            }
+            Ok(EObjectContent::ObjectRef(child)) => collect_missing_fields(&child, &path, missing_fields),
+            _ => {}
+        }
+    }
+}
 
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 pub enum ParsedItem {
@@ -68,6 +95,9 @@ pub enum ContextQueryErrorEnum {
 
     // returns wrong path or empty if path is empty
     WrongFieldPathError(Option<String>),
+
+    // model was frozen and cannot be modified anymore
+    ModelFrozenError,
 }
 
 impl From<DuplicateNameError> for ContextQueryErrorEnum {
@@ -96,6 +126,7 @@ impl Display for ContextQueryErrorEnum {
                 Some(path) => write!(f, "Invalid path '{}'", path),
                 None => write!(f, "Field path is empty"),
             },
+            ContextQueryErrorEnum::ModelFrozenError => write!(f, "Model is frozen and cannot be modified"),
         }
     }
 }
@@ -232,9 +263,17 @@ impl Display for EvalError {
 // Service
 //--------------------------------------------------------------------------------------------------
 
+/// Loads the source of `import "path"`. The core crate does no I/O, callers decide where sources come from.
+pub type ImportResolver = dyn Fn(&str) -> Result<String, String>;
+
 /// Service is stateless
 pub struct EdgeRulesModel {
     pub ast_root: ContextObjectBuilder,
+    import_resolver: Option<Rc<ImportResolver>>,
+    imported: HashSet<String>,
+    /// paths of namespaced imports being loaded, to detect cycles
+    importing: Vec<String>,
+    frozen: Option<FrozenModel>,
 }
 
 impl Default for EdgeRulesModel {
@@ -249,7 +288,59 @@ type ParentResolution<'a> = (Option<Rc<RefCell<ContextObject>>>, &'a str);
 /// Model is reused across multiple executions.
 impl EdgeRulesModel {
     pub fn new() -> Self {
-        Self { ast_root: ContextObjectBuilder::new() }
+        Self {
+            ast_root: ContextObjectBuilder::new(),
+            import_resolver: None,
+            imported: HashSet::new(),
+            importing: Vec::new(),
+            frozen: None,
+        }
+    }
+
+    /// Sets the loader of `import "path"` sources. Without it, any import fails.
+    pub fn set_import_resolver(&mut self, resolver: impl Fn(&str) -> Result<String, String> + 'static) {
+        self.import_resolver = Some(Rc::new(resolver));
+    }
+
+    /// Appends the imported source to the model, or places it under the alias object for `import "path" as alias`.
+    /// Every path is imported once without an alias, repeated imports are skipped.
+    fn import_source(&mut self, import: ImportDefinition) -> Result<(), ParseErrors> {
+        let ImportDefinition { path, alias } = import;
+        let import_error =
+            |message: String| ParseErrors(vec![OtherError(format!("Cannot import '{}': {}", path, message))]);
+
+        if alias.is_none() && self.imported.contains(&path) {
+            return Ok(());
+        }
+
+        if self.importing.contains(&path) {
+            return Err(import_error("cyclic import".to_string()));
+        }
+
+        let Some(resolver) = self.import_resolver.clone() else {
+            return Err(import_error("no import resolver is set".to_string()));
+        };
+
+        let code = resolver(&path).map_err(import_error)?;
+        let in_imported = |mut errors: ParseErrors| {
+            errors.0.push(OtherError(format!("In imported '{}'", path)));
+            errors
+        };
+
+        let Some(alias) = alias else {
+            self.imported.insert(path.clone());
+            return self.append_source(&code).map_err(in_imported);
+        };
+
+        let mut module = EdgeRulesModel::new();
+        module.import_resolver = Some(resolver);
+        module.importing = self.importing.iter().cloned().chain([path.clone()]).collect();
+        module.append_source(&code).map_err(in_imported)?;
+
+        self.ast_root
+            .add_expression(alias.as_str(), ExpressionEnum::StaticObject(module.ast_root.build()))
+            .map(|_| ())
+            .map_err(Self::context_update_error)
     }
 
     fn parse_item(code: &str) -> Result<ParsedItem, ParseErrors> {
@@ -338,6 +429,7 @@ impl EdgeRulesModel {
     }
 
     pub fn set_invocation(&mut self, field_path: &str, spec: InvocationSpec) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         // @Todo: why FieldPath::parse(field_path)?; is not used instead of validate_invocation_method?
         let method_path = Self::validate_invocation_method(&spec.method_path)?;
         let expression = ExpressionEnum::from(UserFunctionCall::new(method_path, spec.arguments));
@@ -357,6 +449,7 @@ impl EdgeRulesModel {
         field_path: &str,
         expression: ExpressionEnum,
     ) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let (parent, field_name) = self.resolve_parent(field_path)?;
         match parent {
             None => self.ast_root.set_expression(field_name, expression).map_err(ContextQueryErrorEnum::from),
@@ -371,6 +464,7 @@ impl EdgeRulesModel {
     }
 
     pub fn remove_expression(&mut self, field_path: &str) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let (parent, field_name) = self.resolve_parent(field_path)?;
         match parent {
             None => {
@@ -407,6 +501,7 @@ impl EdgeRulesModel {
         type_path: &str,
         type_definition: UserTypeBody,
     ) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let (parent, type_name) = self.resolve_parent(type_path)?;
         match parent {
             None => {
@@ -422,6 +517,7 @@ impl EdgeRulesModel {
     }
 
     pub fn remove_user_type(&mut self, type_path: &str) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let (parent, type_name) = self.resolve_parent(type_path)?;
         match parent {
             None => {
@@ -451,6 +547,7 @@ impl EdgeRulesModel {
         definition: UserFunctionDefinition,
         context_path: Option<Vec<&str>>,
     ) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let name = definition.get_name();
         if let Some(path) = context_path {
             if path.is_empty() {
@@ -470,6 +567,7 @@ impl EdgeRulesModel {
     }
 
     pub fn remove_user_function(&mut self, function_path: &str) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let (parent, function_name) = self.resolve_parent(function_path)?;
         match parent {
             None => {
@@ -495,6 +593,7 @@ impl EdgeRulesModel {
     }
 
     pub fn rename_entry(&mut self, old_path: &str, new_path: &str) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
         let old_path_parsed = FieldPath::parse(old_path)?;
         let old_name = old_path_parsed.leaf();
 
@@ -532,8 +631,9 @@ impl EdgeRulesModel {
         }
     }
 
-    pub fn merge_context_object(&mut self, object: Rc<RefCell<ContextObject>>) -> Result<(), DuplicateNameError> {
-        self.ast_root.merge_context_object(object)
+    pub fn merge_context_object(&mut self, object: Rc<RefCell<ContextObject>>) -> Result<(), ContextQueryErrorEnum> {
+        self.ensure_mutable()?;
+        self.ast_root.merge_context_object(object).map_err(ContextQueryErrorEnum::from)
     }
 
     fn resolve_context_or_error(
@@ -569,6 +669,7 @@ impl EdgeRulesModel {
     }
 
     pub fn append_source(&mut self, code: &str) -> Result<(), ParseErrors> {
+        self.ensure_mutable().map_err(Self::context_update_error)?;
         let parsed = Self::parse_item(code)?;
 
         match parsed {
@@ -576,6 +677,10 @@ impl EdgeRulesModel {
                 self.set_expression(field.as_str(), *field_expression).map_err(Self::context_update_error)?;
             }
             ParsedItem::Expression(ExpressionEnum::StaticObject(context_object)) => {
+                let imports = std::mem::take(&mut context_object.borrow_mut().imports);
+                for path in imports {
+                    self.import_source(path)?;
+                }
                 self.merge_context_object(context_object).map_err(Self::context_update_error)?;
             }
             ParsedItem::Definition(definition) => match definition {
@@ -588,6 +693,7 @@ impl EdgeRulesModel {
                 DefinitionEnum::UserType(user_type) => {
                     self.set_user_type(user_type.name.as_str(), user_type.body).map_err(Self::context_update_error)?
                 }
+                DefinitionEnum::Import(import) => self.import_source(import)?,
             },
             ParsedItem::Expression(unexpected) => {
                 return Err(ParseErrors::unexpected_token(
@@ -600,6 +706,22 @@ impl EdgeRulesModel {
         Ok(())
     }
 
+    /// Appends all fragments, continuing after failures, and reports indexes of fragments that failed.
+    /// Fragments that loaded successfully stay in the model.
+    pub fn append_sources(&mut self, fragments: &[&str]) -> Result<(), Vec<(usize, ParseErrors)>> {
+        let failures: Vec<(usize, ParseErrors)> = fragments
+            .iter()
+            .enumerate()
+            .filter_map(|(index, code)| self.append_source(code).err().map(|errors| (index, errors)))
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
     pub fn load_source(&mut self, code: &str) -> Result<(), ParseErrors> {
         self.append_source(code)
     }
@@ -611,13 +733,21 @@ impl EdgeRulesModel {
     /// Converts the model into a runtime instance.
     /// No further code modifications allowed after this call
     pub fn to_runtime(self) -> Result<EdgeRulesRuntime, LinkingError> {
+        if let Some(frozen) = &self.frozen {
+            return Ok(frozen.to_runtime());
+        }
+
         let static_context = self.ast_root.build();
         Ok(EdgeRulesRuntime::new(link_parts(static_context)?))
     }
 
     /// Gets a runtime snapshot of the current model state.
-    /// Model can be further modified after this call
+    /// Model can be further modified after this call, unless it is frozen
     pub fn to_runtime_snapshot(&mut self) -> Result<EdgeRulesRuntime, LinkingError> {
+        if let Some(frozen) = &self.frozen {
+            return Ok(frozen.to_runtime());
+        }
+
         let current_builder = std::mem::take(&mut self.ast_root);
         let static_context = current_builder.build();
         let linked_context = link_parts(static_context)?;
@@ -627,6 +757,42 @@ impl EdgeRulesModel {
         self.ast_root.append(linked_context).map_err(|err| LinkingError::other_error(err.to_string()))?;
         Ok(result)
     }
+
+    /// Links the model once and returns a handle that spawns runtimes without linking again.
+    /// Any further modification of the model fails with `ModelFrozenError`. Freezing again returns the same handle.
+    pub fn freeze(&mut self) -> Result<FrozenModel, LinkingError> {
+        if let Some(frozen) = &self.frozen {
+            return Ok(frozen.clone());
+        }
+
+        let runtime = self.to_runtime_snapshot()?;
+        let frozen = FrozenModel { static_tree: runtime.static_tree };
+        self.frozen = Some(frozen.clone());
+        Ok(frozen)
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.is_some()
+    }
+
+    fn ensure_mutable(&self) -> Result<(), ContextQueryErrorEnum> {
+        match self.frozen {
+            Some(_) => Err(ContextQueryErrorEnum::ModelFrozenError),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Linked, immutable model. Cloning is cheap and every runtime spawned from it has its own evaluation state.
+#[derive(Clone)]
+pub struct FrozenModel {
+    static_tree: Rc<RefCell<ContextObject>>,
+}
+
+impl FrozenModel {
+    pub fn to_runtime(&self) -> EdgeRulesRuntime {
+        EdgeRulesRuntime::new(Rc::clone(&self.static_tree))
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -659,6 +825,18 @@ impl EdgeRulesRuntime {
         model.to_runtime().map_err(EvalError::from)
     }
 
+    /// Compares reals as equal when they differ by at most `tolerance`, `None` restores exact comparison.
+    /// Set it before evaluation, because already evaluated child contexts keep their tolerance.
+    pub fn set_real_tolerance(&mut self, tolerance: Option<Float>) {
+        self.context.borrow_mut().real_tolerance = tolerance.map(RealTolerance::new);
+    }
+
+    /// Replaces the clock read by `today()` and `now()`, e.g. with a fixed instant in tests.
+    /// Set it before evaluation, because already evaluated child contexts keep their clock.
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.context.borrow_mut().clock = EvaluationClock::new(clock);
+    }
+
     /**
      * Evaluates a single field in the root context
      */
@@ -695,6 +873,29 @@ impl EdgeRulesRuntime {
         ExecutionContext::eval_all_fields(&self.context)
     }
 
+    /// Evaluates all fields and reports each evaluated field to the observer
+    pub fn eval_all_observed(&self, observer: &mut dyn EvaluationObserver) -> Result<(), RuntimeError> {
+        ExecutionContext::eval_all_fields_observed(&self.context, Some(observer))
+    }
+
+    /// Evaluates all fields like `eval_all` and reports the fields that evaluated to `Missing` with non-fatal warnings.
+    /// Fields evaluated before this call are reported too.
+    pub fn eval_all_with_diagnostics(&self) -> Result<EvalResult, RuntimeError> {
+        self.eval_all()?;
+
+        let mut warnings = Vec::new();
+        if let Some(tolerance) = &self.context.borrow().real_tolerance {
+            if tolerance.was_applied() {
+                warnings.push(format!("Reals were compared with a tolerance of {}", tolerance.tolerance));
+            }
+        }
+
+        let mut missing_fields = Vec::new();
+        collect_missing_fields(&self.context, "", &mut missing_fields);
+
+        Ok(EvalResult { value: ValueEnum::Reference(Rc::clone(&self.context)), warnings, missing_fields })
+    }
+
     pub fn get_type(&self, field_path: &str) -> Result<ValueType, ContextQueryErrorEnum> {
         if field_path == "*" {
             return Ok(ValueType::ObjectType(Rc::clone(&self.static_tree)));
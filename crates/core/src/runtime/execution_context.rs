@@ -1,17 +1,173 @@
-use crate::ast::context::context_object::ContextObject;
+use crate::ast::context::context_object::{ContextObject, ExpressionEntry};
 use crate::ast::context::context_object_type::EObjectContent;
 use crate::ast::context::context_object_type::EObjectContent::{ConstantValue, ExpressionRef, UserFunctionRef};
 use crate::link::node_data::{ContentHolder, Node, NodeData, NodeDataEnum};
-use crate::typesystem::errors::{LinkingError, RuntimeError};
-use crate::typesystem::types::{TypedValue, ValueType};
-use crate::typesystem::values::ValueEnum;
+use crate::typesystem::errors::{ErrorStack, LinkingError, RuntimeError};
+use crate::typesystem::types::{Float, TypedValue, ValueType};
 use crate::typesystem::values::ValueEnum::Reference;
-use crate::utils::{intern_field_name, Line, Lines};
-use std::cell::RefCell;
-use std::collections::HashMap;
+use crate::typesystem::values::{JsonSpecialValues, ValueEnum};
+use crate::utils::{intern_field_name, json_string, Line, Lines};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::rc::{Rc, Weak};
+use time::OffsetDateTime;
+
+/// Receives field evaluation events from `ExecutionContext::eval_all_fields_observed`,
+/// e.g. to measure how long each field takes to evaluate.
+pub trait EvaluationObserver {
+    /// Called before the field at `path` is evaluated
+    fn before_field(&mut self, path: &[String]);
+    /// Called after the field at `path` is evaluated
+    fn after_field(&mut self, path: &[String], result: &Result<ValueEnum, RuntimeError>);
+}
+
+/// Source of the current instant for `today()` and `now()`, replaced in tests to get deterministic results
+pub trait Clock {
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// Reads the system clock in UTC
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    fn now(&self) -> OffsetDateTime {
+        let nanos = (js_sys::Date::now() * 1_000_000.0) as i128;
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).unwrap_or(OffsetDateTime::UNIX_EPOCH)
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// Clock shared by all contexts of one runtime. It is read once, so every `today()` and `now()` call within
+/// one `eval_all` sees the same timestamp.
+pub struct EvaluationClock {
+    clock: Rc<dyn Clock>,
+    instant: Cell<Option<OffsetDateTime>>,
+}
+
+impl EvaluationClock {
+    pub fn new(clock: Rc<dyn Clock>) -> Rc<Self> {
+        Rc::new(EvaluationClock { clock, instant: Cell::new(None) })
+    }
+
+    pub fn now(&self) -> OffsetDateTime {
+        match self.instant.get() {
+            Some(instant) => instant,
+            None => {
+                let instant = self.clock.now();
+                self.instant.set(Some(instant));
+                instant
+            }
+        }
+    }
+
+    /// True when `today()` or `now()` has read the clock
+    pub fn was_read(&self) -> bool {
+        self.instant.get().is_some()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Debug for EvaluationClock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvaluationClock").field("instant", &self.instant.get()).finish()
+    }
+}
+
+/// Tolerance for comparing reals, shared by all contexts of one runtime. It records whether any comparison used it.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct RealTolerance {
+    pub tolerance: Float,
+    applied: Cell<bool>,
+}
+
+impl RealTolerance {
+    pub fn new(tolerance: Float) -> Rc<Self> {
+        Rc::new(RealTolerance { tolerance, applied: Cell::new(false) })
+    }
+
+    pub fn mark_applied(&self) {
+        self.applied.set(true);
+    }
+
+    /// True when reals were compared with the tolerance
+    pub fn was_applied(&self) -> bool {
+        self.applied.get()
+    }
+}
+
+/// Fields read while evaluating each field, keyed by dotted field paths. Reads made by nested evaluations, such as
+/// referenced fields that are not evaluated yet or function bodies, count for every field being evaluated.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[derive(Default)]
+pub struct DependencyGraph {
+    evaluating: RefCell<Vec<String>>,
+    reads: RefCell<HashMap<String, HashSet<String>>>,
+    positions: RefCell<HashMap<String, usize>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Rc<Self> {
+        Rc::new(DependencyGraph::default())
+    }
+
+    /// Starts recording the reads of `path`, dropping the reads of its previous evaluation
+    pub(crate) fn enter_field(&self, path: String) {
+        self.reads.borrow_mut().insert(path.clone(), HashSet::new());
+        let mut positions = self.positions.borrow_mut();
+        let next = positions.len();
+        positions.entry(path.clone()).or_insert(next);
+        self.evaluating.borrow_mut().push(path);
+    }
+
+    pub(crate) fn exit_field(&self) {
+        self.evaluating.borrow_mut().pop();
+    }
+
+    pub(crate) fn record_read(&self, path: String) {
+        let mut reads = self.reads.borrow_mut();
+        for field in self.evaluating.borrow().iter() {
+            if let Some(fields) = reads.get_mut(field) {
+                fields.insert(path.clone());
+            }
+        }
+    }
+
+    /// Fields that read `changed` directly or through other fields, in the order they were first evaluated.
+    /// Reading an object counts as reading all of its fields.
+    pub fn dependents(&self, changed: &str) -> Vec<String> {
+        fn overlaps(read: &str, changed: &str) -> bool {
+            let nested = |outer: &str, inner: &str| inner.strip_prefix(outer).is_some_and(|rest| rest.starts_with('.'));
+            read == changed || nested(read, changed) || nested(changed, read)
+        }
+
+        let reads = self.reads.borrow();
+        let mut affected: HashSet<&str> = HashSet::new();
+        let mut pending = vec![changed];
+        while let Some(path) = pending.pop() {
+            for (field, fields_read) in reads.iter() {
+                if field == changed || affected.contains(field.as_str()) {
+                    continue;
+                }
+                if fields_read.iter().any(|read| overlaps(read, path)) {
+                    affected.insert(field);
+                    pending.push(field);
+                }
+            }
+        }
+
+        let positions = self.positions.borrow();
+        let mut dependents: Vec<String> = affected.into_iter().map(str::to_string).collect();
+        dependents.sort_by_key(|field| positions.get(field).copied().unwrap_or(usize::MAX));
+        dependents
+    }
+}
 
 /// ---
 /// @TODO: https://doc.rust-lang.org/book/ch15-04-rc.html
@@ -29,6 +185,12 @@ pub struct ExecutionContext {
     pub promise_eval_all: bool,
     /// stack can be constantly updated. accessed via API
     stack: RefCell<HashMap<&'static str, Result<ValueEnum, RuntimeError>>>,
+    /// Optional tolerance for comparing reals, inherited by child contexts. `None` compares exactly.
+    pub real_tolerance: Option<Rc<RealTolerance>>,
+    /// Clock for `today()` and `now()`, shared with child contexts
+    pub clock: Rc<EvaluationClock>,
+    /// Records field reads when set, shared with child contexts
+    pub dependencies: Option<Rc<DependencyGraph>>,
     /// Weak self pointer to allow building parent links from methods that only have &self
     self_ref: Weak<RefCell<ExecutionContext>>,
 }
@@ -99,6 +261,10 @@ impl ContentHolder<ExecutionContext> for ExecutionContext {
     fn get_field_names(&self) -> Vec<&'static str> {
         self.object.borrow().get_field_names()
     }
+
+    fn is_optional_field(&self, name: &str) -> bool {
+        self.object.borrow().is_optional_field(name)
+    }
 }
 
 impl TypedValue for ExecutionContext {
@@ -115,6 +281,9 @@ impl ExecutionContext {
             context_variable: None,
             node: NodeData::new(NodeDataEnum::Isolated()),
             promise_eval_all: false,
+            real_tolerance: None,
+            clock: EvaluationClock::new(Rc::new(SystemClock)),
+            dependencies: None,
             self_ref: Weak::new(),
         }
         .into_rc()
@@ -127,6 +296,9 @@ impl ExecutionContext {
             context_variable: None,
             node: NodeData::new(NodeDataEnum::Root()),
             promise_eval_all: false,
+            real_tolerance: None,
+            clock: EvaluationClock::new(Rc::new(SystemClock)),
+            dependencies: None,
             self_ref: Weak::new(),
         }
         .into_rc()
@@ -143,6 +315,9 @@ impl ExecutionContext {
             context_variable: None,
             node: NodeData::new(NodeDataEnum::Child(assigned_to_field, Weak::new())),
             promise_eval_all: false,
+            real_tolerance: self.real_tolerance.clone(),
+            clock: Rc::clone(&self.clock),
+            dependencies: self.dependencies.clone(),
             self_ref: Weak::new(),
         }
         .into_rc();
@@ -169,6 +344,9 @@ impl ExecutionContext {
             context_variable: None,
             node: NodeData::new(NodeDataEnum::Internal(Rc::downgrade(&parent), None)),
             promise_eval_all: false,
+            real_tolerance: parent.borrow().real_tolerance.clone(),
+            clock: Rc::clone(&parent.borrow().clock),
+            dependencies: parent.borrow().dependencies.clone(),
             self_ref: Weak::new(),
         }
         .into_rc()
@@ -219,18 +397,36 @@ impl ExecutionContext {
 
         lines.tab();
 
+        {
+            // type definitions are kept in a map, so they are sorted to keep the output stable
+            let object = self.object.borrow();
+            let mut type_names: Vec<&String> = object.defined_types.keys().collect();
+            type_names.sort();
+            for type_name in type_names {
+                if let Some(body) = object.defined_types.get(type_name) {
+                    lines.add_str(format!("type {}: {}", type_name, body).as_str());
+                }
+            }
+        }
+
         for field_name in &self.object.borrow().get_field_names() {
             match self.get(field_name) {
                 Ok(field) => {
                     match field {
                         ConstantValue(value) => {
+                            if value.is_omitted() && self.is_optional_field(field_name) {
+                                continue;
+                            }
                             lines.add_str(format!("{}: {}", field_name, value).as_str());
                         }
                         ExpressionRef(expression) => {
-                            lines.add_str(format!("{}: {}", field_name, expression.borrow().expression).as_str());
+                            let marker = if self.is_optional_field(field_name) { "?" } else { "" };
+                            lines.add_str(
+                                format!("{}{}: {}", field_name, marker, expression.borrow().expression).as_str(),
+                            );
                         }
-                        UserFunctionRef(_) => {
-                            // skip
+                        UserFunctionRef(method) => {
+                            lines.add_str(format!("func {}", method.borrow().function_definition).as_str());
                         }
                         EObjectContent::ObjectRef(ref object) => {
                             let result_reference = Rc::clone(object);
@@ -251,6 +447,44 @@ impl ExecutionContext {
         lines.add_str("}");
     }
 
+    /// Evaluates all fields and serializes them as a JSON object.
+    /// Keys are user field names only, so internal `#child` names never appear; functions are skipped.
+    pub fn to_json(ctx: &Rc<RefCell<ExecutionContext>>) -> Result<String, RuntimeError> {
+        ExecutionContext::to_json_with(ctx, JsonSpecialValues::AsText)
+    }
+
+    /// Same as `to_json`, with the given rendering of special values
+    pub fn to_json_with(
+        ctx: &Rc<RefCell<ExecutionContext>>,
+        special_values: JsonSpecialValues,
+    ) -> Result<String, RuntimeError> {
+        ExecutionContext::eval_all_fields(ctx)?;
+
+        let field_names = ctx.borrow().object.borrow().get_field_names();
+        let mut members = Vec::with_capacity(field_names.len());
+
+        for field_name in field_names {
+            let content = ctx.borrow().get(field_name);
+            let value = match content {
+                Ok(ConstantValue(value)) => {
+                    if value.is_omitted() && ctx.borrow().is_optional_field(field_name) {
+                        continue;
+                    }
+                    value.to_json_with(special_values)?
+                }
+                Ok(EObjectContent::ObjectRef(child)) => ExecutionContext::to_json_with(&child, special_values)?,
+                Ok(UserFunctionRef(_)) | Ok(EObjectContent::Definition(_)) => continue,
+                Ok(ExpressionRef(_)) => {
+                    return RuntimeError::eval_error(format!("Field '{}' is not evaluated", field_name)).into()
+                }
+                Err(err) => return RuntimeError::eval_error(err.to_string()).into(),
+            };
+            members.push(format!("{}:{}", json_string(field_name), value));
+        }
+
+        Ok(format!("{{{}}}", members.join(",")))
+    }
+
     pub fn stack_insert(&self, field_name: &'static str, value: Result<ValueEnum, RuntimeError>) {
         if let Ok(Reference(child_ctx)) = &value {
             if let Some(parent) = self.self_ref.upgrade() {
@@ -264,7 +498,46 @@ impl ExecutionContext {
         self.stack.borrow_mut().insert(field_name, value);
     }
 
+    pub(crate) fn stack_get(&self, field_name: &str) -> Option<Result<ValueEnum, RuntimeError>> {
+        self.stack.borrow().get(field_name).cloned()
+    }
+
+    /// Forgets the evaluated value, so the field is evaluated again when it is read
+    pub(crate) fn stack_remove(&self, field_name: &str) -> Option<Result<ValueEnum, RuntimeError>> {
+        self.stack.borrow_mut().remove(field_name)
+    }
+
+    /// Copy of the context with its own evaluated fields, nested objects are copied as well. The copy keeps
+    /// the parent link of the original, so unevaluated fields resolve the same way.
+    pub fn deep_clone(ctx: &Rc<RefCell<ExecutionContext>>) -> Rc<RefCell<ExecutionContext>> {
+        let source = ctx.borrow();
+        let copy = Self {
+            object: Rc::clone(&source.object),
+            stack: RefCell::new(HashMap::new()),
+            context_variable: source.context_variable.clone(),
+            node: NodeData::new(source.node.node_type.clone()),
+            promise_eval_all: source.promise_eval_all,
+            real_tolerance: source.real_tolerance.clone(),
+            clock: Rc::clone(&source.clock),
+            dependencies: source.dependencies.clone(),
+            self_ref: Weak::new(),
+        }
+        .into_rc();
+
+        for (&name, value) in source.stack.borrow().iter() {
+            copy.borrow().stack_insert(name, value.as_ref().map(ValueEnum::deep_clone).map_err(Clone::clone));
+        }
+        copy
+    }
+
     pub fn eval_all_fields(ctx: &Rc<RefCell<ExecutionContext>>) -> Result<(), RuntimeError> {
+        Self::eval_all_fields_observed(ctx, None)
+    }
+
+    pub fn eval_all_fields_observed<'o>(
+        ctx: &Rc<RefCell<ExecutionContext>>,
+        mut observer: Option<&mut (dyn EvaluationObserver + 'o)>,
+    ) -> Result<(), RuntimeError> {
         if ctx.borrow().promise_eval_all {
             return Ok(());
         }
@@ -276,28 +549,11 @@ impl ExecutionContext {
         for name in field_names {
             match ctx.borrow().get(name)? {
                 EObjectContent::ExpressionRef(expression) => {
-                    ctx.borrow().node().lock_field(name)?;
-                    let value = expression.borrow().expression.eval(Rc::clone(ctx));
-
-                    let result = match value {
-                        Ok(v) => Ok(v),
-                        Err(mut err) => {
-                            if err.location().is_empty() {
-                                *err.location_mut() = build_location_from_execution_context(ctx, name);
-                            }
-                            if !err.has_expression() {
-                                err.set_expression(expression.borrow().expression.to_string());
-                            }
-                            Err(err)
-                        }
-                    };
-
-                    ctx.borrow().stack_insert(name, result);
-                    ctx.borrow().node().unlock_field(name);
+                    ExecutionContext::eval_field_observed(ctx, name, expression, observer.as_deref_mut())?;
                 }
                 EObjectContent::ObjectRef(reference) => {
                     NodeData::attach_child(ctx, &reference);
-                    ExecutionContext::eval_all_fields(&reference)?;
+                    ExecutionContext::eval_all_fields_observed(&reference, observer.as_deref_mut())?;
                 }
                 _ => {}
             }
@@ -305,6 +561,53 @@ impl ExecutionContext {
 
         Ok(())
     }
+
+    /// Evaluates one expression field and stores the result in the stack
+    pub(crate) fn eval_field_observed<'o>(
+        ctx: &Rc<RefCell<ExecutionContext>>,
+        name: &'static str,
+        expression: Rc<RefCell<ExpressionEntry>>,
+        mut observer: Option<&mut (dyn EvaluationObserver + 'o)>,
+    ) -> Result<(), RuntimeError> {
+        ctx.borrow().node().lock_field(name)?;
+        let dependencies = ctx.borrow().dependencies.clone();
+        let path =
+            (observer.is_some() || dependencies.is_some()).then(|| build_location_from_execution_context(ctx, name));
+        if let (Some(observer), Some(path)) = (observer.as_deref_mut(), &path) {
+            observer.before_field(path);
+        }
+        if let (Some(dependencies), Some(path)) = (&dependencies, &path) {
+            dependencies.enter_field(path.join("."));
+        }
+
+        let value = expression.borrow().expression.eval(Rc::clone(ctx));
+
+        if let Some(dependencies) = &dependencies {
+            dependencies.exit_field();
+        }
+
+        let result = match value {
+            Ok(v) => Ok(v),
+            Err(mut err) => {
+                let field_path = build_location_from_execution_context(ctx, name);
+                if err.location().is_empty() {
+                    *err.location_mut() = field_path.clone();
+                }
+                if !err.has_expression() {
+                    err.set_expression(expression.borrow().expression.to_string());
+                }
+                Err(err.with_context(|| format!("While evaluating field `{}`", field_path.join("."))))
+            }
+        };
+
+        if let (Some(observer), Some(path)) = (observer, &path) {
+            observer.after_field(path, &result);
+        }
+
+        ctx.borrow().stack_insert(name, result);
+        ctx.borrow().node().unlock_field(name);
+        Ok(())
+    }
 }
 
 pub(crate) fn build_location_from_execution_context(
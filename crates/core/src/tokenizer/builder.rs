@@ -182,6 +182,10 @@ impl ASTBuilder {
         self.result.push_back(token);
     }
 
+    pub fn level(&self) -> u32 {
+        self.current_level
+    }
+
     pub fn incl_level(&mut self) {
         self.current_level += 1;
     }
@@ -199,17 +203,22 @@ pub mod factory {
     use crate::ast::context::context_object::ExpressionEntry;
     use crate::ast::context::context_object_builder::ContextObjectBuilder;
     use crate::ast::context::context_object_type::FormalParameter;
-    use crate::ast::foreach::ForFunction;
+    use crate::ast::decision_table::DecisionTable;
+    use crate::ast::expression::EvaluatableExpression;
+    use crate::ast::foreach::{ForFunction, MapFunction, ReduceFunction};
     use crate::ast::functions::function_types::{
-        BinaryFunction, MultiFunction, UnaryFunction, BINARY_BUILT_IN_FUNCTIONS, BUILT_IN_ALL_FUNCTIONS,
-        MULTI_BUILT_IN_FUNCTIONS, UNARY_BUILT_IN_FUNCTIONS,
+        BinaryFunction, ClockFunction, MultiFunction, UnaryFunction, BINARY_BUILT_IN_FUNCTIONS, BUILT_IN_ALL_FUNCTIONS,
+        CLOCK_BUILT_IN_FUNCTIONS, MULTI_BUILT_IN_FUNCTIONS, PREDICATE_FORM_FUNCTIONS, UNARY_BUILT_IN_FUNCTIONS,
     };
     use crate::ast::ifthenelse::IfThenElseFunction;
+    use crate::ast::lambda::LambdaFunction;
     use crate::ast::metaphors::functions::{FunctionDefinition, InlineFunctionDefinition};
-    use crate::ast::operators::comparators::ComparatorOperator;
+    use crate::ast::operators::comparators::{ComparatorEnum, ComparatorOperator};
     use crate::ast::operators::logical_operators::{LogicalOperator, LogicalOperatorEnum};
-    use crate::ast::operators::math_operators::{MathOperator, MathOperatorEnum, NegationOperator};
-    use crate::ast::selections::{ExpressionFilter, FieldSelection};
+    use crate::ast::operators::math_operators::{MathOperator, MathOperatorEnum, NegationOperator, OperatorData};
+    use crate::ast::selections::{
+        ExpressionFilter, FieldSelection, FlatMap, GroupBy, LookupFunction, Quantifier, SortBy, WhileSelection,
+    };
     use crate::ast::sequence::CollectionExpression;
     use crate::ast::token::DefinitionEnum::{InlineUserFunction, UserFunction as UserFunctionDef};
     use crate::ast::token::EToken;
@@ -218,6 +227,7 @@ pub mod factory {
     use crate::ast::token::ExpressionEnum::*;
     use crate::ast::token::*;
     use crate::ast::user_function_call::UserFunctionCall;
+    use crate::ast::utils::array_to_code_sep;
     use crate::link::node_data::Node;
     use crate::tokenizer::parser::parse_type;
     use crate::tokenizer::utils::*;
@@ -225,6 +235,7 @@ pub mod factory {
     use crate::typesystem::errors::ParseErrorEnum::{FunctionWrongNumberOfArguments, UnexpectedToken, WrongFormat};
     use crate::typesystem::values::ValueEnum;
     use crate::utils::intern_field_name;
+    use std::any::Any;
     use std::cell::RefCell;
     use std::collections::vec_deque::VecDeque;
     use std::rc::Rc;
@@ -363,9 +374,36 @@ pub mod factory {
     ) -> Result<EToken, ParseErrorEnum> {
         // @Todo: need to check level before adding and stop adding if smaller
         let mut obj = ContextObjectBuilder::new();
+        let mut spread_marker = false;
+        let mut optional_marker = false;
 
         while let Some(right_token) = right.pop_front() {
+            if optional_marker {
+                let Expression(ObjectField(field_name, expression)) = right_token else {
+                    return Err(WrongFormat(format!("'{}' cannot be an optional field", right_token)));
+                };
+                if matches!(*expression, StaticObject(_) | TypePlaceholder(_)) {
+                    return Err(WrongFormat(format!("Field '{}' cannot be optional", field_name)));
+                }
+                obj.add_optional_expression(field_name.as_str(), *expression)?;
+                optional_marker = false;
+                continue;
+            }
+
+            if spread_marker {
+                let Expression(Variable(source)) = right_token else {
+                    return Err(WrongFormat(format!("Only object references can be spread, got `...{}`", right_token)));
+                };
+                obj.add_spread(source);
+                spread_marker = false;
+                continue;
+            }
+
             match right_token {
+                // `...` is tokenized as a context variable, here it marks the following object spread
+                Expression(ContextVariable) => spread_marker = true,
+                // `?` is placed before the field name of `field?: value`
+                Unparsed(LiteralToken(marker)) if marker == "?" => optional_marker = true,
                 Definition(definition) => {
                     obj.add_definition(definition)?;
                 }
@@ -392,6 +430,10 @@ pub mod factory {
             }
         }
 
+        if spread_marker {
+            return Err(WrongFormat("Spread source is missing after `...`".to_string()));
+        }
+
         Ok(Expression(StaticObject(obj.build())))
     }
 
@@ -408,12 +450,74 @@ pub mod factory {
         let name = name_string.as_str();
         let mut arguments = right.drain_expressions()?;
 
+        if arguments.iter().any(|argument| matches!(argument, ObjectField(_, _))) {
+            if BUILT_IN_ALL_FUNCTIONS.contains_key(name) {
+                return Err(WrongFormat(format!("Built-in function '{}' does not accept named arguments", name)));
+            }
+
+            return Ok(Expression(FunctionCall(Box::new(UserFunctionCall::with_named_arguments(
+                name_string,
+                arguments,
+            )?))));
+        }
+
+        if name == "decisionTable" {
+            return Ok(Expression(FunctionCall(Box::new(DecisionTable::build(arguments)?))));
+        }
+
         if arguments.len() == 1 {
             if let Some(function) = UNARY_BUILT_IN_FUNCTIONS.get(name) {
                 let expression = arguments.pop().unwrap();
                 return Ok(Expression(UnaryFunction::build(function.clone(), expression).into()));
             }
         } else if arguments.len() == 2 {
+            if name == "map" {
+                let projection = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(FunctionCall(Box::new(MapFunction::build(list, projection)?))));
+            }
+
+            // same as `list[predicate]`
+            if name == "filter" {
+                let predicate = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(Filter(Box::new(ExpressionFilter::build(list, predicate)?))));
+            }
+
+            if name == "groupBy" {
+                let key = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(FunctionCall(Box::new(GroupBy::build(list, key)?))));
+            }
+
+            if name == "sortBy" || name == "sortByDesc" {
+                let key = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(FunctionCall(Box::new(SortBy::build(list, key, name == "sortByDesc")?))));
+            }
+
+            if name == "flatMap" {
+                let projection = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(FunctionCall(Box::new(FlatMap::build(list, projection)?))));
+            }
+
+            if name == "takeWhile" || name == "dropWhile" {
+                let predicate = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(FunctionCall(Box::new(WhileSelection::build(
+                    list,
+                    predicate,
+                    name == "takeWhile",
+                )?))));
+            }
+
+            if PREDICATE_FORM_FUNCTIONS.contains(&name) {
+                let predicate = arguments.pop().unwrap();
+                let list = arguments.pop().unwrap();
+                return Ok(Expression(FunctionCall(Box::new(Quantifier::build(list, predicate, name == "all")?))));
+            }
+
             if let Some(function) = BINARY_BUILT_IN_FUNCTIONS.get(name) {
                 let right_expression = arguments.pop().unwrap();
                 let left_expression = arguments.pop().unwrap();
@@ -423,13 +527,31 @@ pub mod factory {
             }
         }
 
+        if name == "reduce" && arguments.len() == 3 {
+            let body = arguments.pop().unwrap();
+            let initial = arguments.pop().unwrap();
+            let list = arguments.pop().unwrap();
+            return Ok(Expression(FunctionCall(Box::new(ReduceFunction::build(list, initial, body)?))));
+        }
+
+        if name == "lookup" && arguments.len() == 4 {
+            return Ok(Expression(FunctionCall(Box::new(LookupFunction::build(arguments)?))));
+        }
+
+        if let Some(function) = CLOCK_BUILT_IN_FUNCTIONS.get(name) {
+            return Ok(Expression(ClockFunction::build(function.clone(), arguments).into()));
+        }
+
         if let Some(function) = MULTI_BUILT_IN_FUNCTIONS.get(name) {
             return Ok(Expression(MultiFunction::build(function.clone(), arguments).into()));
         }
 
         match BUILT_IN_ALL_FUNCTIONS.get(name) {
             None => Ok(Expression(FunctionCall(Box::new(UserFunctionCall::new(name_string, arguments))))),
-            Some(finding) => Err(FunctionWrongNumberOfArguments(name.to_string(), finding.clone(), arguments.len())),
+            Some(finding) => {
+                let call = format!("{}({})", name, array_to_code_sep(arguments.iter(), ", "));
+                Err(FunctionWrongNumberOfArguments(name.to_string(), finding.clone(), arguments.len(), Some(call)))
+            }
         }
     }
 
@@ -455,17 +577,45 @@ pub mod factory {
         token: EToken,
         right: &mut TokenChain,
     ) -> Result<EToken, ParseErrorEnum> {
-        let mut arguments = Vec::new();
+        let mut arguments: Vec<FormalParameter> = Vec::new();
+        let mut rest_marker = false;
         while let Some(right_token) = right.pop_front() {
+            if arguments.last().is_some_and(|last| last.variadic) {
+                return Err(WrongFormat("Rest parameter must be the last function parameter".to_string()));
+            }
+
             match right_token {
                 Unparsed(CommaToken) => {
+                    if rest_marker {
+                        return Err(WrongFormat("Rest parameter name is missing after `...`".to_string()));
+                    }
                     if arguments.is_empty() {
                         return Err(WrongFormat("Very first function argument is missing".to_string()));
                     }
                 }
                 ParseError(err) => return Err(err),
+                // `...` is tokenized as a context variable, here it marks the following rest parameter
+                Expression(ContextVariable) if !rest_marker => rest_marker = true,
                 Expression(expression) => {
-                    let parameter = parse_function_parameter(expression)?;
+                    let mut parameter = parse_function_parameter(expression)?;
+                    if rest_marker {
+                        if parameter.default_value.is_some() {
+                            return Err(WrongFormat(format!(
+                                "Rest parameter `{}` cannot have a default value",
+                                parameter.name
+                            )));
+                        }
+                        parameter = parameter.as_variadic();
+                        rest_marker = false;
+                    }
+                    let follows_default =
+                        arguments.last().is_some_and(|last: &FormalParameter| last.default_value.is_some());
+                    if follows_default && parameter.default_value.is_none() && !parameter.variadic {
+                        return Err(WrongFormat(format!(
+                            "Parameter `{}` without a default value cannot follow parameters with defaults",
+                            parameter.name
+                        )));
+                    }
                     arguments.push(parameter);
                 }
                 other => {
@@ -474,6 +624,10 @@ pub mod factory {
             }
         }
 
+        if rest_marker {
+            return Err(WrongFormat("Rest parameter name is missing after `...`".to_string()));
+        }
+
         let function_name = match token {
             Unparsed(FunctionNameToken(variable)) => {
                 if variable.path.len() != 1 {
@@ -495,8 +649,8 @@ pub mod factory {
     }
 
     fn parse_function_parameter(expression: ExpressionEnum) -> Result<FormalParameter, ParseErrorEnum> {
-        match expression {
-            Variable(variable) => {
+        match split_default_value(expression)? {
+            (Variable(variable), default_value) => {
                 if variable.path.len() != 1 {
                     return Err(WrongFormat(format!(
                         "Function parameter must be a simple identifier, got `{}`",
@@ -504,14 +658,65 @@ pub mod factory {
                     )));
                 }
 
-                Ok(FormalParameter::with_type_ref(variable.get_name(), ComplexTypeRef::undefined()))
+                let parameter = FormalParameter::with_type_ref(variable.get_name(), ComplexTypeRef::undefined());
+                Ok(with_default(parameter, default_value))
             }
-            ObjectField(name, boxed_expression) => {
-                let annotation = extract_type_annotation(*boxed_expression)?;
+            (ObjectField(name, boxed_expression), None) => {
+                let (annotation, default_value) = split_default_value(*boxed_expression)?;
+                let annotation = extract_type_annotation(annotation)?;
                 let parameter_type = annotation.unwrap_or_else(ComplexTypeRef::undefined);
-                Ok(FormalParameter::with_type_ref(name, parameter_type))
+                Ok(with_default(FormalParameter::with_type_ref(name, parameter_type), default_value))
+            }
+            (expression, _) => {
+                Err(WrongFormat(format!("Unsupported expression `{}` in function parameter list", expression)))
             }
-            _ => Err(WrongFormat(format!("Unsupported expression `{}` in function parameter list", expression))),
+        }
+    }
+
+    fn with_default(parameter: FormalParameter, default_value: Option<ValueEnum>) -> FormalParameter {
+        match default_value {
+            Some(value) => parameter.with_default_value(value),
+            None => parameter,
+        }
+    }
+
+    /// `greeting: string = 'Hello'` is tokenized as an equality comparison, so the default value is
+    /// taken from its right side. Only literal defaults, including negative numbers, are supported.
+    fn split_default_value(expression: ExpressionEnum) -> Result<(ExpressionEnum, Option<ValueEnum>), ParseErrorEnum> {
+        let Operator(operator) = expression else {
+            return Ok((expression, None));
+        };
+
+        let is_assignment = (operator.as_ref() as &dyn Any)
+            .downcast_ref::<ComparatorOperator>()
+            .is_some_and(|comparator| matches!(comparator.data.operator, ComparatorEnum::Equals));
+        if !is_assignment {
+            return Ok((Operator(operator), None));
+        }
+
+        let comparator = (operator as Box<dyn Any>)
+            .downcast::<ComparatorOperator>()
+            .map_err(|_| WrongFormat("Unsupported default value in function parameter list".to_string()))?;
+        let OperatorData { left, right, .. } = comparator.data;
+
+        let default_value = match &right {
+            Value(value) => Some(value.clone()),
+            FunctionCall(function) => negated_literal(function.as_ref()),
+            _ => None,
+        };
+
+        match default_value {
+            Some(value) => Ok((left, Some(value))),
+            None => Err(WrongFormat(format!("Default value of function parameter must be a literal, got `{}`", right))),
+        }
+    }
+
+    /// Value of `-1`, which is tokenized as a negation of the literal `1`
+    fn negated_literal(function: &dyn EvaluatableExpression) -> Option<ValueEnum> {
+        let negation = (function as &dyn Any).downcast_ref::<NegationOperator>()?;
+        match &negation.left {
+            Value(ValueEnum::NumberValue(number)) => Some(ValueEnum::NumberValue(number.negate())),
+            _ => None,
         }
     }
 
@@ -529,7 +734,6 @@ pub mod factory {
                 let type_name = variable.get_name();
                 Ok(Some(parse_type(&type_name)))
             }
-            Value(_) => Err(WrongFormat("Default values for function parameters are not supported".to_string())),
             other => Err(WrongFormat(format!("Unsupported type annotation expression `{}`", other))),
         }
     }
@@ -643,6 +847,31 @@ pub mod factory {
         // ... if ... then ... else ...
         // left---------------^    ^-----------------right
 
+        let (if_condition, then_content) = pop_if_then(left)?;
+
+        let else_content = right
+            .pop_right_expression()
+            .map_err(|err| WrongFormat("Error in else... part".to_string()).before(err))?;
+
+        let func = IfThenElseFunction::build(if_condition, then_content, Some(else_content))?;
+
+        Ok(Expression(FunctionCall(Box::new(func))))
+    }
+
+    /// `if ... then ...` closed by the end of the field, without `else`
+    pub fn build_if_then(
+        left: &mut TokenChain,
+        _token: EToken,
+        _right: &mut TokenChain,
+    ) -> Result<EToken, ParseErrorEnum> {
+        let (if_condition, then_content) = pop_if_then(left)?;
+
+        let func = IfThenElseFunction::build(if_condition, then_content, None)?;
+
+        Ok(Expression(FunctionCall(Box::new(func))))
+    }
+
+    fn pop_if_then(left: &mut TokenChain) -> Result<(ExpressionEnum, ExpressionEnum), ParseErrorEnum> {
         let then_content = left
             .pop_left_expression()
             .map_err(|err| WrongFormat("Error in then... part".to_string()).before(err))?;
@@ -655,13 +884,7 @@ pub mod factory {
 
         let _if_part = left.pop_left_as_expected("if")?;
 
-        let else_content = right
-            .pop_right_expression()
-            .map_err(|err| WrongFormat("Error in else... part".to_string()).before(err))?;
-
-        let func = IfThenElseFunction::build(if_condition, then_content, else_content)?;
-
-        Ok(Expression(FunctionCall(Box::new(func))))
+        Ok((if_condition, then_content))
     }
 
     pub fn build_for_each_return(
@@ -701,6 +924,25 @@ pub mod factory {
         Ok(new_token)
     }
 
+    pub fn build_lambda(
+        _left: &mut TokenChain,
+        token: EToken,
+        right: &mut TokenChain,
+    ) -> Result<EToken, ParseErrorEnum> {
+        // (a, b) => body
+        //           ^----right
+
+        let Unparsed(LambdaToken(parameters)) = token else {
+            return Err(UnexpectedToken(Box::new(token), Some("lambda parameters".into())));
+        };
+
+        let body = right.pop_right_expression().map_err(|err| {
+            WrongFormat(format!("Lambda ({}) => [???] body is not complete", parameters.join(", "))).before(err)
+        })?;
+
+        Ok(Expression(FunctionCall(Box::new(LambdaFunction::build(parameters, body)?))))
+    }
+
     pub fn build_any_operator(
         left: &mut TokenChain,
         token: EToken,
@@ -38,6 +38,9 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
     let mut left_side = true;
     let mut after_colon = false;
 
+    // levels of `then` branches that are still waiting for `else`
+    let mut open_then_levels: Vec<u32> = Vec::new();
+
     // @Todo: does it worth having function_def_gate_open and type_def_gate_open to simplify further parsing?
     // function_def_gate_open and type_def_gate_open can be integers that are alawys increased or decreased depending on ctx
     // Also, maybe those def gate opens could help validate special situations, such as only under type gate we can open < > as
@@ -140,8 +143,13 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
                 ast_builder.incl_level();
             }
             ';' | '\n' => {
+                let line_break = *symbol == '\n';
                 source.next_char();
 
+                if !line_break || !source.next_word_is("else") {
+                    close_if_then(&mut ast_builder, &mut open_then_levels);
+                }
+
                 left_side = true;
 
                 //----------------------> ctx instead of 0!!!!
@@ -150,6 +158,8 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
             '}' => {
                 source.next_char();
 
+                close_if_then(&mut ast_builder, &mut open_then_levels);
+
                 //ctx_open -= 1;
                 ast_builder.dec_level();
 
@@ -159,6 +169,15 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
                 ast_builder.dec_level();
             }
             '(' => {
+                // anonymous function `(a, b) => body` can appear only as a value
+                if !left_side && ast_builder.last_variable().is_none() {
+                    if let Some(parameters) = source.parse_lambda_parameters() {
+                        after_colon = false;
+                        ast_builder.push_node(LambdaPriority as u32, Unparsed(LambdaToken(parameters)), build_lambda);
+                        continue;
+                    }
+                }
+
                 source.next_char();
 
                 // prioritizing function/call merge
@@ -200,6 +219,8 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
             ')' => {
                 source.next_char();
 
+                close_if_then(&mut ast_builder, &mut open_then_levels);
+
                 ast_builder.dec_level();
 
                 ast_builder.merge();
@@ -209,6 +230,8 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
             ',' => {
                 source.next_char();
 
+                close_if_then(&mut ast_builder, &mut open_then_levels);
+
                 ast_builder.push_element(Unparsed(CommaToken));
             }
             ' ' | '\t' | '\r' => {
@@ -232,9 +255,13 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
                                 ast_builder.dec_level();
                                 ast_builder.push_element(Unparsed(LiteralToken(literal.into())));
                                 ast_builder.incl_level();
+                                open_then_levels.push(ast_builder.level());
                             }
 
                             "else" => {
+                                if open_then_levels.last() == Some(&ast_builder.level()) {
+                                    open_then_levels.pop();
+                                }
                                 ast_builder.merge();
                                 ast_builder.dec_level();
                                 ast_builder.push_node(
@@ -310,6 +337,36 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
                                 build_logical_operator,
                             ),
 
+                            "import" if left_side && matches!(source.peek_skip_whitespace(), Some('"' | '\'')) => {
+                                while matches!(source.peek(), Some(' ' | '\t' | '\r')) {
+                                    source.next_char();
+                                }
+                                let quote = source.next_char().unwrap();
+                                let path = source.get_all_till(quote);
+
+                                let mut alias = None;
+                                if source.peek_skip_whitespace() == Some('a') && source.next_word_is("as") {
+                                    while matches!(source.peek(), Some(' ' | '\t' | '\r')) {
+                                        source.next_char();
+                                    }
+                                    source.next_char();
+                                    source.next_char();
+                                    source.skip_whitespace();
+                                    alias = Some(source.get_alphanumeric_or(&['_']));
+                                }
+
+                                match alias {
+                                    Some(alias) if alias.is_empty() => {
+                                        ast_builder.push_element(error_token!("Import alias is missing after `as`"))
+                                    }
+                                    alias => {
+                                        ast_builder.push_element(Definition(DefinitionEnum::Import(ImportDefinition {
+                                            path,
+                                            alias,
+                                        })))
+                                    }
+                                }
+                            }
                             "func" => {
                                 // @Todo: the func is recognized, so it can be mapped to Unparsed::UserFunctionGateOpen
                                 ast_builder.push_element(Unparsed(LiteralToken(literal.into())));
@@ -405,6 +462,8 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
             ']' => {
                 source.next_char();
 
+                close_if_then(&mut ast_builder, &mut open_then_levels);
+
                 ast_builder.dec_level();
 
                 ast_builder.merge();
@@ -433,6 +492,31 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
                         .push_element(error_token!("Unrecognized comparator after '{}'", source.next_char().unwrap()));
                 }
             }
+            '?' => {
+                source.next_char();
+
+                let optional_field = if source.peek_skip_whitespace() == Some(C_ASSIGN) && left_side {
+                    ast_builder.pop_last_variable()
+                } else {
+                    None
+                };
+
+                if let Some(field) = optional_field {
+                    // skipping whitespace and the colon itself
+                    while source.next_char() != Some(C_ASSIGN) {}
+
+                    left_side = false;
+                    after_colon = true;
+
+                    // the marker is placed before the field name, so the assignment is built as usual
+                    ast_builder.push_element(Unparsed(LiteralToken("?".into())));
+                    ast_builder.push_element(field.into());
+                    ast_builder.push_node(Assign as u32, Unparsed(AssignToken), build_assignment);
+                } else {
+                    ast_builder
+                        .push_element(error_token!("Optional field must be written as `field?{} value`", C_ASSIGN));
+                }
+            }
             '"' | '\'' => {
                 let string_starter = source.next_char().unwrap();
 
@@ -449,9 +533,26 @@ pub fn tokenize(input: &str) -> VecDeque<EToken> {
 
     //if seqOpen > 0 { panic!("Sequence not closed"); }
 
+    close_if_then(&mut ast_builder, &mut open_then_levels);
+
     ast_builder.finalize().0
 }
 
+/// Closes `if ... then ...` branches that ended without `else` at the current level
+fn close_if_then(ast_builder: &mut ASTBuilder, open_then_levels: &mut Vec<u32>) {
+    while open_then_levels.last() == Some(&ast_builder.level()) {
+        // `then` followed by a line break, the branch continues on the next line
+        if matches!(ast_builder.last_token(), Some(Unparsed(LiteralToken(literal))) if literal == "then") {
+            return;
+        }
+
+        open_then_levels.pop();
+        ast_builder.merge();
+        ast_builder.dec_level();
+        ast_builder.push_node(ReservedWords as u32, Unparsed(LiteralToken("else".into())), build_if_then);
+    }
+}
+
 pub fn parse_complex_type_in_angle(source: &mut CharStream) -> Result<ComplexTypeRef, ParseErrorEnum> {
     let mut name = String::new();
     while let Some(symbol) = source.peek().cloned() {
@@ -321,6 +321,45 @@ impl<'a> CharStream<'a> {
         Some(result)
     }
 
+    /// Consumes lambda parameters `(a, b) =>` if they follow, otherwise leaves the stream untouched
+    pub fn parse_lambda_parameters(&mut self) -> Option<Vec<String>> {
+        let mut lookahead = self.iter.clone();
+        let skip_whitespace = |iter: &mut Peekable<Chars<'a>>| while iter.next_if(|c| c.is_whitespace()).is_some() {};
+
+        lookahead.next_if_eq(&'(')?;
+        skip_whitespace(&mut lookahead);
+
+        let mut parameters: Vec<String> = Vec::new();
+
+        if lookahead.next_if_eq(&')').is_none() {
+            loop {
+                let mut name = String::new();
+                while let Some(c) = lookahead.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                if !name.chars().next()?.is_alphabetic() {
+                    return None;
+                }
+                parameters.push(name);
+
+                skip_whitespace(&mut lookahead);
+                match lookahead.next()? {
+                    ',' => skip_whitespace(&mut lookahead),
+                    ')' => break,
+                    _ => return None,
+                }
+            }
+        }
+
+        skip_whitespace(&mut lookahead);
+        lookahead.next_if_eq(&'=')?;
+        lookahead.next_if_eq(&'>')?;
+
+        self.iter = lookahead;
+
+        Some(parameters)
+    }
+
     // Override iter.next() method
     pub fn next_char(&mut self) -> Option<char> {
         self.iter.next()
@@ -340,6 +379,13 @@ impl<'a> CharStream<'a> {
         let mut iter = self.iter.clone();
         iter.find(|&c| c != ' ' && c != '\t' && c != '\r')
     }
+
+    /// Checks if the next word after any whitespace, including line breaks, is `word`
+    pub fn next_word_is(&self, word: &str) -> bool {
+        let mut iter = self.iter.clone().skip_while(|c| c.is_whitespace());
+        word.chars().all(|expected| iter.next() == Some(expected))
+            && !iter.next().is_some_and(|c| c.is_alphanumeric() || c == '_')
+    }
 }
 
 //----------------------------------------------------------------------------------------------
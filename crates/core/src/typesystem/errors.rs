@@ -1,5 +1,5 @@
 use crate::ast::context::duplicate_name_error::DuplicateNameError;
-use crate::ast::functions::function_types::EFunctionType;
+use crate::ast::functions::function_types::{describe_arity, EFunctionType, PREDICATE_FORM_FUNCTIONS};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
@@ -200,8 +200,8 @@ pub enum ParseErrorEnum {
     UnexpectedToken(Box<EToken>, Option<String>),
     UnexpectedLiteral(String, Option<String>),
     MissingLiteral(String),
-    /// function_name, type, got
-    FunctionWrongNumberOfArguments(String, EFunctionType, usize),
+    /// function_name, type, got, call source (when known) so tooling can point at the wrong call
+    FunctionWrongNumberOfArguments(String, EFunctionType, usize, Option<String>),
 
     /// Expected format description
     WrongFormat(String),
@@ -259,51 +259,29 @@ impl Display for ParseErrorEnum {
             MissingLiteral(literal) => {
                 write!(f, "{}", prefix_parse(&format!("Missing '{}'", literal)))
             }
-            FunctionWrongNumberOfArguments(function_name, function_type, existing) => {
-                if existing == &0 {
-                    return write!(f, "{}", prefix_parse(&format!("Function '{}' got no arguments", function_name)));
-                }
-                match function_type {
+            FunctionWrongNumberOfArguments(function_name, function_type, existing, call) => {
+                let message = match function_type {
+                    _ if existing == &0 => format!("Function '{}' got no arguments", function_name),
+                    EFunctionType::Unary if PREDICATE_FORM_FUNCTIONS.contains(&function_name.as_str()) => {
+                        format!("{} accepts {}, got {}", function_name, describe_arity(1, 2), existing)
+                    }
                     EFunctionType::Custom(expected) => {
-                        write!(
-                            f,
-                            "{}",
-                            prefix_parse(&format!(
-                                "Function '{}' expected {} arguments, but got {}",
-                                function_name, expected, existing
-                            ))
-                        )
+                        format!("Function '{}' expected {} arguments, but got {}", function_name, expected, existing)
                     }
                     EFunctionType::Binary => {
-                        write!(
-                            f,
-                            "{}",
-                            prefix_parse(&format!(
-                                "Binary function '{}' expected 2 arguments, but got {}",
-                                function_name, existing
-                            ))
-                        )
+                        format!("Binary function '{}' expected 2 arguments, but got {}", function_name, existing)
                     }
                     EFunctionType::Multi => {
-                        write!(
-                            f,
-                            "{}",
-                            prefix_parse(&format!(
-                                "Function '{}' expected 1 or more arguments, but got {}",
-                                function_name, existing
-                            ))
-                        )
+                        format!("Function '{}' expected 1 or more arguments, but got {}", function_name, existing)
                     }
                     EFunctionType::Unary => {
-                        write!(
-                            f,
-                            "{}",
-                            prefix_parse(&format!(
-                                "Function '{}' expected 1 argument, but got {}",
-                                function_name, existing
-                            ))
-                        )
+                        format!("Function '{}' expected 1 argument, but got {}", function_name, existing)
                     }
+                };
+
+                match call {
+                    Some(call) => write!(f, "{}", prefix_parse(&format!("{} in '{}'", message, call))),
+                    None => write!(f, "{}", prefix_parse(&message)),
                 }
             }
         }
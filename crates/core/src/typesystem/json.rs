@@ -0,0 +1,86 @@
+use crate::ast::context::context_object_builder::ContextObjectBuilder;
+use crate::ast::token::ExpressionEnum;
+use crate::link::linker;
+use crate::runtime::execution_context::ExecutionContext;
+use crate::typesystem::errors::RuntimeError;
+use crate::typesystem::types::number::NumberEnum;
+use crate::typesystem::types::string::StringEnum;
+use crate::typesystem::types::{Float, SpecialValueEnum, TypedValue};
+use crate::typesystem::values::{ArrayValue, ValueEnum};
+use serde_json::{Map, Number, Value};
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// Converts JSON (e.g. an HTTP request body) to a value without going through the WASM portable layer.
+/// Strings are never reinterpreted, so a date string stays a string.
+impl TryFrom<Value> for ValueEnum {
+    type Error = RuntimeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Err(RuntimeError::eval_error("JSON null values are not supported")),
+            Value::Bool(boolean) => Ok(ValueEnum::BooleanValue(boolean)),
+            Value::Number(number) => json_number_to_value(&number),
+            Value::String(string) => Ok(ValueEnum::StringValue(StringEnum::from(string))),
+            Value::Array(items) => json_array_to_value(items),
+            Value::Object(entries) => json_object_to_value(entries),
+        }
+    }
+}
+
+fn json_number_to_value(number: &Number) -> Result<ValueEnum, RuntimeError> {
+    if let Some(integer) = number.as_i64() {
+        return Ok(ValueEnum::NumberValue(NumberEnum::Int(integer)));
+    }
+
+    // Parsing the textual form keeps `0.1` exact instead of taking the nearest binary float
+    let text = number.to_string();
+    Float::from_str(&text)
+        .or_else(|_| Float::from_scientific(&text))
+        .map(|real| ValueEnum::NumberValue(NumberEnum::Real(real)))
+        .map_err(|_| RuntimeError::eval_error(format!("JSON number {} is out of range", text)))
+}
+
+/// Null items become Missing of the type the other items have
+fn json_array_to_value(items: Vec<Value>) -> Result<ValueEnum, RuntimeError> {
+    let items = items
+        .into_iter()
+        .map(|item| match item {
+            Value::Null => Ok(None),
+            item => ValueEnum::try_from(item).map(Some),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let values = if items.iter().any(Option::is_none) {
+        let item_type = items
+            .iter()
+            .flatten()
+            .next()
+            .map(|value| value.get_type())
+            .ok_or_else(|| RuntimeError::eval_error("JSON list of only nulls has no item type"))?;
+        let missing = ValueEnum::special_for_type(&item_type, SpecialValueEnum::missing_for(None))
+            .ok_or_else(|| RuntimeError::eval_error(format!("JSON null cannot be an item of a {} list", item_type)))?;
+        items.into_iter().map(|item| item.unwrap_or_else(|| missing.clone())).collect()
+    } else {
+        items.into_iter().flatten().collect()
+    };
+
+    Ok(ValueEnum::Array(ArrayValue::from_values(values)?))
+}
+
+fn json_object_to_value(entries: Map<String, Value>) -> Result<ValueEnum, RuntimeError> {
+    let mut builder = ContextObjectBuilder::new();
+
+    for (key, value) in entries {
+        let value = ValueEnum::try_from(value)?;
+        builder
+            .add_expression(key.as_str(), ExpressionEnum::from(value))
+            .map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+    }
+
+    let static_context = builder.build();
+    linker::link_parts(Rc::clone(&static_context)).map_err(|err| RuntimeError::eval_error(err.to_string()))?;
+    let exec_ctx = ExecutionContext::create_isolated_context(static_context);
+    ExecutionContext::eval_all_fields(&exec_ctx)?;
+    Ok(ValueEnum::Reference(exec_ctx))
+}
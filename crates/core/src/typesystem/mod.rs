@@ -1,3 +1,6 @@
 pub mod errors;
 pub mod types;
 pub mod values;
+
+#[cfg(feature = "serde")]
+pub mod json;
@@ -101,12 +101,14 @@ impl Display for ValueType {
 
 // 1 - Not Applicable -> value is marked as optional and is not necessary. Functions will ignore it if possible.
 // 2 - Missing -> value is mandatory, but not present. Functions will not be applied for this value and result will be Missing
+// 3 - Omitted -> `if` without `else` did not produce a value. Optional object fields with this value are dropped from output
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub enum SpecialValueEnum {
     Missing(String),
     NotApplicable(String),
     NotFound(String),
+    Omitted,
 }
 
 impl SpecialValueEnum {
@@ -150,6 +152,7 @@ impl Display for SpecialValueEnum {
             SpecialValueEnum::Missing(field) => write!(f, "Missing('{}')", field),
             SpecialValueEnum::NotApplicable(field) => write!(f, "NotApplicable('{}')", field),
             SpecialValueEnum::NotFound(field) => write!(f, "NotFound('{}')", field),
+            SpecialValueEnum::Omitted => write!(f, "Omitted"),
         }
     }
 }
@@ -173,7 +176,7 @@ pub mod number {
 
     #[allow(non_snake_case)]
     #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
-    #[derive(PartialEq, Clone)]
+    #[derive(PartialEq, Eq, Hash, Clone)]
     pub enum NumberEnum {
         Real(Float),
         Int(Integer),
@@ -356,7 +359,7 @@ pub mod string {
 
     #[allow(non_snake_case)]
     #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
-    #[derive(PartialEq, Clone)]
+    #[derive(PartialEq, Eq, Hash, Clone)]
     pub enum StringEnum {
         String(String),
         Char(char),
@@ -1,14 +1,20 @@
 use crate::ast::context::context_object::ContextObject;
 use crate::ast::context::context_object_type::EObjectContent;
+use crate::ast::functions::function_list::merge_item_type;
+use crate::ast::sequence::merge_collection_types;
 use crate::typesystem::errors::RuntimeError;
 use crate::typesystem::types::number::NumberEnum;
 use crate::typesystem::types::string::StringEnum;
 use crate::typesystem::types::ValueType::{DurationType, PeriodType};
 use crate::typesystem::types::{Float, Integer, SpecialValueEnum, TypedValue, ValueType};
+use crate::utils::json_string;
+use rust_decimal::prelude::ToPrimitive;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 use std::rc::Rc;
 use std::string::String;
@@ -18,7 +24,7 @@ use crate::runtime::execution_context::ExecutionContext;
 use crate::typesystem::values::ValueEnum::{Array, BooleanValue, NumberValue, RangeValue, Reference, StringValue};
 
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub enum ValueOrSv<OkValue, SpecialValue> {
     Value(OkValue),
     Sv(SpecialValue),
@@ -47,7 +53,8 @@ pub enum ValueEnum {
     /// All context is still immutable, but for performance reasons, calculations will not be recalculated.
     Reference(Rc<RefCell<ExecutionContext>>),
 
-    // @Todo: inclusive or exclusive range
+    /// Range `1..5` includes both bounds in the surface syntax and in edge-js output `{start: 1, end: 5}`,
+    /// but is stored as exclusive-end `Range` `1..6`, so it can be iterated directly.
     // @Todo: infinity or static
     // @Todo: range is not a value, it one of filter methods
     RangeValue(Range<Integer>),
@@ -70,9 +77,62 @@ pub enum ArrayValue {
     // @Todo: support array in array - currently not supported
 }
 
+impl Eq for ArrayValue {}
+
+/// Hashes the items only, equal arrays always have equal items. Objects are hashed shallowly.
+impl Hash for ArrayValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            ArrayValue::EmptyUntyped => state.write_usize(0),
+            ArrayValue::PrimitivesArray { values, .. } => values.hash(state),
+            ArrayValue::ObjectsArray { values, .. } => {
+                state.write_usize(values.len());
+                for ctx in values {
+                    hash_object_shallow(ctx, state);
+                }
+            }
+        }
+    }
+}
+
 type ObjectArrayParts = (Vec<Rc<RefCell<ExecutionContext>>>, Rc<RefCell<ContextObject>>);
 
 impl ArrayValue {
+    /// Builds a list with the item type inferred from the values.
+    /// Object references make an objects list typed by all their fields combined, other values must all be of the same type.
+    pub fn from_values(values: Vec<ValueEnum>) -> Result<ArrayValue, RuntimeError> {
+        if let Some(Reference(_)) = values.first() {
+            let mut object_type: Option<ValueType> = None;
+            let mut objects = Vec::with_capacity(values.len());
+            for value in values {
+                let Reference(object) = value else {
+                    return Err(RuntimeError::type_not_supported(value.get_type()));
+                };
+                let item_type = ValueType::ObjectType(Rc::clone(&object.borrow().object));
+                object_type = Some(match object_type {
+                    None => item_type,
+                    Some(existing) => merge_collection_types(existing, item_type)
+                        .map_err(|err| RuntimeError::eval_error(err.to_string()))?,
+                });
+                objects.push(object);
+            }
+            let Some(ValueType::ObjectType(object_type)) = object_type else {
+                unreachable!("objects list always has an object type");
+            };
+            return Ok(ArrayValue::ObjectsArray { values: objects, object_type });
+        }
+
+        let mut item_type = ValueType::UndefinedType;
+        for value in &values {
+            merge_item_type(&mut item_type, value.get_type())?;
+        }
+
+        match item_type {
+            ValueType::UndefinedType => Ok(ArrayValue::EmptyUntyped),
+            item_type => Ok(ArrayValue::PrimitivesArray { values, item_type }),
+        }
+    }
+
     pub fn is_empty_untyped(&self) -> bool {
         matches!(self, ArrayValue::EmptyUntyped)
     }
@@ -216,14 +276,15 @@ fn format_datetime_value(value: &OffsetDateTime) -> String {
     // For non-UTC offsets, we append the offset.
     if !value.offset().is_utc() {
         let (off_h, off_m, _) = value.offset().as_hms();
-        base.push_str(&format!("{:+03}:{:02}", off_h, off_m.abs()));
+        let sign = if value.offset().is_negative() { '-' } else { '+' };
+        base.push_str(&format!("{}{:02}:{:02}", sign, off_h.abs(), off_m.abs()));
     }
 
     base
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct DurationValue {
     seconds: u64,
     is_negative: bool,
@@ -394,7 +455,7 @@ impl PartialOrd for DurationValue {
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PeriodValue {
     months: u32,
     days: u32,
@@ -568,6 +629,195 @@ impl TypedValue for ValueEnum {
     }
 }
 
+impl FromIterator<ValueEnum> for Result<ArrayValue, RuntimeError> {
+    fn from_iter<I: IntoIterator<Item = ValueEnum>>(iter: I) -> Self {
+        ArrayValue::from_values(iter.into_iter().collect())
+    }
+}
+
+impl Eq for ValueEnum {}
+
+/// Canonical hash consistent with `==`. `Int(1)` and `Real(1.0)` are different values and are not
+/// normalized, while reals are hashed by their normalized decimal, so `1.0` and `1.00` hash equally.
+/// Datetimes hash by the instant, as they compare. Objects hash shallowly by their field names.
+impl Hash for ValueEnum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            NumberValue(number) => number.hash(state),
+            BooleanValue(flag) => flag.hash(state),
+            StringValue(text) => text.hash(state),
+            ValueEnum::DateValue(date) => date.hash(state),
+            ValueEnum::TimeValue(time) => time.hash(state),
+            ValueEnum::DateTimeValue(datetime) => datetime.hash(state),
+            ValueEnum::DurationValue(duration) => duration.hash(state),
+            ValueEnum::PeriodValue(period) => period.hash(state),
+            Array(array) => array.hash(state),
+            Reference(ctx) => hash_object_shallow(ctx, state),
+            RangeValue(range) => range.hash(state),
+            // types are rarely used as keys, so all of them share the discriminant hash
+            ValueEnum::TypeValue(_) => {}
+        }
+    }
+}
+
+fn hash_object_shallow<H: Hasher>(ctx: &Rc<RefCell<ExecutionContext>>, state: &mut H) {
+    let ctx = ctx.borrow();
+    ctx.node.get_assigned_to_field().hash(state);
+    ctx.object.borrow().get_field_names().hash(state);
+}
+
+impl ValueEnum {
+    /// Omitted value of the given type, `None` if the type cannot hold special values
+    pub fn omitted_for_type(value_type: &ValueType) -> Option<ValueEnum> {
+        Self::special_for_type(value_type, SpecialValueEnum::Omitted)
+    }
+
+    /// The special value held as the given type, `None` if the type cannot hold special values
+    pub fn special_for_type(value_type: &ValueType, sv: SpecialValueEnum) -> Option<ValueEnum> {
+        match value_type {
+            ValueType::NumberType => Some(NumberValue(NumberEnum::SV(sv))),
+            // booleans have no special values, so the same as for Missing, string is used
+            ValueType::StringType | ValueType::BooleanType => Some(StringValue(StringEnum::SV(sv))),
+            ValueType::DateType => Some(ValueEnum::DateValue(ValueOrSv::Sv(sv))),
+            ValueType::TimeType => Some(ValueEnum::TimeValue(ValueOrSv::Sv(sv))),
+            ValueType::DateTimeType => Some(ValueEnum::DateTimeValue(ValueOrSv::Sv(sv))),
+            ValueType::DurationType => Some(ValueEnum::DurationValue(ValueOrSv::Sv(sv))),
+            ValueType::PeriodType => Some(ValueEnum::PeriodValue(ValueOrSv::Sv(sv))),
+            _ => None,
+        }
+    }
+
+    /// Copy that shares no evaluation state with the original, see [`ExecutionContext::deep_clone`]
+    pub fn deep_clone(&self) -> ValueEnum {
+        match self {
+            Reference(ctx) => Reference(ExecutionContext::deep_clone(ctx)),
+            Array(ArrayValue::ObjectsArray { values, object_type }) => Array(ArrayValue::ObjectsArray {
+                values: values.iter().map(ExecutionContext::deep_clone).collect(),
+                object_type: Rc::clone(object_type),
+            }),
+            Array(ArrayValue::PrimitivesArray { values, item_type }) => Array(ArrayValue::PrimitivesArray {
+                values: values.iter().map(ValueEnum::deep_clone).collect(),
+                item_type: item_type.clone(),
+            }),
+            other => other.clone(),
+        }
+    }
+
+    /// Integer value, reals are accepted only if they have no fraction. Special values give `None`
+    pub fn as_i64(&self) -> Option<Integer> {
+        match self {
+            NumberValue(NumberEnum::Int(value)) => Some(*value),
+            NumberValue(NumberEnum::Real(value)) if value.fract().is_zero() => value.to_i64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            NumberValue(NumberEnum::Int(value)) => Some(*value as f64),
+            NumberValue(NumberEnum::Real(value)) => value.to_f64(),
+            _ => None,
+        }
+    }
+
+    /// Text of a string or a single character value
+    pub fn as_str(&self) -> Option<Cow<'_, str>> {
+        match self {
+            StringValue(StringEnum::String(value)) => Some(Cow::Borrowed(value.as_str())),
+            StringValue(StringEnum::Char(value)) => Some(Cow::Owned(value.to_string())),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            BooleanValue(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_date(&self) -> Option<Date> {
+        match self {
+            ValueEnum::DateValue(ValueOrSv::Value(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Portable JSON of the value: special values and temporal values are strings, objects omit internal names
+    pub fn to_json(&self) -> Result<String, RuntimeError> {
+        self.to_json_with(JsonSpecialValues::AsText)
+    }
+
+    /// Portable JSON of the value with the given rendering of special values
+    pub fn to_json_with(&self, special_values: JsonSpecialValues) -> Result<String, RuntimeError> {
+        if let Some(sv) = self.special_value() {
+            return Ok(special_values.render(sv));
+        }
+
+        let json = match self {
+            BooleanValue(flag) => flag.to_string(),
+            NumberValue(number) => number.to_string(),
+            StringValue(StringEnum::String(text)) => json_string(text),
+            StringValue(StringEnum::Char(symbol)) => json_string(&symbol.to_string()),
+            Array(ArrayValue::EmptyUntyped) => "[]".to_string(),
+            Array(ArrayValue::PrimitivesArray { values, .. }) => {
+                let items: Result<Vec<String>, RuntimeError> =
+                    values.iter().map(|value| value.to_json_with(special_values)).collect();
+                format!("[{}]", items?.join(","))
+            }
+            Array(ArrayValue::ObjectsArray { values, .. }) => {
+                let items: Result<Vec<String>, RuntimeError> =
+                    values.iter().map(|ctx| ExecutionContext::to_json_with(ctx, special_values)).collect();
+                format!("[{}]", items?.join(","))
+            }
+            Reference(ctx) => ExecutionContext::to_json_with(ctx, special_values)?,
+            RangeValue(range) => format!("{{\"start\":{},\"endExclusive\":{}}}", range.start, range.end),
+            other => json_string(&other.to_string()),
+        };
+
+        Ok(json)
+    }
+
+    /// Special value carried by a primitive value, if any
+    pub fn special_value(&self) -> Option<&SpecialValueEnum> {
+        match self {
+            NumberValue(NumberEnum::SV(sv)) | StringValue(StringEnum::SV(sv)) => Some(sv),
+            ValueEnum::DateValue(ValueOrSv::Sv(sv))
+            | ValueEnum::TimeValue(ValueOrSv::Sv(sv))
+            | ValueEnum::DateTimeValue(ValueOrSv::Sv(sv))
+            | ValueEnum::DurationValue(ValueOrSv::Sv(sv))
+            | ValueEnum::PeriodValue(ValueOrSv::Sv(sv)) => Some(sv),
+            _ => None,
+        }
+    }
+
+    pub fn is_omitted(&self) -> bool {
+        matches!(self.special_value(), Some(SpecialValueEnum::Omitted))
+    }
+}
+
+/// How special values are written to JSON.
+/// `to_code` output is not affected, it always keeps the code form such as `Missing('field')`.
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum JsonSpecialValues {
+    /// Special value is written as its code form string, e.g. `"Missing('field')"`
+    AsText,
+    /// Every special kind (`Missing`, `NotApplicable`, `NotFound` and `Omitted`) is written as `null`,
+    /// as standard JSON consumers expect. Omitted optional fields are still left out of objects.
+    AsNull,
+}
+
+impl JsonSpecialValues {
+    fn render(self, sv: &SpecialValueEnum) -> String {
+        match self {
+            JsonSpecialValues::AsText => json_string(&sv.to_string()),
+            JsonSpecialValues::AsNull => "null".to_string(),
+        }
+    }
+}
+
 impl Display for ValueEnum {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -600,7 +850,7 @@ impl Display for ValueEnum {
                     f.write_str("false")
                 }
             }
-            RangeValue(range) => write!(f, "{}..{}", range.start, range.end - 1),
+            RangeValue(range) => write!(f, "{}..{}", range.start, range.end.saturating_sub(1)),
             ValueEnum::DateValue(date) => match date {
                 ValueOrSv::Value(date) => write!(f, "{}", date),
                 ValueOrSv::Sv(sv) => write!(f, "{}", sv),
@@ -675,3 +925,118 @@ impl From<PeriodValue> for ValueEnum {
         ValueEnum::PeriodValue(ValueOrSv::Value(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typesystem::types::SpecialValueEnum::Missing;
+
+    fn missing_number() -> ValueEnum {
+        NumberValue(NumberEnum::SV(Missing("x".to_string())))
+    }
+
+    #[test]
+    fn from_values_infers_item_type() {
+        let array = ArrayValue::from_values(vec![
+            NumberValue(NumberEnum::Int(1)),
+            NumberValue(NumberEnum::Real(Float::new(15, 1))),
+        ])
+        .unwrap();
+        assert_eq!(array.list_type(), ValueType::list_of(ValueType::NumberType));
+        assert_eq!(Array(array).to_string(), "[1, 1.5]");
+
+        let collected: Result<ArrayValue, RuntimeError> =
+            ["a", "b"].into_iter().map(|s| StringValue(StringEnum::String(s.to_string()))).collect();
+        assert_eq!(collected.unwrap().item_type(), Some(ValueType::StringType));
+
+        assert!(ArrayValue::from_values(Vec::new()).unwrap().is_empty_untyped());
+        assert!(ArrayValue::from_values(vec![NumberValue(NumberEnum::Int(1)), BooleanValue(true)]).is_err());
+    }
+
+    #[test]
+    fn as_i64_accepts_integral_numbers() {
+        assert_eq!(NumberValue(NumberEnum::Int(7)).as_i64(), Some(7));
+        assert_eq!(NumberValue(NumberEnum::Real(Float::new(70, 1))).as_i64(), Some(7));
+        assert_eq!(NumberValue(NumberEnum::Real(Float::new(75, 1))).as_i64(), None);
+        assert_eq!(missing_number().as_i64(), None);
+        assert_eq!(StringValue(StringEnum::from("7")).as_i64(), None);
+    }
+
+    #[test]
+    fn as_f64_accepts_all_numbers() {
+        assert_eq!(NumberValue(NumberEnum::Int(7)).as_f64(), Some(7.0));
+        assert_eq!(NumberValue(NumberEnum::Real(Float::new(75, 1))).as_f64(), Some(7.5));
+        assert_eq!(missing_number().as_f64(), None);
+        assert_eq!(BooleanValue(true).as_f64(), None);
+    }
+
+    #[test]
+    fn as_str_accepts_strings_and_chars() {
+        assert_eq!(StringValue(StringEnum::from("text")).as_str().as_deref(), Some("text"));
+        assert_eq!(StringValue(StringEnum::Char('c')).as_str().as_deref(), Some("c"));
+        assert_eq!(StringValue(StringEnum::SV(Missing("x".to_string()))).as_str(), None);
+        assert_eq!(NumberValue(NumberEnum::Int(1)).as_str(), None);
+    }
+
+    #[test]
+    fn as_bool_accepts_booleans() {
+        assert_eq!(BooleanValue(true).as_bool(), Some(true));
+        assert_eq!(BooleanValue(false).as_bool(), Some(false));
+        assert_eq!(StringValue(StringEnum::from("true")).as_bool(), None);
+    }
+
+    #[test]
+    fn as_date_accepts_dates() {
+        let date = Date::from_calendar_date(2024, Month::January, 31).unwrap();
+        assert_eq!(ValueEnum::DateValue(ValueOrSv::Value(date)).as_date(), Some(date));
+        assert_eq!(ValueEnum::DateValue(ValueOrSv::Sv(Missing("d".to_string()))).as_date(), None);
+        assert_eq!(StringValue(StringEnum::from("2024-01-31")).as_date(), None);
+    }
+
+    fn hash_of(value: &ValueEnum) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn equal_values_hash_equally() {
+        let pairs = [
+            (NumberValue(NumberEnum::Int(7)), NumberValue(NumberEnum::Int(7))),
+            (NumberValue(NumberEnum::Real(Float::new(10, 1))), NumberValue(NumberEnum::Real(Float::new(100, 2)))),
+            (StringValue(StringEnum::from("a")), StringValue(StringEnum::from("a"))),
+            (missing_number(), missing_number()),
+            (
+                ValueEnum::DateTimeValue(ValueOrSv::Value(time::macros::datetime!(2024-01-01 12:00 +02:00))),
+                ValueEnum::DateTimeValue(ValueOrSv::Value(time::macros::datetime!(2024-01-01 10:00 UTC))),
+            ),
+            (
+                ValueEnum::DurationValue(ValueOrSv::Value(DurationValue::new(90, false))),
+                ValueEnum::DurationValue(ValueOrSv::Value(DurationValue::new(90, false))),
+            ),
+            (
+                Array(ArrayValue::from_values(vec![BooleanValue(true)]).unwrap()),
+                Array(ArrayValue::from_values(vec![BooleanValue(true)]).unwrap()),
+            ),
+        ];
+        for (left, right) in pairs {
+            assert_eq!(left, right);
+            assert_eq!(hash_of(&left), hash_of(&right), "{} and {}", left, right);
+        }
+
+        // integers and reals are different values
+        assert_ne!(NumberValue(NumberEnum::Int(1)), NumberValue(NumberEnum::Real(Float::ONE)));
+        assert_ne!(hash_of(&NumberValue(NumberEnum::Int(1))), hash_of(&NumberValue(NumberEnum::Real(Float::ONE))));
+    }
+
+    #[test]
+    fn distinct_values_rarely_collide() {
+        let values = (0..1000)
+            .map(|i| NumberValue(NumberEnum::Int(i)))
+            .chain((0..1000).map(|i| NumberValue(NumberEnum::Real(Float::new(i, 2)))))
+            .chain((0..1000).map(|i| StringValue(StringEnum::from(format!("key-{}", i).as_str()))))
+            .chain((0..1000).map(|i| ValueEnum::PeriodValue(ValueOrSv::Value(PeriodValue::new(i, 0, false)))));
+        let hashes: std::collections::HashSet<u64> = values.map(|value| hash_of(&value)).collect();
+        assert_eq!(hashes.len(), 4000);
+    }
+}
@@ -40,6 +40,25 @@ pub fn context_unwrap(input: String) -> String {
     }
 }
 
+/// Quotes and escapes text as a JSON string
+pub fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for symbol in text.chars() {
+        match symbol {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            control if control.is_control() => quoted.push_str(&format!("\\u{:04x}", control as u32)),
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 #[allow(dead_code)]
 pub fn capitalize(s: String) -> String {
     let mut c = s.chars();
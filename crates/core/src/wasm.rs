@@ -9,6 +9,11 @@ extern "C" {
     #[wasm_bindgen(constructor, js_class = "RegExp", catch)]
     fn new(pattern: &str, flags: &str) -> Result<HostRegExp, JsValue>;
 
+    #[wasm_bindgen(method, catch)]
+    fn test(this: &HostRegExp, s: &str) -> Result<bool, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn exec(this: &HostRegExp, s: &str) -> Result<JsValue, JsValue>;
+
     #[wasm_bindgen(js_name = Object)]
     pub type HostObject;
     #[wasm_bindgen(method, structural, catch)]
@@ -56,6 +61,21 @@ pub(crate) fn regex_split_js(s: &str, pattern: &str, flags: Option<&str>) -> Res
     Ok(parts)
 }
 
+pub(crate) fn regex_test_js(s: &str, pattern: &str) -> Result<bool, String> {
+    let re = HostRegExp::new(pattern, "").map_err(|e| format!("{:?}", e))?;
+    re.test(s).map_err(|e| format!("{:?}", e))
+}
+
+pub(crate) fn regex_extract_js(s: &str, pattern: &str, group: u32) -> Result<Option<String>, String> {
+    let re = HostRegExp::new(pattern, "").map_err(|e| format!("{:?}", e))?;
+    let found = re.exec(s).map_err(|e| format!("{:?}", e))?;
+    if found.is_null() {
+        return Ok(None);
+    }
+    let group_val = js_sys::Reflect::get(&found, &JsValue::from_f64(group as f64)).map_err(|e| format!("{:?}", e))?;
+    Ok(group_val.as_string())
+}
+
 pub(crate) fn to_base64_js(s: &str) -> Result<String, String> {
     if let Ok(out) = btoa(s) {
         return Ok(out);
@@ -83,11 +83,15 @@ fn resolve_in_scopes(name: &str, scope: Option<&str>, fallback_scope: Option<&st
     format!("({})", parts.join(" ?? "))
 }
 
+/// Every special value becomes `NaN`, whatever its type, so JS arithmetic over it stays `NaN` and comparisons with it
+/// are false, the same way the runtime propagates special values
+const SPECIAL_VALUE_JS: &str = "NaN";
+
 fn render_number(number: &NumberEnum) -> String {
     match number {
         NumberEnum::Int(int) => int.to_string(),
         NumberEnum::Real(real) => real.to_string(),
-        NumberEnum::SV(sv) => quote_str(&sv.to_string()),
+        NumberEnum::SV(_) => SPECIAL_VALUE_JS.to_string(),
     }
 }
 
@@ -95,11 +99,14 @@ fn render_string_enum(value: &StringEnum) -> String {
     match value {
         StringEnum::String(value) => quote_str(value),
         StringEnum::Char(ch) => quote_str(&ch.to_string()),
-        StringEnum::SV(sv) => quote_str(&sv.to_string()),
+        StringEnum::SV(_) => SPECIAL_VALUE_JS.to_string(),
     }
 }
 
 fn render_value(value: &ValueEnum, scope: Option<&str>, fallback_scope: Option<&str>) -> String {
+    if value.special_value().is_some() {
+        return SPECIAL_VALUE_JS.to_string();
+    }
     match value {
         ValueEnum::NumberValue(num) => render_number(num),
         ValueEnum::BooleanValue(flag) => flag.to_string(),
@@ -173,8 +180,13 @@ fn render_math_operator(op: &MathOperator, scope: Option<&str>, fallback_scope:
         MathOperatorEnum::Power => "**",
         MathOperatorEnum::Modulus => "%",
     };
-    let left = render_expression(&op.data.left, scope, fallback_scope);
-    let right = render_expression(&op.data.right, scope, fallback_scope);
+    let precedence = match op.data.operator {
+        MathOperatorEnum::Addition | MathOperatorEnum::Subtraction => 11,
+        MathOperatorEnum::Power => 13,
+        _ => 12,
+    };
+    let left = render_operand(&op.data.left, precedence, false, scope, fallback_scope);
+    let right = render_operand(&op.data.right, precedence, true, scope, fallback_scope);
     Some(format!("({} {} {})", left, symbol, right))
 }
 
@@ -187,9 +199,10 @@ fn render_comparator(op: &ComparatorOperator, scope: Option<&str>, fallback_scop
         ComparatorEnum::LessEquals => "<=",
         ComparatorEnum::GreaterEquals => ">=",
     };
-    let left = render_expression(&op.data.left, scope, fallback_scope);
-    let right = render_expression(&op.data.right, scope, fallback_scope);
-    Some(format!("({} {} {})", left, symbol, right))
+    let precedence = comparator_precedence(&op.data.operator);
+    let left = render_operand(&op.data.left, precedence, false, scope, fallback_scope);
+    let right = render_operand(&op.data.right, precedence, true, scope, fallback_scope);
+    Some(format!("{} {} {}", left, symbol, right))
 }
 
 fn render_logical(op: &LogicalOperator, scope: Option<&str>, fallback_scope: Option<&str>) -> Option<String> {
@@ -199,14 +212,66 @@ fn render_logical(op: &LogicalOperator, scope: Option<&str>, fallback_scope: Opt
         LogicalOperatorEnum::Xor => "^",
         LogicalOperatorEnum::Not => "!",
     };
+    let precedence = logical_precedence(&op.data.operator);
 
-    let left = render_expression(&op.data.left, scope, fallback_scope);
-    let right = render_expression(&op.data.right, scope, fallback_scope);
+    // `not` keeps its operand on the left side, right side is a placeholder
+    let left = render_operand(&op.data.left, precedence, false, scope, fallback_scope);
 
     if matches!(op.data.operator, LogicalOperatorEnum::Not) {
-        Some(format!("(!{})", right))
+        Some(format!("!{}", left))
     } else {
-        Some(format!("({} {} {})", left, symbol, right))
+        let right = render_operand(&op.data.right, precedence, true, scope, fallback_scope);
+        Some(format!("{} {} {}", left, symbol, right))
+    }
+}
+
+/// JavaScript precedence of the rendered operator
+fn comparator_precedence(operator: &ComparatorEnum) -> u8 {
+    match operator {
+        ComparatorEnum::Equals | ComparatorEnum::NotEquals => 8,
+        _ => 9,
+    }
+}
+
+/// JavaScript precedence of the rendered operator
+fn logical_precedence(operator: &LogicalOperatorEnum) -> u8 {
+    match operator {
+        LogicalOperatorEnum::Not => 14,
+        LogicalOperatorEnum::Xor => 6,
+        LogicalOperatorEnum::And => 4,
+        LogicalOperatorEnum::Or => 3,
+    }
+}
+
+/// Renders an operator operand, wrapping comparator or logical operand only when JavaScript would group it differently.
+/// Math operators and other expressions are rendered with their own parentheses and never need extra ones.
+fn render_operand(
+    operand: &ExpressionEnum,
+    precedence: u8,
+    is_right: bool,
+    scope: Option<&str>,
+    fallback_scope: Option<&str>,
+) -> String {
+    let operand_precedence = match operand {
+        ExpressionEnum::Operator(op) => {
+            let any_ref = op.as_ref() as &dyn Any;
+            if let Some(comparator) = any_ref.downcast_ref::<ComparatorOperator>() {
+                Some(comparator_precedence(&comparator.data.operator))
+            } else {
+                any_ref.downcast_ref::<LogicalOperator>().map(|logical| logical_precedence(&logical.data.operator))
+            }
+        }
+        _ => None,
+    };
+
+    let rendered = render_expression(operand, scope, fallback_scope);
+    match operand_precedence {
+        Some(operand_precedence)
+            if operand_precedence < precedence || (is_right && operand_precedence == precedence) =>
+        {
+            format!("({})", rendered)
+        }
+        _ => rendered,
     }
 }
 
@@ -248,7 +313,11 @@ fn render_function_call(
     if let Some(ifelse) = any_ref.downcast_ref::<IfThenElseFunction>() {
         let condition = render_expression(&ifelse.condition, scope, fallback_scope);
         let then_js = render_expression(&ifelse.then_expression, scope, fallback_scope);
-        let else_js = render_expression(&ifelse.else_expression, scope, fallback_scope);
+        // `if` without `else` leaves the value out
+        let else_js = match &ifelse.else_expression {
+            Some(else_expression) => render_expression(else_expression, scope, fallback_scope),
+            None => "undefined".to_string(),
+        };
         return Some(format!("({} ? {} : {})", condition, then_js, else_js));
     }
 
@@ -321,7 +390,7 @@ fn render_filter(filter: &ExpressionFilter, scope: Option<&str>, fallback_scope:
             "    const compute = (it, index) => ({});\n",
             "    const probe = compute(source[0], 0);\n",
             "    if (typeof probe === \"number\") {{\n",
-            "        const idx = Math.trunc(probe);\n",
+            "        const idx = Math.trunc(probe) < 0 ? source.length + Math.trunc(probe) : Math.trunc(probe);\n",
             "        return idx >= 0 && idx < source.length ? source[idx] : undefined;\n",
             "    }}\n",
             "    return source.filter((item, index) => !!compute(item, index));\n",
@@ -571,7 +640,8 @@ mod tests {
     use super::*;
     use edge_rules::runtime::edge_rules::EdgeRulesModel;
     use edge_rules::typesystem::types::number::NumberEnum;
-    use edge_rules::typesystem::values::ValueEnum;
+    use edge_rules::typesystem::types::SpecialValueEnum;
+    use edge_rules::typesystem::values::{ValueEnum, ValueOrSv};
 
     #[test]
     fn renders_primitives() {
@@ -579,6 +649,45 @@ mod tests {
         assert_eq!(ValueEnum::StringValue(StringEnum::String("hi\"there".into())).to_js(), "\"hi\\\"there\"");
     }
 
+    #[test]
+    fn renders_missing_number_as_nan() {
+        let missing = ValueEnum::NumberValue(NumberEnum::SV(SpecialValueEnum::missing_for(Some("price"))));
+        assert_eq!(missing.to_js(), "NaN");
+
+        let mut model = EdgeRulesModel::new();
+        model.append_source("{ price: 10; total: price + 1 }").expect("parse model");
+        model.set_expression("price", ExpressionEnum::Value(missing)).expect("set price");
+        let js = to_js_model(&mut model).expect("to js model");
+        assert!(js.contains("ctx[\"price\"] = NaN;"));
+        assert!(js.contains("ctx[\"total\"] = ("));
+    }
+
+    #[test]
+    fn renders_special_values_of_every_type_as_nan() {
+        let sv = || SpecialValueEnum::missing_for(Some("field"));
+        let specials = [
+            ValueEnum::StringValue(StringEnum::SV(sv())),
+            ValueEnum::DateValue(ValueOrSv::Sv(sv())),
+            ValueEnum::TimeValue(ValueOrSv::Sv(sv())),
+            ValueEnum::DateTimeValue(ValueOrSv::Sv(sv())),
+            ValueEnum::DurationValue(ValueOrSv::Sv(sv())),
+            ValueEnum::PeriodValue(ValueOrSv::Sv(sv())),
+        ];
+        for special in specials {
+            assert_eq!(special.to_js(), "NaN", "special value {}", special);
+        }
+    }
+
+    #[test]
+    fn renders_range_value_and_expression_with_inclusive_end() {
+        let expression = EdgeRulesModel::parse_expression("1..5").expect("parse range");
+        let value = ValueEnum::RangeValue(1..6);
+
+        assert_eq!(value.to_string(), "1..5");
+        assert_eq!(expression.to_js(), "({start: 1, end: 5})");
+        assert_eq!(value.to_js(), expression.to_js());
+    }
+
     #[test]
     fn renders_math_expression() {
         let expr = EdgeRulesModel::parse_expression("2 + 3").expect("parse expression");
@@ -586,6 +695,18 @@ mod tests {
         assert!(js.contains("+"));
     }
 
+    #[test]
+    fn renders_logical_and_comparator_with_precedence_parentheses() {
+        let render = |code: &str| EdgeRulesModel::parse_expression(code).expect("parse expression").to_js();
+
+        assert_eq!(render("true and false or true"), "true && false || true");
+        assert_eq!(render("true and (false or true)"), "true && (false || true)");
+        assert_eq!(render("1 < 2 and 2 = 2"), "1 < 2 && 2 === 2");
+        assert_eq!(render("true xor false and true"), "true ^ (false && true)");
+        assert_eq!(render("not (1 > 2)"), "!(1 > 2)");
+        assert_eq!(render("not false"), "!false");
+    }
+
     #[test]
     fn renders_filter_expression() {
         let expr = EdgeRulesModel::parse_expression("[1,2,3][...>1]").expect("parse filter expression");
@@ -594,6 +715,13 @@ mod tests {
         assert!(js.contains("filter("));
     }
 
+    #[test]
+    fn renders_filter_function_as_filter_expression() {
+        let function = EdgeRulesModel::parse_expression("filter([1,2,3], it > 1)").expect("parse filter function");
+        let bracket = EdgeRulesModel::parse_expression("[1,2,3][it > 1]").expect("parse filter expression");
+        assert_eq!(function.to_js(), bracket.to_js());
+    }
+
     #[test]
     fn renders_selection_expression() {
         let expr = EdgeRulesModel::parse_expression("[1,2,3][...>1].length").expect("parse selection");
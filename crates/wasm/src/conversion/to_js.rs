@@ -137,6 +137,9 @@ impl ToJs for Rc<RefCell<ExecutionContext>> {
             let field_val_opt = self.borrow().get(field_name);
             match field_val_opt {
                 Ok(EObjectContent::ConstantValue(value)) => {
+                    if value.is_omitted() && self.borrow().is_optional_field(field_name) {
+                        continue;
+                    }
                     set_prop(&js_object, field_name, &value.to_js()?).map_err(RuntimeError::eval_error)?;
                 }
                 Ok(EObjectContent::ObjectRef(child)) => {
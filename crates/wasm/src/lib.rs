@@ -140,6 +140,14 @@ impl DecisionService {
         }
     }
 
+    #[wasm_bindgen(js_name = "warmUp")]
+    pub fn warm_up(&self) -> bool {
+        match with_decision_service(|svc| svc.warm_up()) {
+            Ok(_) => true,
+            Err(err) => throw_portable_error(err),
+        }
+    }
+
     pub fn get(&self, path: &str) -> JsValue {
         match with_decision_service(|svc| svc.get_entry(path)) {
             Ok(value) => value,
@@ -27,6 +27,10 @@ impl DecisionServiceController {
         Ok(self.service.execute(method, args)?)
     }
 
+    pub fn warm_up(&mut self) -> Result<(), PortableError> {
+        Ok(self.service.warm_up()?)
+    }
+
     pub fn model_snapshot(&mut self) -> Result<JsValue, PortableError> {
         let model = self.service.get_model();
         let snap = {
@@ -141,6 +141,29 @@ impl Display for SchemaViolationType {
     }
 }
 
+/// Why a model path does not lead to an entry
+pub enum PathViolationType {
+    NotFound,
+    TraversesScalar,
+    IndexOutOfRange,
+}
+
+impl PathViolationType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PathViolationType::NotFound => "Path not found",
+            PathViolationType::TraversesScalar => "Path traverses a scalar",
+            PathViolationType::IndexOutOfRange => "Index out of range",
+        }
+    }
+}
+
+impl Display for PathViolationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 pub enum PortableError {
     DecisionServiceError(String),
@@ -150,6 +173,8 @@ pub enum PortableError {
     RuntimeStage(RuntimeError),
     SerializationError(PortableObjectKey, SchemaViolationType),
     SchemaViolation(PortableObjectKey, SchemaViolationType),
+    /// The path segment that could not be followed and the reason
+    PathViolation(String, PathViolationType),
 }
 
 impl PortableError {
@@ -261,6 +286,12 @@ impl PortableError {
                 .add_str("key", key.as_str())
                 .add_str("violation", violation.as_str())
                 .into_js(),
+            PortableError::PathViolation(path, violation) => JsBuilder::new()
+                .add_type("PathViolation")
+                .add_str("path", path)
+                .add_str("violation", violation.as_str())
+                .add_str("message", &format!("{} at '{}'", violation, path))
+                .into_js(),
         }
     }
 }
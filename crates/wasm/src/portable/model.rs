@@ -1,4 +1,4 @@
-use crate::portable::error::{PortableError, PortableObjectKey, SchemaViolationType};
+use crate::portable::error::{PathViolationType, PortableError, PortableObjectKey, SchemaViolationType};
 use crate::utils::{get_prop, is_object, set_prop};
 use edge_rules::ast::context::context_object::ContextObject;
 use edge_rules::ast::context::context_object_builder::ContextObjectBuilder;
@@ -123,7 +123,7 @@ pub fn apply_portable_entry(model: &mut EdgeRulesModel, path: &str, payload: &Js
                 ));
             }
             let definition = parse_function_definition(&function_name, &def_obj)?;
-            apply_function_with_path(model, context_path, definition)?;
+            apply_function_with_path(model, context_path, definition).map_err(|err| path_error(model, path, err))?;
         }
         PortableKind::Invocation(inv_obj) => {
             let (_context_path, name) = split_path(path)?;
@@ -134,7 +134,7 @@ pub fn apply_portable_entry(model: &mut EdgeRulesModel, path: &str, payload: &Js
                 ));
             }
             let spec = parse_invocation_spec(&inv_obj)?;
-            model.set_invocation(path, spec)?;
+            model.set_invocation(path, spec).map_err(|err| path_error(model, path, err.into()))?;
         }
         PortableKind::Type(def_obj) => {
             let (_context_path, name) = split_path(path)?;
@@ -145,14 +145,14 @@ pub fn apply_portable_entry(model: &mut EdgeRulesModel, path: &str, payload: &Js
                 ));
             }
             let body = parse_type_definition(&def_obj)?;
-            model.set_user_type(path, body)?;
+            model.set_user_type(path, body).map_err(|err| path_error(model, path, err.into()))?;
         }
         PortableKind::Context(ctx_obj) => {
             let (context_path, name) = split_path(path)?;
             match parse_path_target(&name)? {
                 PathTarget::Field => {
                     let expr = parse_static_object(&ctx_obj)?;
-                    model.set_expression(path, expr)?;
+                    model.set_expression(path, expr).map_err(|err| path_error(model, path, err.into()))?;
                 }
                 PathTarget::ArrayElement(array_name, index) => {
                     let full_array_path = join_path(context_path, &array_name);
@@ -166,7 +166,7 @@ pub fn apply_portable_entry(model: &mut EdgeRulesModel, path: &str, payload: &Js
             match parse_path_target(&name)? {
                 PathTarget::Field => {
                     let expr = parse_expression_value(&raw)?;
-                    model.set_expression(path, expr)?;
+                    model.set_expression(path, expr).map_err(|err| path_error(model, path, err.into()))?;
                 }
                 PathTarget::ArrayElement(array_name, index) => {
                     let full_array_path = join_path(context_path, &array_name);
@@ -183,27 +183,12 @@ pub fn remove_portable_entry(model: &mut EdgeRulesModel, path: &str) -> Result<(
     let (context_path, name) = split_path(path)?;
     if let PathTarget::ArrayElement(array_name, index) = parse_path_target(&name)? {
         let full_array_path = join_path(context_path, &array_name);
-        let entry = model.get_expression(&full_array_path).map_err(PortableError::from)?;
+        let entry = model.get_expression(&full_array_path).map_err(|err| path_error(model, path, err.into()))?;
         let mut borrowed = entry.borrow_mut();
-
-        match &mut borrowed.expression {
-            ExpressionEnum::Collection(col) => {
-                if index >= col.elements.len() {
-                    return Err(PortableError::from(ContextQueryErrorEnum::WrongFieldPathError(Some(format!(
-                        "Index {} out of bounds",
-                        index
-                    )))));
-                }
-                col.elements.remove(index);
-                return Ok(());
-            }
-            _ => {
-                return Err(PortableError::from(ContextQueryErrorEnum::WrongFieldPathError(Some(format!(
-                    "Field '{}' is not an array",
-                    full_array_path
-                )))));
-            }
-        }
+        let col = collection_at_mut(&mut borrowed.expression, &full_array_path)?;
+        check_index(&full_array_path, index, col.elements.len())?;
+        col.elements.remove(index);
+        return Ok(());
     }
 
     match model.get_user_type(path) {
@@ -212,7 +197,7 @@ pub fn remove_portable_entry(model: &mut EdgeRulesModel, path: &str) -> Result<(
             return Ok(());
         }
         Err(ContextQueryErrorEnum::EntryNotFoundError(_)) => {}
-        Err(err) => return Err(PortableError::from(err)),
+        Err(err) => return Err(path_error(model, path, err.into())),
     }
 
     match model.get_user_function(path) {
@@ -221,7 +206,7 @@ pub fn remove_portable_entry(model: &mut EdgeRulesModel, path: &str) -> Result<(
             return Ok(());
         }
         Err(ContextQueryErrorEnum::EntryNotFoundError(_)) => {}
-        Err(err) => return Err(PortableError::from(err)),
+        Err(err) => return Err(path_error(model, path, err.into())),
     }
 
     match model.get_expression(path) {
@@ -230,57 +215,109 @@ pub fn remove_portable_entry(model: &mut EdgeRulesModel, path: &str) -> Result<(
             return Ok(());
         }
         Err(ContextQueryErrorEnum::EntryNotFoundError(_)) => {}
-        Err(err) => return Err(PortableError::from(err)),
+        Err(err) => return Err(path_error(model, path, err.into())),
     }
 
-    Err(ContextQueryErrorEnum::EntryNotFoundError(path.to_string()).into())
+    Err(path_not_found(model, path))
 }
 
 pub fn get_portable_entry(model: &EdgeRulesModel, path: &str) -> Result<JsValue, PortableError> {
     let (context_path, name) = split_path(path)?;
     if let PathTarget::ArrayElement(array_name, index) = parse_path_target(&name)? {
         let full_array_path = join_path(context_path, &array_name);
-        let entry = model.get_expression(&full_array_path).map_err(PortableError::from)?;
+        let entry = model.get_expression(&full_array_path).map_err(|err| path_error(model, path, err.into()))?;
         let borrowed = entry.borrow();
-
-        match &borrowed.expression {
-            ExpressionEnum::Collection(col) => {
-                if index >= col.elements.len() {
-                    return Err(PortableError::from(ContextQueryErrorEnum::WrongFieldPathError(Some(format!(
-                        "Index {} out of bounds",
-                        index
-                    )))));
-                }
-                return serialize_expression(&col.elements[index]);
-            }
-            _ => {
-                return Err(PortableError::from(ContextQueryErrorEnum::WrongFieldPathError(Some(format!(
-                    "Field '{}' is not an array",
-                    full_array_path
-                )))));
-            }
-        }
+        let col = collection_at(&borrowed.expression, &full_array_path)?;
+        check_index(&full_array_path, index, col.elements.len())?;
+        return serialize_expression(&col.elements[index]);
     }
 
     match model.get_user_type(path) {
         Ok(body) => return serialize_type_body(&body),
         Err(ContextQueryErrorEnum::EntryNotFoundError(_)) => {}
-        Err(err) => return Err(PortableError::from(err)),
+        Err(err) => return Err(path_error(model, path, err.into())),
     }
 
     match model.get_user_function(path) {
         Ok(function) => return serialize_function(&function.borrow().function_definition),
         Err(ContextQueryErrorEnum::EntryNotFoundError(_)) => {}
-        Err(err) => return Err(PortableError::from(err)),
+        Err(err) => return Err(path_error(model, path, err.into())),
     }
 
     match model.get_expression(path) {
         Ok(expression) => return serialize_expression(&expression.borrow().expression),
         Err(ContextQueryErrorEnum::EntryNotFoundError(_)) => {}
-        Err(err) => return Err(PortableError::from(err)),
+        Err(err) => return Err(path_error(model, path, err.into())),
+    }
+
+    Err(path_not_found(model, path))
+}
+
+/// Replaces a generic lookup error with the reason why `path` does not lead to an entry
+fn path_error(model: &EdgeRulesModel, path: &str, err: PortableError) -> PortableError {
+    match err {
+        PortableError::EdgeRulesAPIError(
+            ContextQueryErrorEnum::EntryNotFoundError(_) | ContextQueryErrorEnum::ContextNotFoundError(_),
+        ) => path_not_found(model, path),
+        other => other,
+    }
+}
+
+/// A path traverses a scalar when one of its parent segments resolves to a value instead of an object or array
+fn path_not_found(model: &EdgeRulesModel, path: &str) -> PortableError {
+    let segments: Vec<&str> = path.split('.').map(str::trim).collect();
+    for depth in 1..segments.len() {
+        let parent = segments[..depth].join(".");
+        let (field, indexed) = match parse_path_target(segments[depth - 1]) {
+            Ok(PathTarget::ArrayElement(array_name, _)) => {
+                let context = (depth > 1).then(|| segments[..depth - 1].iter().map(|s| s.to_string()).collect());
+                (join_path(context, &array_name), true)
+            }
+            _ => (parent.clone(), false),
+        };
+        let Ok(entry) = model.get_expression(&field) else {
+            continue;
+        };
+        let is_scalar = match &entry.borrow().expression {
+            ExpressionEnum::StaticObject(_) => false,
+            ExpressionEnum::Collection(_) => !indexed,
+            _ => true,
+        };
+        if is_scalar {
+            return PortableError::PathViolation(parent, PathViolationType::TraversesScalar);
+        }
+    }
+    PortableError::PathViolation(path.to_string(), PathViolationType::NotFound)
+}
+
+fn collection_at<'a>(
+    expression: &'a ExpressionEnum,
+    array_path: &str,
+) -> Result<&'a CollectionExpression, PortableError> {
+    match expression {
+        ExpressionEnum::Collection(col) => Ok(col),
+        _ => Err(PortableError::PathViolation(array_path.to_string(), PathViolationType::TraversesScalar)),
+    }
+}
+
+fn collection_at_mut<'a>(
+    expression: &'a mut ExpressionEnum,
+    array_path: &str,
+) -> Result<&'a mut CollectionExpression, PortableError> {
+    match expression {
+        ExpressionEnum::Collection(col) => Ok(col),
+        _ => Err(PortableError::PathViolation(array_path.to_string(), PathViolationType::TraversesScalar)),
     }
+}
 
-    Err(ContextQueryErrorEnum::EntryNotFoundError(path.to_string()).into())
+fn check_index(array_path: &str, index: usize, len: usize) -> Result<(), PortableError> {
+    if index >= len {
+        return Err(PortableError::PathViolation(
+            format!("{}[{}]", array_path, index),
+            PathViolationType::IndexOutOfRange,
+        ));
+    }
+    Ok(())
 }
 
 fn join_path(context: Option<Vec<String>>, name: &str) -> String {
@@ -296,28 +333,18 @@ fn set_array_element(
     index: usize,
     value: ExpressionEnum,
 ) -> Result<(), PortableError> {
-    let entry = model.get_expression(array_path).map_err(PortableError::from)?;
+    let entry = model.get_expression(array_path).map_err(|err| path_error(model, array_path, err.into()))?;
     let mut borrowed = entry.borrow_mut();
-    match &mut borrowed.expression {
-        ExpressionEnum::Collection(col) => {
-            let len = col.elements.len();
-            if index > len {
-                return Err(PortableError::from(ContextQueryErrorEnum::WrongFieldPathError(Some(format!(
-                    "Index {} is out of bounds for array of length {} (no gaps allowed)",
-                    index, len
-                )))));
-            } else if index == len {
-                col.elements.push(value);
-            } else {
-                col.elements[index] = value;
-            }
-            Ok(())
-        }
-        _ => Err(PortableError::from(ContextQueryErrorEnum::WrongFieldPathError(Some(format!(
-            "Field '{}' is not an array",
-            array_path
-        ))))),
+    let col = collection_at_mut(&mut borrowed.expression, array_path)?;
+    // appending right after the last element is allowed, gaps are not
+    let len = col.elements.len();
+    check_index(array_path, index, len + 1)?;
+    if index == len {
+        col.elements.push(value);
+    } else {
+        col.elements[index] = value;
     }
+    Ok(())
 }
 
 enum PathTarget {
@@ -858,8 +885,7 @@ mod tests {
         // Try [1] on empty -> error
         let val = ExpressionEnum::from(ValueEnum::from(10));
         let err = set_array_element(&mut model, "list", 1, val);
-        assert!(err.is_err());
-        assert!(err.unwrap_err().to_string().contains("out of bounds"));
+        assert!(matches!(err, Err(PortableError::PathViolation(_, PathViolationType::IndexOutOfRange))));
     }
 
     #[test]
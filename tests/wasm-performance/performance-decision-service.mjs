@@ -1,5 +1,6 @@
 import wasm from '../../target/pkg-node/edge_rules.js';
 import { argv } from 'node:process';
+import assert from 'node:assert/strict';
 import {
     parseBenchmarkArgs,
     runSingle,
@@ -8,6 +9,7 @@ import {
     measureIterations,
     logPerformanceSummary,
     logMemoryUsage,
+    quantiles,
 } from './utils.mjs';
 
 wasm.init_panic_hook();
@@ -159,6 +161,31 @@ const REQUEST = {
     ]
 };
 
+// ------ first request: cold vs warmed up service ------
+// Creating a service replaces the previous one, so the benchmarked service is created last
+const FIRST_REQUEST_RUNS = 20;
+const measureFirstRequest = warmUp => {
+    const samplesMs = [];
+    let result;
+    for (let i = 0; i < FIRST_REQUEST_RUNS; i++) {
+        const firstService = new wasm.DecisionService(PORTABLE_MODEL);
+        if (warmUp) {
+            assert.ok(firstService.warmUp(), 'warm up must succeed');
+        }
+        const start = process.hrtime.bigint();
+        result = firstService.execute('applicationDecisions', REQUEST);
+        samplesMs.push(Number(process.hrtime.bigint() - start) / 1e6);
+    }
+    return { result, p50: quantiles(samplesMs).p50 };
+};
+const cold = measureFirstRequest(false);
+const warm = measureFirstRequest(true);
+console.log(`Cold first request p50 over ${FIRST_REQUEST_RUNS} services`, cold.p50.toFixed(3), 'ms');
+console.log(`Warmed up first request p50 over ${FIRST_REQUEST_RUNS} services`, warm.p50.toFixed(3), 'ms');
+assert.deepStrictEqual(warm.result, cold.result, 'warmed up service must return the same result');
+assert.ok(warm.p50 < cold.p50, 'warmed up first request must be faster than a cold one');
+service = new wasm.DecisionService(PORTABLE_MODEL);
+
 // ------ CLI args ------
 // Usage: node node-ds-performance.mjs [iterations] [warmup]
 // defaults: iterations=1000, warmup=10
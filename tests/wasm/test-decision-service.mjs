@@ -198,7 +198,7 @@ describe('DecisionService CRUD', () => {
         assert.deepEqual(service.get('rules[2]'), {id: 4, action: "'D'"});
 
         // Verify length (attempting to get index 3 should fail)
-        assert.throws(() => service.get('rules[3]'), {message: /Index 3 out of bounds/});
+        assert.throws(() => service.get('rules[3]'), {message: /Index out of range at 'rules\[3\]'/});
     });
 });
 
@@ -416,7 +416,7 @@ describe('Decision Service', () => {
             assert.throws(() => {
                 service.get('auditNote');
             }, (err) => {
-                return /Entry 'auditNote' not found/.test(err.message);
+                return /Path not found at 'auditNote'/.test(err.message);
             });
         });
 
@@ -227,17 +227,17 @@ describe('Abnormal Path Handling', () => {
 
         it('throws for non-existent root path', () => {
             const error = getError(() => service.get('nonexistent'));
-            assert.match(error.message, /Entry 'nonexistent' not found/);
+            assert.match(error.message, /Path not found at 'nonexistent'/);
         });
         
         it('throws for non-existent nested leaf', () => {
              const error = getError(() => service.get('nested.ghost'));
-             assert.match(error.message, /Entry 'nested.ghost' not found/);
+             assert.match(error.message, /Path not found at 'nested.ghost'/);
         });
 
         it('throws for non-existent nested parent', () => {
              const error = getError(() => service.get('ghost.child'));
-             assert.match(error.message, /Context 'ghost' not found/);
+             assert.match(error.message, /Path not found at 'ghost.child'/);
         });
     });
 
@@ -301,21 +301,61 @@ describe('Array Access Exceptions', () => {
 
     it('set throws on gap', () => {
         const error = getError(() => service.set('list[4]', 99));
-        assert.match(error.message, /Index 4 is out of bounds for array of length 3/);
+        assert.match(error.message, /Index out of range at 'list\[4\]'/);
     });
 
     it('get throws on out of bounds', () => {
         const error = getError(() => service.get('list[3]'));
-         assert.match(error.message, /Index 3 out of bounds/);
+         assert.match(error.message, /Index out of range at 'list\[3\]'/);
     });
     
     it('remove throws on out of bounds', () => {
         const error = getError(() => service.remove('list[3]'));
-         assert.match(error.message, /Index 3 out of bounds/);
+         assert.match(error.message, /Index out of range at 'list\[3\]'/);
     });
 
     it('set throws if field is not an array', () => {
          const error = getError(() => service.set('scalar[0]', 2));
-         assert.match(error.message, /Field 'scalar' is not an array/);
+         assert.match(error.message, /Path traverses a scalar at 'scalar'/);
+    });
+});
+
+describe('Path Violations', () => {
+    before(() => {
+        wasm.init_panic_hook();
+    });
+
+    const violation = (fn) => {
+        try {
+            fn();
+        } catch (e) {
+            assert.equal(e.type, 'PathViolation');
+            return {path: e.path, violation: e.violation};
+        }
+        assert.fail('Expected a path violation');
+    };
+
+    it('reports a missing path as not found', () => {
+        const service = new wasm.DecisionService({a: {b: 1}});
+
+        assert.deepEqual(violation(() => service.get('a.c')), {path: 'a.c', violation: 'Path not found'});
+        assert.deepEqual(violation(() => service.remove('missing')), {path: 'missing', violation: 'Path not found'});
+        assert.deepEqual(violation(() => service.set('x.y', 1)), {path: 'x.y', violation: 'Path not found'});
     });
-});
\ No newline at end of file
+
+    it('reports a path through a scalar', () => {
+        const service = new wasm.DecisionService({a: 1});
+
+        assert.deepEqual(violation(() => service.get('a.b')), {path: 'a', violation: 'Path traverses a scalar'});
+        assert.deepEqual(violation(() => service.set('a.b', 2)), {path: 'a', violation: 'Path traverses a scalar'});
+        assert.deepEqual(violation(() => service.get('a[0]')), {path: 'a', violation: 'Path traverses a scalar'});
+    });
+
+    it('reports an index out of range', () => {
+        const service = new wasm.DecisionService({list: [1]});
+
+        assert.deepEqual(violation(() => service.get('list[3]')), {path: 'list[3]', violation: 'Index out of range'});
+        assert.deepEqual(violation(() => service.remove('list[1]')), {path: 'list[1]', violation: 'Index out of range'});
+        assert.deepEqual(violation(() => service.set('list[2]', 2)), {path: 'list[2]', violation: 'Index out of range'});
+    });
+});